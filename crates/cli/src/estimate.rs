@@ -0,0 +1,945 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+};
+
+use alloy::primitives::Address;
+use clap::Parser;
+use dry_hint_processor::syscall_handler::{evm, injected_state, starknet, unconstrained};
+use fetcher::{load_mmr_configs, parse_syscall_handler, proof_keys::ProofKeys, FetcherError};
+use indexer_client::models::MMRHasherConfig;
+use serde::{Deserialize, Serialize};
+use syscall_handler::SyscallHandler;
+use thiserror::Error;
+use tracing::{info, warn};
+use types::{
+    budget::BuiltinLimit,
+    chain_registry::ChainRegistryError,
+    proofs::{
+        injected_state::{Action, StateProof},
+        mpt::MPTProof,
+    },
+    report::{ExecutionResourcesReport, OutputFormat},
+    ChainProofs, HashingFunction, ProofsData,
+};
+
+/// Calibration shipped with the CLI. Its figures are placeholders that were not measured from sound runs, so it is
+/// marked unmeasured and estimates relying on it give no `fits` verdict. Replace it with the output of `hdp calibrate`.
+const DEFAULT_CALIBRATION: &str = include_str!("estimate_calibration.json");
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[arg(
+        short = 'i',
+        long = "inputs",
+        default_value = "dry_run_output.json",
+        help = "The output of the dry_run step"
+    )]
+    pub inputs: PathBuf,
+    #[arg(
+        long = "proofs",
+        help = "Path to the fetch-proofs output, counts the actual proof nodes instead of assuming typical proof sizes"
+    )]
+    pub proofs: Option<PathBuf>,
+    #[arg(
+        long = "calibration",
+        help = "Path to a JSON file with measured costs per verifier, overriding the built-in calibration"
+    )]
+    pub calibration: Option<PathBuf>,
+    #[arg(
+        long = "mmr-hasher-config",
+        help = "Path to JSON file containing fetcher config - mapping chain_id -> to mmr_hashing_function"
+    )]
+    pub mmr_hasher_config: Option<PathBuf>,
+    #[arg(
+        long = "measured",
+        help = "Path to the JSON report of the sound run, printed next to the estimate to check the calibration"
+    )]
+    pub measured: Option<PathBuf>,
+    #[arg(long = "max-steps", help = "Report whether the estimated step count fits under this limit")]
+    pub max_steps: Option<u64>,
+    #[arg(
+        long = "max-builtin",
+        value_name = "BUILTIN=COUNT",
+        help = "Report whether the estimated builtin usage fits under this limit (repeatable)"
+    )]
+    pub max_builtins: Vec<BuiltinLimit>,
+    #[arg(
+        long = "output-format",
+        default_value = "pretty",
+        help = "Format of the estimate printed to stdout: `json` on a single line, or `pretty`"
+    )]
+    pub output_format: OutputFormat,
+}
+
+#[derive(Error, Debug)]
+pub enum EstimateError {
+    #[error("Failed to read estimate inputs: {0}")]
+    IO(#[from] std::io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Fetcher(#[from] FetcherError),
+    #[error(transparent)]
+    ChainRegistry(#[from] ChainRegistryError),
+    #[error("No calibration for verifier `{0}`, add it with `--calibration`")]
+    UnknownVerifier(String),
+    #[error("Calibration pairs every --report with a --proofs, got {reports} reports and {proofs} proofs")]
+    UnpairedRuns { reports: usize, proofs: usize },
+    #[error("The runs do not determine the {0} cost, add runs that vary it independently of the others")]
+    Undetermined(String),
+}
+
+/// Cairo steps and builtin instances.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Cost {
+    #[serde(default)]
+    pub steps: u64,
+    #[serde(default)]
+    pub builtins: BTreeMap<String, u64>,
+}
+
+impl From<ExecutionResourcesReport> for Cost {
+    fn from(resources: ExecutionResourcesReport) -> Self {
+        Self {
+            steps: resources.n_steps as u64,
+            builtins: resources
+                .builtin_instance_counter
+                .into_iter()
+                .map(|(builtin, count)| (builtin, count as u64))
+                .collect(),
+        }
+    }
+}
+
+/// The part of a sound run report `--measured` reads.
+#[derive(Deserialize)]
+struct MeasuredRun {
+    execution_resources: ExecutionResourcesReport,
+}
+
+impl Cost {
+    fn add(&mut self, other: &Cost, times: u64) {
+        self.steps += other.steps * times;
+        for (builtin, count) in &other.builtins {
+            *self.builtins.entry(builtin.clone()).or_default() += count * times;
+        }
+    }
+}
+
+/// Measured cost of a verifier: a fixed cost per verified item plus a cost per proof node (MPT node, MMR path element,
+/// trie node), and the node count assumed when only the dry run keys are known.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VerifierCost {
+    /// Whether the costs were fitted from sound runs rather than placeholders.
+    #[serde(default)]
+    pub measured: bool,
+    #[serde(default)]
+    pub per_item: Cost,
+    #[serde(default)]
+    pub per_node: Cost,
+    #[serde(default)]
+    pub typical_nodes: u64,
+}
+
+/// Costs keyed by verifier, e.g. `evm::receipt` or `starknet::mmr_poseidon`. `base` covers the program itself.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Calibration {
+    /// Whether `base` was fitted from sound runs rather than a placeholder.
+    #[serde(default)]
+    pub measured: bool,
+    pub base: Cost,
+    pub verifiers: BTreeMap<String, VerifierCost>,
+}
+
+impl Calibration {
+    /// Loads the built-in calibration, with the entries of `path` replacing the built-in ones.
+    pub fn load(path: Option<&PathBuf>) -> Result<Self, EstimateError> {
+        let mut calibration: Calibration = serde_json::from_str(DEFAULT_CALIBRATION)?;
+        if let Some(path) = path {
+            let overrides: Calibration = serde_json::from_slice(&std::fs::read(path)?)?;
+            calibration.measured = overrides.measured;
+            calibration.base = overrides.base;
+            calibration.verifiers.extend(overrides.verifiers);
+        }
+        Ok(calibration)
+    }
+
+    /// Fits `base` and the `per_item` and `per_node` costs of every verifier seen in `runs` to their measured steps and
+    /// builtins by least squares. A `per_node` cost the runs cannot tell apart from `per_item`, because nodes grow in
+    /// step with items, is folded into `per_item`.
+    pub fn fit(runs: &[CalibrationRun]) -> Result<Self, EstimateError> {
+        let verifiers: Vec<&String> = runs
+            .iter()
+            .flat_map(|run| run.usage.keys())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let builtins: Vec<&String> = runs
+            .iter()
+            .flat_map(|run| run.measured.builtins.keys())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let usage = |run: &CalibrationRun, verifier: &str| run.usage.get(verifier).cloned().unwrap_or_default();
+        let mut unknowns = vec![(Unknown::Base, runs.iter().map(|_| 1.0).collect::<Vec<f64>>())];
+        for verifier in &verifiers {
+            let items = runs.iter().map(|run| usage(run, verifier).items as f64).collect();
+            unknowns.push((Unknown::PerItem(verifier.to_string()), items));
+        }
+        for verifier in &verifiers {
+            let nodes: Vec<f64> = runs
+                .iter()
+                .map(|run| usage(run, verifier).nodes.unwrap_or_default() as f64)
+                .collect();
+            // Verifiers without proof nodes, e.g. headers, have no per node cost
+            if nodes.iter().any(|nodes| *nodes != 0.0) {
+                unknowns.push((Unknown::PerNode(verifier.to_string()), nodes));
+            }
+        }
+        let targets: Vec<Vec<f64>> = runs
+            .iter()
+            .map(|run| {
+                std::iter::once(run.measured.steps)
+                    .chain(
+                        builtins
+                            .iter()
+                            .map(|builtin| run.measured.builtins.get(*builtin).copied().unwrap_or_default()),
+                    )
+                    .map(|value| value as f64)
+                    .collect()
+            })
+            .collect();
+
+        // Normal equations of the least squares fit
+        let a = unknowns
+            .iter()
+            .map(|(_, row)| unknowns.iter().map(|(_, column)| dot(row, column)).collect())
+            .collect();
+        let b = unknowns
+            .iter()
+            .map(|(_, column)| {
+                (0..builtins.len() + 1)
+                    .map(|resource| column.iter().zip(&targets).map(|(x, target)| x * target[resource]).sum())
+                    .collect()
+            })
+            .collect();
+        let (solution, undetermined) = solve(a, b);
+        for index in undetermined {
+            match &unknowns[index].0 {
+                Unknown::PerNode(verifier) => warn!(
+                    "The runs do not determine the per node cost of {}, it is folded into per_item",
+                    verifier
+                ),
+                unknown => return Err(EstimateError::Undetermined(unknown.to_string())),
+            }
+        }
+
+        let cost = |values: &[f64]| Cost {
+            steps: values[0].round().max(0.0) as u64,
+            builtins: builtins
+                .iter()
+                .zip(&values[1..])
+                .map(|(builtin, value)| (builtin.to_string(), value.round().max(0.0) as u64))
+                .filter(|(_, count)| *count != 0)
+                .collect(),
+        };
+        let mut calibration = Calibration {
+            measured: true,
+            base: Cost::default(),
+            verifiers: verifiers
+                .iter()
+                .map(|verifier| {
+                    let (items, nodes) = runs.iter().fold((0, 0), |(items, nodes), run| {
+                        let usage = usage(run, verifier);
+                        (items + usage.items, nodes + usage.nodes.unwrap_or_default())
+                    });
+                    let costs = VerifierCost {
+                        measured: true,
+                        typical_nodes: (nodes as f64 / items.max(1) as f64).round() as u64,
+                        ..Default::default()
+                    };
+                    (verifier.to_string(), costs)
+                })
+                .collect(),
+        };
+        for ((unknown, _), values) in unknowns.iter().zip(&solution) {
+            match unknown {
+                Unknown::Base => calibration.base = cost(values),
+                Unknown::PerItem(verifier) => calibration.verifiers.get_mut(verifier).unwrap().per_item = cost(values),
+                Unknown::PerNode(verifier) => calibration.verifiers.get_mut(verifier).unwrap().per_node = cost(values),
+            }
+        }
+
+        Ok(calibration)
+    }
+}
+
+/// A sound run used to fit a calibration: what it verified, counted from its proofs, and what it used.
+#[derive(Clone, Debug)]
+pub struct CalibrationRun {
+    pub usage: BTreeMap<String, Usage>,
+    pub measured: Cost,
+}
+
+/// A cost fitted by [`Calibration::fit`].
+enum Unknown {
+    Base,
+    PerItem(String),
+    PerNode(String),
+}
+
+impl std::fmt::Display for Unknown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unknown::Base => write!(f, "base"),
+            Unknown::PerItem(verifier) => write!(f, "{} per item", verifier),
+            Unknown::PerNode(verifier) => write!(f, "{} per node", verifier),
+        }
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Solves `a x = b` for every column of `b` by Gaussian elimination with partial pivoting. Unknowns whose column of `a`
+/// depends on the previous ones are left at zero and returned as undetermined.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<Vec<f64>>) -> (Vec<Vec<f64>>, Vec<usize>) {
+    let n = a.len();
+    let columns = b.first().map_or(0, Vec::len);
+    let scale: Vec<f64> = (0..n).map(|i| a[i][i]).collect();
+
+    let (mut pivots, mut undetermined, mut row) = (vec![], vec![], 0);
+    for col in 0..n {
+        let pivot = (row..n).max_by(|x, y| a[*x][col].abs().total_cmp(&a[*y][col].abs()));
+        let Some(pivot) = pivot.filter(|pivot| a[*pivot][col].abs() > scale[col] * 1e-9) else {
+            undetermined.push(col);
+            continue;
+        };
+        a.swap(row, pivot);
+        b.swap(row, pivot);
+        let (pivot_a, pivot_b) = (a[row].clone(), b[row].clone());
+        for other in row + 1..n {
+            let factor = a[other][col] / pivot_a[col];
+            a[other].iter_mut().zip(&pivot_a).for_each(|(x, pivot)| *x -= factor * pivot);
+            b[other].iter_mut().zip(&pivot_b).for_each(|(x, pivot)| *x -= factor * pivot);
+        }
+        pivots.push((row, col));
+        row += 1;
+    }
+
+    let mut x = vec![vec![0.0; columns]; n];
+    for (row, col) in pivots.into_iter().rev() {
+        for column in 0..columns {
+            let rest: f64 = (col + 1..n).map(|j| a[row][j] * x[j][column]).sum();
+            x[col][column] = (b[row][column] - rest) / a[row][col];
+        }
+    }
+    (x, undetermined)
+}
+
+/// Number of items a verifier checks and their proof nodes.
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct Usage {
+    pub items: u64,
+    /// `None` until a proof is counted, the calibrated typical size is used for the missing nodes.
+    pub nodes: Option<u64>,
+}
+
+impl Usage {
+    fn item(&mut self, nodes: Option<usize>) {
+        self.items += 1;
+        if let Some(nodes) = nodes {
+            self.nodes = Some(self.nodes.unwrap_or_default() + nodes as u64);
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct VerifierEstimate {
+    pub verifier: String,
+    pub items: u64,
+    pub nodes: u64,
+    pub cost: Cost,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Estimate {
+    /// `proofs` if proof sizes were counted from `proofs.json`, `dry_run` if typical sizes were assumed.
+    pub source: &'static str,
+    pub verifiers: Vec<VerifierEstimate>,
+    pub total: Cost,
+    /// `base` and the verifiers costed with placeholder figures rather than measured ones.
+    pub unmeasured: Vec<String>,
+    /// Whether the estimate fits under every `--max-steps` and `--max-builtin` limit, `None` while `unmeasured` is not
+    /// empty.
+    pub fits: Option<bool>,
+    /// Limits the estimate exceeds.
+    pub exceeded: Vec<String>,
+    /// What the sound run actually used, read from `--measured`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub measured: Option<Cost>,
+}
+
+impl Estimate {
+    pub fn new(usage: BTreeMap<String, Usage>, calibration: &Calibration, source: &'static str) -> Result<Self, EstimateError> {
+        let mut total = calibration.base.clone();
+        let mut unmeasured = match calibration.measured {
+            true => vec![],
+            false => vec!["base".to_string()],
+        };
+        let verifiers = usage
+            .into_iter()
+            .map(|(verifier, usage)| {
+                let costs = calibration
+                    .verifiers
+                    .get(&verifier)
+                    .ok_or_else(|| EstimateError::UnknownVerifier(verifier.clone()))?;
+                if !costs.measured {
+                    unmeasured.push(verifier.clone());
+                }
+                let nodes = usage.nodes.unwrap_or(usage.items * costs.typical_nodes);
+                let mut cost = Cost::default();
+                cost.add(&costs.per_item, usage.items);
+                cost.add(&costs.per_node, nodes);
+                total.add(&cost, 1);
+                Ok(VerifierEstimate {
+                    verifier,
+                    items: usage.items,
+                    nodes,
+                    cost,
+                })
+            })
+            .collect::<Result<_, EstimateError>>()?;
+
+        Ok(Self {
+            source,
+            verifiers,
+            total,
+            unmeasured,
+            fits: None,
+            exceeded: vec![],
+            measured: None,
+        })
+    }
+
+    /// Checks the estimate against the limits. Placeholder costs say nothing about a real run, so no verdict is given
+    /// while any are involved.
+    fn check_limits(&mut self, max_steps: Option<u64>, max_builtins: &[BuiltinLimit]) {
+        if !self.unmeasured.is_empty() {
+            if max_steps.is_some() || !max_builtins.is_empty() {
+                warn!(
+                    "Not checking limits, the costs of {} are placeholders, calibrate them with `hdp calibrate`",
+                    self.unmeasured.join(", ")
+                );
+            }
+            return;
+        }
+        if max_steps.is_some_and(|max_steps| self.total.steps > max_steps) {
+            self.exceeded.push("steps".to_string());
+        }
+        for BuiltinLimit { builtin, limit } in max_builtins {
+            if self.total.builtins.get(builtin.to_str()).is_some_and(|used| *used > *limit as u64) {
+                self.exceeded.push(format!("{} builtin", builtin.to_str()));
+            }
+        }
+        self.fits = Some(self.exceeded.is_empty());
+    }
+}
+
+fn mmr_verifier(chain: &str, hasher: HashingFunction) -> String {
+    match hasher {
+        HashingFunction::Poseidon => format!("{}::mmr_poseidon", chain),
+        HashingFunction::Keccak => format!("{}::mmr_keccak", chain),
+    }
+}
+
+fn mpt_nodes(proofs: &[MPTProof]) -> usize {
+    proofs.iter().map(|proof| proof.proof.len()).sum()
+}
+
+/// Counts what the sound run will verify from the dry run keys alone, proof sizes are unknown. The MMR of each chain is
+/// costed with the hasher the fetcher will use for it.
+fn usage_from_keys(proof_keys: &ProofKeys, mmr_hasher_config: &MMRHasherConfig) -> Result<BTreeMap<String, Usage>, EstimateError> {
+    let mut usage: BTreeMap<String, Usage> = BTreeMap::new();
+    let hasher = |chain_id: u128| {
+        mmr_hasher_config
+            .get(&chain_id)
+            .copied()
+            .ok_or(ChainRegistryError::UnknownChain(chain_id))
+    };
+
+    let evm_headers: HashSet<(u128, u64)> = proof_keys
+        .evm
+        .header_keys
+        .iter()
+        .map(|key| (key.chain_id, key.block_number))
+        .chain(proof_keys.evm.account_keys.iter().map(|key| (key.chain_id, key.block_number)))
+        .chain(proof_keys.evm.storage_keys.iter().map(|key| (key.chain_id, key.block_number)))
+        .chain(proof_keys.evm.receipt_keys.iter().map(|key| (key.chain_id, key.block_number)))
+        .chain(proof_keys.evm.transaction_keys.iter().map(|key| (key.chain_id, key.block_number)))
+        .collect();
+    for (chain_id, _) in evm_headers {
+        usage.entry("evm::header".to_string()).or_default().item(None);
+        usage.entry(mmr_verifier("evm", hasher(chain_id)?)).or_default().item(None);
+    }
+    // A storage slot is proven against the storage root of its account, so the fetcher proves that account as well
+    let evm_accounts: HashSet<(u128, u64, Address)> = proof_keys
+        .evm
+        .account_keys
+        .iter()
+        .map(|key| (key.chain_id, key.block_number, key.address))
+        .chain(
+            proof_keys
+                .evm
+                .storage_keys
+                .iter()
+                .map(|key| (key.chain_id, key.block_number, key.address)),
+        )
+        .collect();
+    for _ in evm_accounts {
+        usage.entry("evm::account".to_string()).or_default().item(None);
+    }
+    for _ in &proof_keys.evm.storage_keys {
+        usage.entry("evm::storage_item".to_string()).or_default().item(None);
+    }
+    for _ in &proof_keys.evm.receipt_keys {
+        usage.entry("evm::receipt".to_string()).or_default().item(None);
+    }
+    for _ in &proof_keys.evm.transaction_keys {
+        usage.entry("evm::transaction".to_string()).or_default().item(None);
+    }
+
+    let starknet_headers: HashSet<(u128, u64)> = proof_keys
+        .starknet
+        .header_keys
+        .iter()
+        .map(|key| (key.chain_id, key.block_number))
+        .chain(proof_keys.starknet.storage_keys.iter().map(|key| (key.chain_id, key.block_number)))
+        .collect();
+    for (chain_id, _) in starknet_headers {
+        usage.entry("starknet::header".to_string()).or_default().item(None);
+        usage.entry(mmr_verifier("starknet", hasher(chain_id)?)).or_default().item(None);
+    }
+    for _ in &proof_keys.starknet.storage_keys {
+        usage.entry("starknet::storage".to_string()).or_default().item(None);
+    }
+
+    for action in proof_keys.injected_state.values().flatten() {
+        let verifier = match action {
+            Action::Read(_) => "injected_state::inclusion",
+            Action::Write(_) | Action::Delete(_) => "injected_state::update",
+        };
+        usage.entry(verifier.to_string()).or_default().item(None);
+    }
+
+    Ok(usage)
+}
+
+/// Counts what the sound run will verify from the fetched proofs, including the size of every proof.
+fn usage_from_proofs(proofs_data: &ProofsData) -> BTreeMap<String, Usage> {
+    let mut usage: BTreeMap<String, Usage> = BTreeMap::new();
+
+    for chain_proofs in &proofs_data.chain_proofs {
+        match chain_proofs {
+            ChainProofs::Evm { proofs, .. } => {
+                for header_with_mmr in &proofs.headers_with_mmr {
+                    let mmr = mmr_verifier("evm", header_with_mmr.mmr_meta.hasher);
+                    for header in &header_with_mmr.headers {
+                        usage.entry("evm::header".to_string()).or_default().item(Some(0));
+                        usage.entry(mmr.clone()).or_default().item(Some(header.proof.mmr_path.len()));
+                    }
+                }
+                for account in &proofs.accounts {
+                    usage
+                        .entry("evm::account".to_string())
+                        .or_default()
+                        .item(Some(mpt_nodes(&account.proofs)));
+                }
+                for storage in &proofs.storages {
+                    usage
+                        .entry("evm::storage_item".to_string())
+                        .or_default()
+                        .item(Some(mpt_nodes(&storage.proofs)));
+                }
+                for receipt in &proofs.transaction_receipts {
+                    usage
+                        .entry("evm::receipt".to_string())
+                        .or_default()
+                        .item(Some(receipt.proof.proof.len()));
+                }
+                for transaction in &proofs.transactions {
+                    usage
+                        .entry("evm::transaction".to_string())
+                        .or_default()
+                        .item(Some(transaction.proof.proof.len()));
+                }
+            }
+            ChainProofs::Starknet { proofs, .. } => {
+                for header_with_mmr in &proofs.headers_with_mmr {
+                    let mmr = mmr_verifier("starknet", header_with_mmr.mmr_meta.hasher);
+                    for header in &header_with_mmr.headers {
+                        usage.entry("starknet::header".to_string()).or_default().item(Some(0));
+                        usage.entry(mmr.clone()).or_default().item(Some(header.proof.mmr_path.len()));
+                    }
+                }
+                for storage in &proofs.storages {
+                    let nodes = storage.output.contracts_proof.nodes.0.len()
+                        + storage
+                            .output
+                            .contracts_storage_proofs
+                            .iter()
+                            .map(|proof| proof.0.len())
+                            .sum::<usize>();
+                    usage.entry("starknet::storage".to_string()).or_default().item(Some(nodes));
+                }
+            }
+        }
+    }
+
+    for state_proof in &proofs_data.state_proofs {
+        let (verifier, nodes) = match state_proof {
+            StateProof::Read(proof) => ("injected_state::inclusion", proof.state_proof.len()),
+            StateProof::Write(proof) => (
+                "injected_state::update",
+                proof.state_proof_prev.len() + proof.state_proof_post.len(),
+            ),
+            StateProof::Delete(proof) => (
+                "injected_state::update",
+                proof.state_proof_prev.len() + proof.state_proof_post.len(),
+            ),
+        };
+        usage.entry(verifier.to_string()).or_default().item(Some(nodes));
+    }
+
+    usage
+}
+
+pub fn run_with_args(args: Args) -> Result<(), EstimateError> {
+    let calibration = Calibration::load(args.calibration.as_ref())?;
+
+    let mut estimate = match &args.proofs {
+        Some(path) => {
+            info!("Reading proofs from: {}", path.display());
+            let proofs_data: ProofsData = serde_json::from_slice(&std::fs::read(path)?)?;
+            Estimate::new(usage_from_proofs(&proofs_data), &calibration, "proofs")?
+        }
+        None => {
+            info!("Reading dry run output from: {}", args.inputs.display());
+            let syscall_handler: SyscallHandler<
+                evm::CallContractHandler,
+                starknet::CallContractHandler,
+                injected_state::CallContractHandler,
+                unconstrained::CallContractHandler,
+            > = serde_json::from_slice(&std::fs::read(&args.inputs)?)?;
+            let proof_keys = parse_syscall_handler(syscall_handler)?;
+            let (mmr_hasher_config, _) = load_mmr_configs(args.mmr_hasher_config.as_ref(), None)?;
+            Estimate::new(usage_from_keys(&proof_keys, &mmr_hasher_config)?, &calibration, "dry_run")?
+        }
+    };
+    estimate.check_limits(args.max_steps, &args.max_builtins);
+    if let Some(path) = &args.measured {
+        info!("Reading sound run report from: {}", path.display());
+        let measured: MeasuredRun = serde_json::from_slice(&std::fs::read(path)?)?;
+        estimate.measured = Some(measured.execution_resources.into());
+    }
+
+    let report = match args.output_format {
+        OutputFormat::Json => serde_json::to_string(&estimate)?,
+        OutputFormat::Pretty => serde_json::to_string_pretty(&estimate)?,
+    };
+    println!("{}", report);
+
+    Ok(())
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct CalibrateArgs {
+    #[arg(
+        long = "report",
+        required = true,
+        help = "Path to the JSON report of a sound run, paired in order with --proofs (repeatable)"
+    )]
+    pub reports: Vec<PathBuf>,
+    #[arg(
+        long = "proofs",
+        required = true,
+        help = "Path to the fetch-proofs output the sound run consumed, paired in order with --report (repeatable)"
+    )]
+    pub proofs: Vec<PathBuf>,
+    #[arg(
+        short = 'o',
+        long = "output",
+        default_value = "calibration.json",
+        help = "Path to write the fitted calibration to, for `hdp estimate --calibration`"
+    )]
+    pub output: PathBuf,
+}
+
+pub fn calibrate_with_args(args: CalibrateArgs) -> Result<(), EstimateError> {
+    if args.reports.len() != args.proofs.len() {
+        return Err(EstimateError::UnpairedRuns {
+            reports: args.reports.len(),
+            proofs: args.proofs.len(),
+        });
+    }
+
+    let runs = args
+        .reports
+        .iter()
+        .zip(&args.proofs)
+        .map(|(report, proofs)| {
+            info!("Reading sound run report {} and proofs {}", report.display(), proofs.display());
+            let measured: MeasuredRun = serde_json::from_slice(&std::fs::read(report)?)?;
+            let proofs_data: ProofsData = serde_json::from_slice(&std::fs::read(proofs)?)?;
+            Ok(CalibrationRun {
+                usage: usage_from_proofs(&proofs_data),
+                measured: measured.execution_resources.into(),
+            })
+        })
+        .collect::<Result<Vec<_>, EstimateError>>()?;
+
+    let calibration = Calibration::fit(&runs)?;
+    for (run, report) in runs.iter().zip(&args.reports) {
+        let estimate = Estimate::new(run.usage.clone(), &calibration, "proofs")?;
+        info!(
+            "{}: measured {} steps, fitted {}",
+            report.display(),
+            run.measured.steps,
+            estimate.total.steps
+        );
+    }
+
+    std::fs::write(&args.output, serde_json::to_string_pretty(&calibration)?)?;
+    info!("Calibration written to: {}", args.output.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use types::keys;
+
+    use super::*;
+
+    const VERIFIERS: [&str; 13] = [
+        "evm::header",
+        "evm::mmr_keccak",
+        "evm::mmr_poseidon",
+        "evm::account",
+        "evm::storage_item",
+        "evm::receipt",
+        "evm::transaction",
+        "starknet::header",
+        "starknet::mmr_keccak",
+        "starknet::mmr_poseidon",
+        "starknet::storage",
+        "injected_state::inclusion",
+        "injected_state::update",
+    ];
+
+    fn cost(steps: u64, range_check: u64) -> Cost {
+        Cost {
+            steps,
+            builtins: BTreeMap::from([("range_check".to_string(), range_check)]),
+        }
+    }
+
+    fn calibration() -> Calibration {
+        Calibration {
+            measured: true,
+            base: cost(100, 10),
+            verifiers: BTreeMap::from([(
+                "evm::account".to_string(),
+                VerifierCost {
+                    measured: true,
+                    per_item: cost(1000, 50),
+                    per_node: cost(200, 20),
+                    typical_nodes: 3,
+                },
+            )]),
+        }
+    }
+
+    fn usage(items: u64, nodes: Option<u64>) -> BTreeMap<String, Usage> {
+        BTreeMap::from([("evm::account".to_string(), Usage { items, nodes })])
+    }
+
+    fn header_keys(chain_id: u128) -> ProofKeys {
+        let mut proof_keys = ProofKeys::default();
+        proof_keys.evm.header_keys.insert(keys::evm::header::Key {
+            chain_id,
+            block_number: 7692344,
+        });
+        proof_keys
+    }
+
+    #[test]
+    fn default_calibration_covers_every_verifier() {
+        let calibration = Calibration::load(None).unwrap();
+        for verifier in VERIFIERS {
+            assert!(calibration.verifiers.contains_key(verifier), "missing {}", verifier);
+        }
+        assert!(
+            !calibration.measured && calibration.verifiers.values().all(|costs| !costs.measured),
+            "the built-in costs are placeholders"
+        );
+    }
+
+    #[test]
+    fn placeholder_costs_give_no_verdict() {
+        let mut placeholder = calibration();
+        placeholder.verifiers.get_mut("evm::account").unwrap().measured = false;
+        let mut estimate = Estimate::new(usage(1, Some(0)), &placeholder, "proofs").unwrap();
+        estimate.check_limits(Some(1), &[]);
+        assert_eq!(estimate.unmeasured, vec!["evm::account"]);
+        assert_eq!(estimate.fits, None);
+        assert!(estimate.exceeded.is_empty());
+
+        let mut estimate = Estimate::new(usage(1, Some(0)), &Calibration::load(None).unwrap(), "proofs").unwrap();
+        estimate.check_limits(Some(1), &[]);
+        assert_eq!(estimate.unmeasured, vec!["base", "evm::account"]);
+        assert_eq!(estimate.fits, None);
+    }
+
+    #[test]
+    fn typical_nodes_are_assumed_without_proofs() {
+        let estimate = Estimate::new(usage(2, None), &calibration(), "dry_run").unwrap();
+        assert_eq!(estimate.verifiers[0].nodes, 6);
+        assert_eq!(estimate.verifiers[0].cost, cost(2 * 1000 + 6 * 200, 2 * 50 + 6 * 20));
+        assert_eq!(estimate.total, cost(100 + 2 * 1000 + 6 * 200, 10 + 2 * 50 + 6 * 20));
+    }
+
+    #[test]
+    fn counted_nodes_replace_typical_nodes() {
+        let estimate = Estimate::new(usage(2, Some(11)), &calibration(), "proofs").unwrap();
+        assert_eq!(estimate.verifiers[0].nodes, 11);
+        assert_eq!(estimate.total, cost(100 + 2 * 1000 + 11 * 200, 10 + 2 * 50 + 11 * 20));
+    }
+
+    #[test]
+    fn uncalibrated_verifier_fails() {
+        let usage = BTreeMap::from([("evm::receipt".to_string(), Usage { items: 1, nodes: None })]);
+        assert!(matches!(
+            Estimate::new(usage, &calibration(), "dry_run"),
+            Err(EstimateError::UnknownVerifier(verifier)) if verifier == "evm::receipt"
+        ));
+    }
+
+    #[test]
+    fn limits_report_exceeded_resources() {
+        let mut estimate = Estimate::new(usage(1, Some(0)), &calibration(), "proofs").unwrap();
+        estimate.check_limits(Some(1100), &["range_check=60".parse().unwrap()]);
+        assert_eq!(estimate.fits, Some(true));
+
+        estimate.check_limits(Some(1099), &["range_check=60".parse().unwrap(), "range_check=59".parse().unwrap()]);
+        assert_eq!(estimate.fits, Some(false));
+        assert_eq!(estimate.exceeded, vec!["steps", "range_check builtin"]);
+    }
+
+    #[test]
+    fn mmr_verifier_follows_hasher_config() {
+        let keccak: MMRHasherConfig = serde_json::from_str(r#"{"11155111": "keccak"}"#).unwrap();
+        let usage = usage_from_keys(&header_keys(11155111), &keccak).unwrap();
+        assert_eq!(usage.keys().collect::<Vec<_>>(), vec!["evm::header", "evm::mmr_keccak"]);
+
+        let poseidon: MMRHasherConfig = serde_json::from_str(r#"{"11155111": "poseidon"}"#).unwrap();
+        let usage = usage_from_keys(&header_keys(11155111), &poseidon).unwrap();
+        assert_eq!(usage.keys().collect::<Vec<_>>(), vec!["evm::header", "evm::mmr_poseidon"]);
+    }
+
+    #[test]
+    fn chain_missing_from_hasher_config_fails() {
+        let config: MMRHasherConfig = serde_json::from_str("{}").unwrap();
+        assert!(matches!(
+            usage_from_keys(&header_keys(11155111), &config),
+            Err(EstimateError::ChainRegistry(ChainRegistryError::UnknownChain(11155111)))
+        ));
+    }
+
+    #[test]
+    fn measured_report_reads_execution_resources() {
+        let report = r#"{"output": [], "execution_resources": {"n_steps": 1234, "n_memory_holes": 5,
+            "builtin_instance_counter": {"range_check": 42}}, "step_count": null}"#;
+        let measured: MeasuredRun = serde_json::from_str(report).unwrap();
+        assert_eq!(Cost::from(measured.execution_resources), cost(1234, 42));
+    }
+
+    /// What a run verifying `accounts` accounts with `nodes` proof nodes in total and `headers` headers uses under
+    /// `calibration`.
+    fn run(calibration: &Calibration, accounts: u64, nodes: u64, headers: u64) -> CalibrationRun {
+        let usage = BTreeMap::from([
+            (
+                "evm::account".to_string(),
+                Usage {
+                    items: accounts,
+                    nodes: Some(nodes),
+                },
+            ),
+            (
+                "evm::header".to_string(),
+                Usage {
+                    items: headers,
+                    nodes: Some(0),
+                },
+            ),
+        ]);
+        let measured = Estimate::new(usage.clone(), calibration, "proofs").unwrap().total;
+        CalibrationRun { usage, measured }
+    }
+
+    fn two_verifiers() -> Calibration {
+        let mut calibration = calibration();
+        calibration.verifiers.insert(
+            "evm::header".to_string(),
+            VerifierCost {
+                measured: true,
+                per_item: cost(9000, 450),
+                per_node: Cost::default(),
+                typical_nodes: 0,
+            },
+        );
+        calibration
+    }
+
+    #[test]
+    fn fit_recovers_costs() {
+        let expected = two_verifiers();
+        let runs = [
+            run(&expected, 1, 3, 1),
+            run(&expected, 2, 5, 1),
+            run(&expected, 2, 7, 2),
+            run(&expected, 4, 12, 3),
+        ];
+
+        // 27 nodes over 9 accounts gives the expected 3 typical nodes
+        assert_eq!(Calibration::fit(&runs).unwrap(), expected);
+    }
+
+    #[test]
+    fn fit_folds_per_node_cost_into_per_item_when_nodes_follow_items() {
+        let expected = two_verifiers();
+        let runs = [run(&expected, 1, 3, 1), run(&expected, 2, 6, 1), run(&expected, 3, 9, 2)];
+
+        let fitted = Calibration::fit(&runs).unwrap();
+        assert_eq!(fitted.verifiers["evm::account"].per_item, cost(1000 + 3 * 200, 50 + 3 * 20));
+        assert_eq!(fitted.verifiers["evm::account"].per_node, Cost::default());
+        assert_eq!(fitted.base, expected.base);
+    }
+
+    #[test]
+    fn fit_fails_when_runs_do_not_determine_a_verifier() {
+        let expected = two_verifiers();
+        // Every run verifies as many headers as accounts
+        let runs = [run(&expected, 1, 3, 1), run(&expected, 2, 5, 2), run(&expected, 3, 10, 3)];
+
+        assert!(matches!(
+            Calibration::fit(&runs),
+            Err(EstimateError::Undetermined(unknown)) if unknown == "evm::header per item"
+        ));
+    }
+}
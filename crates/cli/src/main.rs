@@ -15,15 +15,19 @@ use cairo_vm::{
     program_hash::compute_program_hash_chain,
 };
 use clap::{Parser, Subcommand};
-use dry_hint_processor as _;
+use dry_hint_processor::rpc_fixture::FixtureMode;
 use dry_run::Program;
 use indexer_client as _;
 use serde_json as _;
 use sound_run::HDP_COMPILED_JSON;
-use syscall_handler as _;
 use tracing::{self as _, info, level_filters::LevelFilter};
 use tracing_subscriber::EnvFilter;
-use types::error::Error;
+use types::{chain_registry::ChainRegistry, error::Error};
+
+mod estimate;
+#[cfg(feature = "stwo")]
+mod prove;
+mod run;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about)]
@@ -37,6 +41,11 @@ struct Cli {
     #[arg(long = "debug")]
     debug: bool,
 
+    /// Path to the chain registry config file (TOML or JSON) mapping chain ids to RPC URLs and MMR settings.
+    /// Can also be set via HDP_CHAIN_REGISTRY environment variable. Defaults to the built-in chains.
+    #[arg(long = "chain-registry", value_name = "PATH", global = true)]
+    chain_registry: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -65,6 +74,23 @@ enum Commands {
     /// Run the sound-run functionality
     #[command(name = "sound-run")]
     SoundRun(sound_run::Args),
+    /// Run dry-run, fetch-proofs and sound-run in a single process
+    #[command(name = "run")]
+    Run(run::Args),
+    /// Estimate the steps and builtins a sound run needs, per verifier, from the dry-run output
+    #[command(name = "estimate")]
+    Estimate(estimate::Args),
+    /// Fit the per verifier costs used by `estimate` from sound run reports and their proofs
+    #[command(name = "calibrate")]
+    Calibrate(estimate::CalibrateArgs),
+    /// Prove a proof mode sound run with the stwo-cairo prover (CPU)
+    #[cfg(feature = "stwo")]
+    #[command(name = "prove")]
+    Prove(prove::ProveArgs),
+    /// Verify a proof written by `prove` and check its public output
+    #[cfg(feature = "stwo")]
+    #[command(name = "verify")]
+    Verify(prove::VerifyArgs),
     /// Get program hash
     #[command(name = "program-hash")]
     ProgramHash {
@@ -95,9 +121,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     setup_tracing(cli.log_level.as_ref(), cli.debug)?;
-
-    match cli.command {
-        Commands::DryRun(_) | Commands::FetchProofs(_) | Commands::SoundRun(_) => check_env()?,
+    ChainRegistry::init_global_from(cli.chain_registry.as_deref())?;
+
+    match &cli.command {
+        // Replaying a fixture needs no rpc endpoints
+        Commands::DryRun(args) if args.rpc_fixture.is_some() && args.rpc_fixture_mode == FixtureMode::Replay => {}
+        Commands::FetchProofs(args) if args.rpc_fixture.is_some() && args.rpc_fixture_mode == FixtureMode::Replay => {}
+        Commands::Run(args) if args.rpc_fixture.is_some() && args.rpc_fixture_mode == FixtureMode::Replay => {}
+        Commands::DryRun(_) | Commands::FetchProofs(_) | Commands::SoundRun(_) | Commands::Run(_) => check_env()?,
         _ => {}
     }
 
@@ -105,6 +136,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::DryRun(args) => dry_run::run_with_args(args).await?,
         Commands::FetchProofs(args) => fetcher::run_with_args(args).await?,
         Commands::SoundRun(args) => sound_run::run_with_args(args).await?,
+        Commands::Run(args) => run::run_with_args(args).await?,
+        Commands::Estimate(args) => estimate::run_with_args(args)?,
+        Commands::Calibrate(args) => estimate::calibrate_with_args(args)?,
+        #[cfg(feature = "stwo")]
+        Commands::Prove(args) => prove::prove_with_args(args)?,
+        #[cfg(feature = "stwo")]
+        Commands::Verify(args) => prove::verify_with_args(args)?,
         Commands::ProgramHash { program } => {
             let program_file = std::fs::read(program.unwrap_or(PathBuf::from(HDP_COMPILED_JSON))).map_err(Error::IO)?;
             let program = Program::from_bytes(&program_file, Some(cairo_run::CairoRunConfig::default().entrypoint))?;
@@ -297,6 +335,8 @@ fn setup_tracing(log_level: Option<&String>, debug: bool) -> Result<(), Box<dyn
 
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::builder().with_default_directive(level_filter.into()).from_env_lossy())
+        // Keep stdout for the run report
+        .with_writer(std::io::stderr)
         .init();
 
     Ok(())
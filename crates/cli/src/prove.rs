@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use cairo_vm::{cairo_run::CairoRunConfig, types::program::Program};
+use clap::Parser;
+use serde::Deserialize;
+use sound_run::{
+    prove::{prove, verify, ExpectedOutput, StwoProof},
+    HDP_COMPILED_JSON,
+};
+use stwo_cairo_adapter::ProverInput;
+use tracing::info;
+use types::{error::Error, report::OutputFormat, results::HDPResults, HDPOutput};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct ProveArgs {
+    #[arg(
+        long = "stwo_prover_input",
+        default_value = "stwo_prover_input.json",
+        help = "Path to the prover input written by a proof mode sound run"
+    )]
+    pub stwo_prover_input: PathBuf,
+    #[arg(long = "proof", default_value = "proof.json", help = "Path where the proof will be written")]
+    pub proof: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct VerifyArgs {
+    #[arg(long = "proof", default_value = "proof.json", help = "Path to the proof written by `prove`")]
+    pub proof: PathBuf,
+    #[arg(
+        long = "results",
+        required_unless_present = "report",
+        conflicts_with = "report",
+        help = "Path to the results file of the sound run, checked against the proven task hash and results root"
+    )]
+    pub results: Option<PathBuf>,
+    #[arg(
+        long = "report",
+        help = "Path to the JSON report of the sound run (`--print_output`), its output is checked against the proven output"
+    )]
+    pub report: Option<PathBuf>,
+    #[arg(long = "program", help = "Path to the compiled HDP program the proof must have run")]
+    pub program: Option<PathBuf>,
+    #[arg(
+        long = "output-format",
+        default_value = "pretty",
+        help = "Format of the verified HDP output printed to stdout: `json` on a single line, or `pretty`"
+    )]
+    pub output_format: OutputFormat,
+}
+
+/// The part of a sound run report `verify --report` reads.
+#[derive(Deserialize)]
+struct SoundRunReport {
+    output: HDPOutput,
+}
+
+pub fn prove_with_args(args: ProveArgs) -> Result<(), Error> {
+    info!("Reading prover input from: {}", args.stwo_prover_input.display());
+    let input: ProverInput = serde_json::from_slice(&std::fs::read(&args.stwo_prover_input)?)?;
+
+    let proof = prove(input)?;
+    std::fs::write(&args.proof, serde_json::to_string(&proof)?)?;
+    info!("Proof saved to: {}", args.proof.display());
+
+    Ok(())
+}
+
+pub fn verify_with_args(args: VerifyArgs) -> Result<(), Error> {
+    info!("Reading proof from: {}", args.proof.display());
+    let proof: StwoProof = serde_json::from_slice(&std::fs::read(&args.proof)?)?;
+
+    let program_path = args.program.unwrap_or(PathBuf::from(HDP_COMPILED_JSON));
+    info!("Reading HDP program from: {}", program_path.display());
+    let program = Program::from_bytes(&std::fs::read(program_path)?, Some(CairoRunConfig::default().entrypoint))?;
+
+    let expected = match (args.results, args.report) {
+        (Some(path), _) => ExpectedOutput::Results(serde_json::from_slice::<HDPResults>(&std::fs::read(path)?)?),
+        (None, Some(path)) => ExpectedOutput::Output(serde_json::from_slice::<SoundRunReport>(&std::fs::read(path)?)?.output),
+        (None, None) => unreachable!("clap requires --results or --report"),
+    };
+
+    let output = verify(proof, &program, &expected)?;
+    info!("Proven output matches the sound run.");
+
+    let output = match args.output_format {
+        OutputFormat::Json => serde_json::to_string(&output)?,
+        OutputFormat::Pretty => serde_json::to_string_pretty(&output)?,
+    };
+    println!("{}", output);
+
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use dry_hint_processor::{
+    rpc_fixture::FixtureMode,
+    syscall_handler::{evm, injected_state, starknet, unconstrained},
+};
+use dry_run::{init_rpc_fixture, DRY_RUN_COMPILED_JSON};
+use fetcher::{cache::ProofCache, load_mmr_configs, parse_syscall_handler, Fetcher, FetcherError};
+use sound_run::HDP_COMPILED_JSON;
+use syscall_handler::SyscallHandler;
+use thiserror::Error;
+use tracing::info;
+use types::{
+    budget::ResourceBudget,
+    error::Error,
+    param::Param,
+    report::{OutputFormat, RunArtifacts, RunReport},
+    CasmContractClass, HDPDryRunInput, HDPInput, InjectedState,
+};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Args {
+    #[arg(long = "dry_run_program", help = "Path to the compiled dry run hdp program")]
+    pub dry_run_program: Option<PathBuf>,
+    #[arg(long = "sound_run_program", help = "Path to the compiled hdp program")]
+    pub sound_run_program: Option<PathBuf>,
+    #[arg(short = 'm', long = "compiled_module", help = "Path to the compiled module file")]
+    pub compiled_module: PathBuf,
+    #[arg(short = 'i', long = "inputs", help = "Path to the JSON file containing input parameters")]
+    pub inputs: Option<PathBuf>,
+    #[arg(
+        short = 's',
+        long = "injected_state",
+        help = "Path to the JSON file containing injected_state parameters"
+    )]
+    pub injected_state: Option<PathBuf>,
+    #[arg(
+        long = "rpc-fixture",
+        help = "Path to the rpc fixture folder dry run responses and proofs are recorded to or replayed from"
+    )]
+    pub rpc_fixture: Option<PathBuf>,
+    #[arg(
+        long = "rpc-fixture-mode",
+        default_value = "replay",
+        requires = "rpc_fixture",
+        help = "`record` rpc responses and proofs to the fixture, or `replay` them without network access"
+    )]
+    pub rpc_fixture_mode: FixtureMode,
+    #[arg(
+        long = "mmr-hasher-config",
+        help = "Path to JSON file containing fetcher config - mapping chain_id -> to mmr_hashing_function"
+    )]
+    pub mmr_hasher_config: Option<PathBuf>,
+    #[arg(
+        long = "mmr-deployment-config",
+        help = "Path to JSON file containing fetcher config - mapping chain_id -> to chain_id"
+    )]
+    pub mmr_deployment_config: Option<PathBuf>,
+    #[arg(
+        long = "proof-cache",
+        help = "Path to the SQLite proof cache reused across runs, only use with finalized blocks (e.g. db/proof_cache.db)"
+    )]
+    pub proof_cache: Option<PathBuf>,
+    #[arg(
+        long = "keep_artifacts",
+        default_value_t = false,
+        help = "Write the intermediate dry run output and proofs files [default: false]"
+    )]
+    pub keep_artifacts: bool,
+    #[arg(
+        long = "dry_run_output",
+        default_value = "dry_run_output.json",
+        help = "Path where the dry run output will be written when keeping artifacts"
+    )]
+    pub dry_run_output: PathBuf,
+    #[arg(
+        long = "proofs",
+        default_value = "proofs.json",
+        help = "Path where the proofs will be written when keeping artifacts"
+    )]
+    pub proofs: PathBuf,
+    #[arg(
+        long = "print_output",
+        default_value_t = false,
+        help = "Print the run report to stdout [default: false]"
+    )]
+    pub print_output: bool,
+    #[arg(
+        long = "output-format",
+        default_value = "pretty",
+        help = "Format of the run report printed with --print_output: `json` on a single line, or `pretty`"
+    )]
+    pub output_format: OutputFormat,
+    #[arg(
+        long = "results",
+        default_value = "results.json",
+        help = "Path where the module results and their inclusion proofs against the output tree root will be written"
+    )]
+    pub results: PathBuf,
+    #[arg(long = "proof_mode", conflicts_with = "cairo_pie", help = "Configure runner in proof mode")]
+    pub proof_mode: bool,
+    #[arg(
+        long = "cairo_pie",
+        default_value = None,
+        conflicts_with = "proof_mode",
+        help = "Path where the Cairo PIE zip file will be written"
+    )]
+    pub cairo_pie: Option<PathBuf>,
+    #[arg(
+        long = "stwo_prover_input",
+        default_value = None,
+        requires = "proof_mode",
+        conflicts_with = "cairo_pie",
+        help = "Path where the STWO prover input file will be written"
+    )]
+    pub stwo_prover_input: Option<PathBuf>,
+    // Applied to the dry run and the sound run alike
+    #[command(flatten)]
+    pub budget: ResourceBudget,
+}
+
+/// Error of the `run` pipeline, tagged with the stage that produced it.
+#[derive(Error, Debug)]
+pub enum RunError {
+    #[error("Failed to read run inputs: {0}")]
+    Inputs(#[source] Error),
+    #[error("Dry run failed: {0}")]
+    DryRun(#[source] Error),
+    #[error("Fetching proofs failed: {0}")]
+    FetchProofs(#[source] FetcherError),
+    #[error("Sound run failed: {0}")]
+    SoundRun(#[source] Error),
+}
+
+pub async fn run_with_args(args: Args) -> Result<(), RunError> {
+    info!("Reading compiled module from: {}", args.compiled_module.display());
+    let (compiled_class, params, injected_state) = read_inputs(&args).map_err(RunError::Inputs)?;
+
+    info!("Starting dry run execution...");
+    init_rpc_fixture(args.rpc_fixture.as_deref(), args.rpc_fixture_mode).map_err(RunError::DryRun)?;
+    let (syscall_handler, _) = dry_run::run(
+        args.dry_run_program.clone().unwrap_or(PathBuf::from(DRY_RUN_COMPILED_JSON)),
+        HDPDryRunInput {
+            compiled_class: compiled_class.clone(),
+            params: params.clone(),
+            injected_state: injected_state.clone(),
+        },
+        args.budget.clone(),
+    )
+    .map_err(RunError::DryRun)?;
+
+    if args.keep_artifacts {
+        info!("Writing dry run output to: {}", args.dry_run_output.display());
+        std::fs::write(
+            &args.dry_run_output,
+            serde_json::to_vec::<
+                SyscallHandler<
+                    evm::CallContractHandler,
+                    starknet::CallContractHandler,
+                    injected_state::CallContractHandler,
+                    unconstrained::CallContractHandler,
+                >,
+            >(&syscall_handler)
+            .map_err(|e| RunError::DryRun(e.into()))?,
+        )
+        .map_err(|e| RunError::DryRun(e.into()))?;
+    }
+
+    info!("Starting fetcher execution...");
+    let proof_keys = parse_syscall_handler(syscall_handler).map_err(RunError::FetchProofs)?;
+    let (mmr_hasher_config, mmr_deployment_config) =
+        load_mmr_configs(args.mmr_hasher_config.as_ref(), args.mmr_deployment_config.as_ref()).map_err(RunError::FetchProofs)?;
+    let mut fetcher = Fetcher::new(&proof_keys, mmr_hasher_config, mmr_deployment_config);
+    if let Some(path) = &args.proof_cache {
+        fetcher = fetcher.with_proof_cache(ProofCache::open(path).map_err(RunError::FetchProofs)?);
+    }
+    let proofs_data = fetcher.collect_proofs().await.map_err(RunError::FetchProofs)?;
+
+    if args.keep_artifacts {
+        info!("Writing proofs to: {}", args.proofs.display());
+        std::fs::write(
+            &args.proofs,
+            serde_json::to_string_pretty(&proofs_data).map_err(|e| RunError::FetchProofs(e.into()))?,
+        )
+        .map_err(|e| RunError::FetchProofs(e.into()))?;
+    }
+
+    info!("Starting sound run execution...");
+    let (cairo_runner, output, results) = sound_run::run(
+        args.sound_run_program.unwrap_or(PathBuf::from(HDP_COMPILED_JSON)),
+        sound_run::cairo_run_config(args.proof_mode),
+        HDPInput {
+            chain_proofs: proofs_data.chain_proofs,
+            compiled_class,
+            params,
+            state_proofs: proofs_data.state_proofs,
+            injected_state,
+            unconstrained: proofs_data.unconstrained,
+        },
+        args.budget,
+    )
+    .map_err(RunError::SoundRun)?;
+
+    sound_run::write_results(&results, &args.results).map_err(RunError::SoundRun)?;
+    sound_run::write_artifacts(
+        &cairo_runner,
+        args.proof_mode,
+        args.cairo_pie.as_deref(),
+        args.stwo_prover_input.as_deref(),
+    )
+    .map_err(RunError::SoundRun)?;
+
+    if args.print_output {
+        let artifacts = RunArtifacts {
+            dry_run_output: args.keep_artifacts.then_some(args.dry_run_output),
+            proofs: args.keep_artifacts.then_some(args.proofs),
+            results: Some(args.results),
+            cairo_pie: args.cairo_pie,
+            stwo_prover_input: args.stwo_prover_input,
+        };
+        RunReport::new(output, &cairo_runner, artifacts)
+            .and_then(|report| report.print(args.output_format))
+            .map_err(RunError::SoundRun)?;
+    }
+
+    info!("Run completed successfully.");
+
+    Ok(())
+}
+
+fn read_inputs(args: &Args) -> Result<(CasmContractClass, Vec<Param>, InjectedState), Error> {
+    let compiled_class: CasmContractClass = serde_json::from_slice(&std::fs::read(&args.compiled_module)?)?;
+    let params: Vec<Param> = if let Some(ref path) = args.inputs {
+        serde_json::from_slice(&std::fs::read(path)?)?
+    } else {
+        Vec::new()
+    };
+    let injected_state: InjectedState = if let Some(ref path) = args.injected_state {
+        serde_json::from_slice(&std::fs::read(path)?)?
+    } else {
+        InjectedState::default()
+    };
+
+    Ok((compiled_class, params, injected_state))
+}
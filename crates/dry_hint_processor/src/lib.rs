@@ -4,7 +4,9 @@
 #![forbid(unsafe_code)]
 
 pub mod input;
+pub mod oracle;
 pub mod output;
+pub mod rpc_fixture;
 pub mod syscall_handler;
 
 use std::{any::Any, collections::HashMap};
@@ -28,7 +30,10 @@ use hints::{extensive_hints, hints, vars, ExtensiveHintImpl, HintImpl};
 use starknet_types_core::felt::Felt;
 use syscall_handler::{evm, starknet};
 use tokio::{runtime::Handle, task};
-use types::HDPDryRunInput;
+use types::{
+    budget::{BudgetTracker, ResourceBudget},
+    HDPDryRunInput,
+};
 
 use crate::syscall_handler::{injected_state, unconstrained};
 
@@ -38,6 +43,7 @@ pub struct CustomHintProcessor {
     cairo1_builtin_hint_proc: Cairo1HintProcessor,
     hints: HashMap<String, HintImpl>,
     extensive_hints: HashMap<String, ExtensiveHintImpl>,
+    budget: BudgetTracker,
 }
 
 impl CustomHintProcessor {
@@ -48,9 +54,20 @@ impl CustomHintProcessor {
             cairo1_builtin_hint_proc: Cairo1HintProcessor::new(Default::default(), Default::default(), true),
             hints: Self::hints(),
             extensive_hints: Self::extensive_hints(),
+            budget: BudgetTracker::default(),
         }
     }
 
+    /// Caps the resources the run may use, see [`ResourceBudget`].
+    pub fn with_budget(mut self, budget: ResourceBudget) -> Self {
+        self.budget = BudgetTracker::new(budget);
+        self
+    }
+
+    pub fn budget(&self) -> &BudgetTracker {
+        &self.budget
+    }
+
     #[rustfmt::skip]
     fn hints() -> HashMap<String, HintImpl> {
         let mut hints = hints();
@@ -85,6 +102,8 @@ impl HintProcessorLogic for CustomHintProcessor {
         hint_data: &Box<dyn Any>,
         constants: &HashMap<String, Felt>,
     ) -> Result<HintExtension, HintError> {
+        self.budget.consume_builtins(vm)?;
+
         if let Some(hpd) = hint_data.downcast_ref::<HintProcessorData>() {
             let hint_code = hpd.code.as_str();
 
@@ -115,6 +134,7 @@ impl HintProcessorLogic for CustomHintProcessor {
 
         if let Some(hint) = hint_data.downcast_ref::<Hint>() {
             if let Hint::Starknet(StarknetHint::SystemCall { system }) = hint {
+                self.budget.consume_syscall()?;
                 let syscall_ptr = get_ptr_from_res_operand(vm, system)?;
                 let syscall_handler = exec_scopes.get_mut_ref::<SyscallHandlerWrapper<
                     evm::CallContractHandler,
@@ -142,7 +162,15 @@ impl HintProcessorLogic for CustomHintProcessor {
     }
 }
 
-impl ResourceTracker for CustomHintProcessor {}
+impl ResourceTracker for CustomHintProcessor {
+    fn consumed(&self) -> bool {
+        self.budget.consumed()
+    }
+
+    fn consume_step(&mut self) {
+        self.budget.consume_step();
+    }
+}
 
 fn get_ptr_from_res_operand(vm: &mut VirtualMachine, res: &ResOperand) -> Result<Relocatable, HintError> {
     let (cell, base_offset) = match res {
@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+};
+
+use alloy::primitives::U256;
+use syscall_handler::{SyscallExecutionError, SyscallResult};
+use types::{cairo::unconstrained::oracle::OracleValue, Felt252};
+
+static ORACLE_REGISTRY: OnceLock<OracleRegistry> = OnceLock::new();
+
+/// `'sort_permutation'`: returns the indices that sort the input felts in ascending order.
+pub const SORT_PERMUTATION_ORACLE_ID: Felt252 = Felt252::from_hex_unchecked("0x736f72745f7065726d75746174696f6e");
+/// `'mod_inverse'`: takes `[a.low, a.high, n.low, n.high]` and returns `[inv.low, inv.high]` with `a * inv = 1 mod n`.
+pub const MOD_INVERSE_ORACLE_ID: Felt252 = Felt252::from_hex_unchecked("0x6d6f645f696e7665727365");
+
+pub type OracleFuture<'a> = Pin<Box<dyn Future<Output = SyscallResult<OracleValue>> + Send + 'a>>;
+
+/// An off-chain witness provider callable from Cairo modules through the unconstrained memorizer.
+///
+/// Oracles only run during the dry run. Their values are stored with the fetched proofs and handed
+/// to the sound run unchanged, so modules must check every witness they receive.
+pub trait Oracle: Send + Sync {
+    /// Identifier the Cairo SDK selects the oracle with, usually a short string.
+    fn id(&self) -> Felt252;
+
+    /// Computes the witness for `input`.
+    fn resolve<'a>(&'a self, input: &'a [Felt252]) -> OracleFuture<'a>;
+}
+
+#[derive(Clone)]
+pub struct OracleRegistry {
+    oracles: HashMap<Felt252, Arc<dyn Oracle>>,
+}
+
+impl Default for OracleRegistry {
+    fn default() -> Self {
+        Self::empty().with(SortPermutationOracle).with(ModInverseOracle)
+    }
+}
+
+impl OracleRegistry {
+    /// A registry without the built-in oracles.
+    pub fn empty() -> Self {
+        Self { oracles: HashMap::new() }
+    }
+
+    /// Adds `oracle`, replacing any oracle registered under the same id.
+    pub fn with(mut self, oracle: impl Oracle + 'static) -> Self {
+        self.oracles.insert(oracle.id(), Arc::new(oracle));
+        self
+    }
+
+    /// Returns the process wide registry, holding the built-in oracles if
+    /// [`OracleRegistry::init_global`] was not called.
+    pub fn global() -> &'static Self {
+        ORACLE_REGISTRY.get_or_init(Self::default)
+    }
+
+    pub fn init_global(registry: Self) -> SyscallResult<()> {
+        ORACLE_REGISTRY
+            .set(registry)
+            .map_err(|_| SyscallExecutionError::InternalError("oracle registry has already been initialized".into()))
+    }
+
+    pub async fn resolve(&self, oracle_id: Felt252, input: &[Felt252]) -> SyscallResult<OracleValue> {
+        let oracle = self.oracles.get(&oracle_id).ok_or(SyscallExecutionError::InvalidSyscallInput {
+            input: oracle_id,
+            info: "Unknown oracle".to_string(),
+        })?;
+        oracle.resolve(input).await
+    }
+}
+
+pub struct SortPermutationOracle;
+
+impl Oracle for SortPermutationOracle {
+    fn id(&self) -> Felt252 {
+        SORT_PERMUTATION_ORACLE_ID
+    }
+
+    fn resolve<'a>(&'a self, input: &'a [Felt252]) -> OracleFuture<'a> {
+        Box::pin(async move {
+            let mut indices: Vec<usize> = (0..input.len()).collect();
+            indices.sort_by_key(|&i| input[i]);
+            Ok(OracleValue(indices.into_iter().map(Felt252::from).collect()))
+        })
+    }
+}
+
+pub struct ModInverseOracle;
+
+impl Oracle for ModInverseOracle {
+    fn id(&self) -> Felt252 {
+        MOD_INVERSE_ORACLE_ID
+    }
+
+    fn resolve<'a>(&'a self, input: &'a [Felt252]) -> OracleFuture<'a> {
+        Box::pin(async move {
+            let [a_low, a_high, n_low, n_high] = input else {
+                return Err(SyscallExecutionError::InternalError(
+                    format!("mod_inverse expects 4 input felts, got {}", input.len()).into(),
+                ));
+            };
+            let value = u256_from_felts(*a_low, *a_high)?;
+            let modulus = u256_from_felts(*n_low, *n_high)?;
+            let inverse = value
+                .inv_mod(modulus)
+                .ok_or(SyscallExecutionError::InternalError("mod_inverse: value is not invertible".into()))?;
+
+            let [low, high] = [inverse & U256::from(u128::MAX), inverse >> 128].map(|limb| Felt252::from(limb.to::<u128>()));
+            Ok(OracleValue(vec![low, high]))
+        })
+    }
+}
+
+fn u256_from_felts(low: Felt252, high: Felt252) -> SyscallResult<U256> {
+    let [low, high] = [low, high].map(|limb| {
+        u128::try_from(limb).map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: limb,
+            info: format!("{}", e),
+        })
+    });
+    Ok((U256::from(high?) << 128) | U256::from(low?))
+}
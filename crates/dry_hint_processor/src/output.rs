@@ -9,6 +9,7 @@ use cairo_vm::{
     Felt252,
 };
 use hints::vars;
+use tracing::info;
 
 use super::CustomHintProcessor;
 
@@ -30,7 +31,7 @@ impl CustomHintProcessor {
             .map(|v| v.get_int().unwrap())
             .collect::<Vec<Felt252>>();
 
-        println!("result: {}, {}", result[0], result[1]);
+        info!("result: {}, {}", result[0], result[1]);
         Ok(())
     }
 }
@@ -0,0 +1,228 @@
+use std::{
+    env,
+    future::Future,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use alloy::primitives::keccak256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use syscall_handler::SyscallExecutionError;
+use thiserror::Error;
+use tracing::debug;
+
+/// Environment variable holding the path of the rpc fixture folder.
+pub const RPC_FIXTURE_PATH: &str = "HDP_RPC_FIXTURE";
+/// Environment variable selecting the fixture mode, `record` or `replay` (default).
+pub const RPC_FIXTURE_MODE: &str = "HDP_RPC_FIXTURE_MODE";
+
+static RPC_FIXTURE: OnceLock<Result<Option<RpcFixture>, String>> = OnceLock::new();
+
+#[derive(Debug, Error)]
+#[error("Rpc fixture error: {0}")]
+pub struct RpcFixtureError(String);
+
+impl From<RpcFixtureError> for SyscallExecutionError {
+    fn from(e: RpcFixtureError) -> Self {
+        SyscallExecutionError::InternalError(e.to_string().into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FixtureMode {
+    /// Forwards requests to the network and saves every response to the fixture.
+    Record,
+    /// Serves responses from the fixture, failing on requests that were not recorded.
+    #[default]
+    Replay,
+}
+
+impl FromStr for FixtureMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            _ => Err(format!("invalid rpc fixture mode `{}`, expected `record` or `replay`", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    request: String,
+    response: T,
+}
+
+/// Folder of recorded rpc responses, one file per request keyed by the hash of its
+/// chain id, method and params.
+///
+/// Lets the dry run and the proof fetching be replayed deterministically without any network
+/// access. Storing every request in its own file keeps concurrent recordings (e.g. parallel test
+/// processes) from overwriting each other.
+#[derive(Debug)]
+pub struct RpcFixture {
+    path: PathBuf,
+    mode: FixtureMode,
+}
+
+impl RpcFixture {
+    /// Opens the fixture folder at `path`, creating it in record mode.
+    pub fn open(path: impl AsRef<Path>, mode: FixtureMode) -> Result<Self, RpcFixtureError> {
+        let path = path.as_ref().to_path_buf();
+        match mode {
+            FixtureMode::Record => std::fs::create_dir_all(&path),
+            FixtureMode::Replay => std::fs::read_dir(&path).map(|_| ()),
+        }
+        .map_err(|e| RpcFixtureError(format!("{}: {}", path.display(), e)))?;
+        debug!("using rpc fixture {} in {:?} mode", path.display(), mode);
+
+        Ok(Self { path, mode })
+    }
+
+    /// Returns the process wide fixture, read from the `HDP_RPC_FIXTURE` and
+    /// `HDP_RPC_FIXTURE_MODE` env vars if [`RpcFixture::init_global`] was not called.
+    ///
+    /// An invalid mode or a fixture folder that cannot be opened fails every rpc request.
+    pub fn global() -> Result<Option<&'static Self>, RpcFixtureError> {
+        RPC_FIXTURE
+            .get_or_init(|| {
+                let Ok(path) = env::var(RPC_FIXTURE_PATH) else {
+                    return Ok(None);
+                };
+                let mode = env::var(RPC_FIXTURE_MODE)
+                    .ok()
+                    .map(|mode| mode.parse())
+                    .transpose()?
+                    .unwrap_or_default();
+                Self::open(path, mode)
+                    .map(Some)
+                    .map_err(|e| format!("failed to open rpc fixture: {}", e))
+            })
+            .as_ref()
+            .map(Option::as_ref)
+            .map_err(|e| RpcFixtureError(e.clone()))
+    }
+
+    pub fn init_global(fixture: Self) -> Result<(), RpcFixtureError> {
+        RPC_FIXTURE
+            .set(Ok(Some(fixture)))
+            .map_err(|_| RpcFixtureError("rpc fixture has already been initialized".to_string()))
+    }
+
+    fn request(chain_id: u128, method: &str, params: &impl Serialize) -> Result<String, RpcFixtureError> {
+        let params = serde_json::to_string(params).map_err(|e| RpcFixtureError(e.to_string()))?;
+        Ok(format!("{:#x}:{}:{}", chain_id, method, params))
+    }
+
+    fn entry_path(&self, request: &str) -> PathBuf {
+        self.path.join(format!("{}.json", keccak256(request.as_bytes())))
+    }
+
+    fn get<T: DeserializeOwned>(&self, request: &str) -> Result<T, RpcFixtureError> {
+        let content = std::fs::read(self.entry_path(request))
+            .map_err(|e| RpcFixtureError(format!("no recorded rpc response for {}: {}", request, e)))?;
+        let entry: Entry<T> = serde_json::from_slice(&content).map_err(|e| RpcFixtureError(e.to_string()))?;
+        Ok(entry.response)
+    }
+
+    fn insert<T: Serialize>(&self, request: String, response: &T) -> Result<(), RpcFixtureError> {
+        let path = self.entry_path(&request);
+        let content = serde_json::to_vec_pretty(&Entry { request, response }).map_err(|e| RpcFixtureError(e.to_string()))?;
+        std::fs::write(path, content).map_err(|e| RpcFixtureError(e.to_string()))
+    }
+
+    /// Resolves a request through this fixture, see [`recorded`].
+    pub async fn resolve<T, P, E, Fut>(&self, chain_id: u128, method: &str, params: P, fetch: Fut) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        P: Serialize,
+        E: From<RpcFixtureError>,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let request = Self::request(chain_id, method, &params)?;
+        match self.mode {
+            FixtureMode::Replay => Ok(self.get(&request)?),
+            FixtureMode::Record => {
+                let response = fetch.await?;
+                self.insert(request, &response)?;
+                Ok(response)
+            }
+        }
+    }
+}
+
+/// Resolves an rpc request through the global fixture, if one is configured.
+///
+/// In replay mode `fetch` is never awaited, so no rpc url has to be configured.
+pub async fn recorded<T, P, E, Fut>(chain_id: u128, method: &str, params: P, fetch: Fut) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+    P: Serialize,
+    E: From<RpcFixtureError>,
+    Fut: Future<Output = Result<T, E>>,
+{
+    match RpcFixture::global()? {
+        Some(fixture) => fixture.resolve(chain_id, method, params, fetch).await,
+        None => fetch.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    async fn fetch_counted(calls: &AtomicUsize, value: u64) -> Result<u64, RpcFixtureError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn replays_recorded_responses_without_fetching() {
+        let path = env::temp_dir().join(format!("hdp_rpc_fixture_{}", std::process::id()));
+        let calls = AtomicUsize::new(0);
+
+        let fixture = RpcFixture::open(&path, FixtureMode::Record).unwrap();
+        let recorded = fixture
+            .resolve(1, "eth_getProof", (7692344, "0x01"), fetch_counted(&calls, 7))
+            .await;
+        assert_eq!(recorded.unwrap(), 7);
+
+        let fixture = RpcFixture::open(&path, FixtureMode::Replay).unwrap();
+        let replayed = fixture
+            .resolve(1, "eth_getProof", (7692344, "0x01"), fetch_counted(&calls, 8))
+            .await;
+        assert_eq!(replayed.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other params were never recorded, replaying them fails instead of fetching
+        let missing = fixture
+            .resolve(1, "eth_getProof", (7692345, "0x01"), fetch_counted(&calls, 9))
+            .await;
+        assert!(missing.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_not_recorded() {
+        let path = env::temp_dir().join(format!("hdp_rpc_fixture_failed_{}", std::process::id()));
+        let fixture = RpcFixture::open(&path, FixtureMode::Record).unwrap();
+
+        let failed: Result<u64, _> = fixture
+            .resolve(1, "eth_getProof", 7692344, async { Err(RpcFixtureError("rpc down".to_string())) })
+            .await;
+        assert!(failed.is_err());
+
+        let fixture = RpcFixture::open(&path, FixtureMode::Replay).unwrap();
+        let replayed: Result<u64, RpcFixtureError> = fixture.resolve(1, "eth_getProof", 7692344, async { Ok(7) }).await;
+        assert!(replayed.is_err());
+
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -1,18 +1,17 @@
 use alloy::{
-    network::Ethereum,
-    providers::{Provider, RootProvider},
-    transports::http::reqwest::Url,
+    consensus::EMPTY_ROOT_HASH,
+    primitives::{B256, KECCAK256_EMPTY},
+    providers::Provider,
 };
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
     cairo::{evm::account::FunctionId, structs::Uint256, traits::CairoType},
-    keys::evm::{
-        account::{CairoKey, Key},
-        get_corresponding_rpc_url,
-    },
+    keys::evm::account::{CairoKey, Key},
 };
 
+use super::with_recorded_provider;
+
 #[derive(Debug, Default)]
 pub struct AccountCallHandler;
 
@@ -41,31 +40,44 @@ impl CallHandler for AccountCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        let value = match function_id {
-            FunctionId::Balance => provider
-                .get_balance(key.address)
-                .block_id(key.block_number.into())
-                .await
-                .map(Uint256::from),
-            FunctionId::Nonce => provider
-                .get_transaction_count(key.address)
-                .block_id(key.block_number.into())
-                .await
-                .map(Uint256::from),
-            FunctionId::StateRoot => provider
-                .get_proof(key.address, vec![])
-                .block_id(key.block_number.into())
-                .await
-                .map(|f| Uint256::from(f.storage_hash)),
-            FunctionId::CodeHash => provider
-                .get_proof(key.address, vec![])
-                .block_id(key.block_number.into())
-                .await
-                .map(|f| Uint256::from(f.code_hash)),
+        let key = &key;
+        let params = (key.address, key.block_number);
+        match function_id {
+            FunctionId::Balance => with_recorded_provider(key.chain_id, "eth_getBalance", params, |provider| async move {
+                provider
+                    .get_balance(key.address)
+                    .block_id(key.block_number.into())
+                    .await
+                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+            })
+            .await
+            .map(Uint256::from),
+            FunctionId::Nonce => with_recorded_provider(key.chain_id, "eth_getTransactionCount", params, |provider| async move {
+                provider
+                    .get_transaction_count(key.address)
+                    .block_id(key.block_number.into())
+                    .await
+                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+            })
+            .await
+            .map(Uint256::from),
+            FunctionId::StateRoot | FunctionId::CodeHash => {
+                let proof = with_recorded_provider(key.chain_id, "eth_getProof", params, |provider| async move {
+                    provider
+                        .get_proof(key.address, vec![])
+                        .block_id(key.block_number.into())
+                        .await
+                        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+                })
+                .await?;
+                // Some nodes return zero hashes for accounts that do not exist, the exclusion proof reads them as the empty account
+                match function_id {
+                    FunctionId::StateRoot if proof.storage_hash == B256::ZERO => Ok(Uint256::from(EMPTY_ROOT_HASH)),
+                    FunctionId::StateRoot => Ok(Uint256::from(proof.storage_hash)),
+                    _ if proof.code_hash == B256::ZERO => Ok(Uint256::from(KECCAK256_EMPTY)),
+                    _ => Ok(Uint256::from(proof.code_hash)),
+                }
+            }
         }
-        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        Ok(value)
     }
 }
@@ -0,0 +1,93 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    providers::Provider,
+    rpc::types::{Block, Header, TransactionReceipt},
+};
+use syscall_handler::{SyscallExecutionError, SyscallResult};
+
+use super::with_recorded_provider;
+
+type BlockKey = (u128, u64);
+
+/// Blocks and block receipts fetched during a dry run, shared by the EVM call handlers.
+///
+/// Each block is fetched at most once for its header, once with its full transactions and once
+/// for its receipts, no matter how many headers, transactions, receipts or logs of it the module reads.
+#[derive(Debug, Default, Clone)]
+pub struct BlockCache {
+    headers: Arc<RwLock<HashMap<BlockKey, Arc<Header>>>>,
+    blocks: Arc<RwLock<HashMap<BlockKey, Arc<Block>>>>,
+    receipts: Arc<RwLock<HashMap<BlockKey, Arc<Vec<TransactionReceipt>>>>>,
+}
+
+impl BlockCache {
+    /// Returns the header of the block `block_number` of `chain_id`, without fetching its transactions.
+    pub async fn header(&self, chain_id: u128, block_number: u64) -> SyscallResult<Arc<Header>> {
+        if let Some(header) = self.headers.read().unwrap().get(&(chain_id, block_number)) {
+            return Ok(header.clone());
+        }
+        if let Some(block) = self.blocks.read().unwrap().get(&(chain_id, block_number)) {
+            return Ok(Arc::new(block.header.clone()));
+        }
+
+        let header = with_recorded_provider(chain_id, "eth_getBlockByNumber", block_number, |provider| async move {
+            provider
+                .get_block(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+                .hashes()
+                .await
+                .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
+                .ok_or(SyscallExecutionError::InternalError("Block not found".into()))
+        })
+        .await
+        .map(|block| Arc::new(block.header))?;
+
+        self.headers.write().unwrap().insert((chain_id, block_number), header.clone());
+        Ok(header)
+    }
+
+    /// Returns the block `block_number` of `chain_id` with its full transactions.
+    pub async fn block(&self, chain_id: u128, block_number: u64) -> SyscallResult<Arc<Block>> {
+        if let Some(block) = self.blocks.read().unwrap().get(&(chain_id, block_number)) {
+            return Ok(block.clone());
+        }
+
+        let block = with_recorded_provider(chain_id, "eth_getBlockByNumber_full", block_number, |provider| async move {
+            provider
+                .get_block(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+                .full()
+                .await
+                .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
+                .ok_or(SyscallExecutionError::InternalError("Block not found".into()))
+        })
+        .await
+        .map(Arc::new)?;
+
+        self.blocks.write().unwrap().insert((chain_id, block_number), block.clone());
+        Ok(block)
+    }
+
+    /// Returns the receipts of every transaction in the block `block_number` of `chain_id`.
+    pub async fn receipts(&self, chain_id: u128, block_number: u64) -> SyscallResult<Arc<Vec<TransactionReceipt>>> {
+        if let Some(receipts) = self.receipts.read().unwrap().get(&(chain_id, block_number)) {
+            return Ok(receipts.clone());
+        }
+
+        let receipts = with_recorded_provider(chain_id, "eth_getBlockReceipts", block_number, |provider| async move {
+            provider
+                .get_block_receipts(BlockId::Number(BlockNumberOrTag::Number(block_number)))
+                .await
+                .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
+                .ok_or(SyscallExecutionError::InternalError("Block receipts not found".into()))
+        })
+        .await
+        .map(Arc::new)?;
+
+        self.receipts.write().unwrap().insert((chain_id, block_number), receipts.clone());
+        Ok(receipts)
+    }
+}
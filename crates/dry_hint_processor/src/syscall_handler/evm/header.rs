@@ -1,8 +1,3 @@
-use alloy::{
-    network::Ethereum,
-    providers::{Provider, RootProvider},
-    transports::http::reqwest::Url,
-};
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
@@ -10,14 +5,21 @@ use types::{
         evm::header::{CairoHeader, FunctionId},
         traits::CairoType,
     },
-    keys::evm::{
-        get_corresponding_rpc_url,
-        header::{CairoKey, Key},
-    },
+    keys::evm::header::{CairoKey, Key},
 };
 
+use super::block_cache::BlockCache;
+
 #[derive(Debug, Default)]
-pub struct HeaderCallHandler;
+pub struct HeaderCallHandler {
+    block_cache: BlockCache,
+}
+
+impl HeaderCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+}
 
 #[allow(refining_impl_trait)]
 impl CallHandler for HeaderCallHandler {
@@ -44,13 +46,7 @@ impl CallHandler for HeaderCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        let value = provider
-            .get_block_by_number(key.block_number.into())
-            .await
-            .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
-            .ok_or(SyscallExecutionError::InternalError("Block not found".into()))?;
-        Ok(CairoHeader::from(value.header.inner).handle(function_id))
+        let header = self.block_cache.header(key.chain_id, key.block_number).await?;
+        Ok(CairoHeader::from(header.inner.clone()).handle(function_id))
     }
 }
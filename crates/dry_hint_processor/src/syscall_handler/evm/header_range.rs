@@ -0,0 +1,63 @@
+use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
+use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{
+        evm::header::{CairoHeader, FunctionId},
+        traits::CairoType,
+    },
+    keys::evm::header_range::{CairoKey, Key},
+};
+
+use super::block_cache::BlockCache;
+
+#[derive(Debug, Default)]
+pub struct HeaderRangeCallHandler {
+    block_cache: BlockCache,
+}
+
+impl HeaderRangeCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl CallHandler for HeaderRangeCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        match Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?) {
+            // Only fields fitting a single Uint256 can be packed into the range result
+            Some(FunctionId::Bloom | FunctionId::ExtraData) => Err(SyscallExecutionError::InvalidSyscallInput {
+                input: selector,
+                info: "Variable length header fields are not supported in header ranges".to_string(),
+            }),
+            Some(function_id) => Ok(function_id),
+            None => Err(SyscallExecutionError::InvalidSyscallInput {
+                input: selector,
+                info: "Invalid function identifier".to_string(),
+            }),
+        }
+    }
+
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        let mut result = Vec::new();
+        for header_key in key.header_keys() {
+            let header = self.block_cache.header(header_key.chain_id, header_key.block_number).await?;
+            result.extend(CairoHeader::from(header.inner.clone()).handle(function_id));
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,85 @@
+use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
+use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{
+        evm::{
+            log::CairoReceiptWithBloom,
+            log_absence::{bloom_may_contain, supports_receipt_count, FunctionId},
+        },
+        traits::CairoType,
+    },
+    keys::evm::log_absence::{CairoKey, Key},
+};
+
+use super::block_cache::BlockCache;
+
+#[derive(Debug, Default)]
+pub struct LogAbsenceCallHandler {
+    block_cache: BlockCache,
+}
+
+impl LogAbsenceCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+}
+
+impl CallHandler for LogAbsenceCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?)
+        .ok_or(SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: "Invalid function identifier".to_string(),
+        })
+    }
+
+    /// Returns the number of receipts needed to prove the absence, zero when the header bloom
+    /// already rules the log out.
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        match function_id {
+            FunctionId::NoMatchingLog => {
+                let header = self.block_cache.header(key.chain_id, key.block_number).await?;
+                if !bloom_may_contain(&header.inner.logs_bloom, key.address, key.topic0) {
+                    return Ok(vec![Felt252::ZERO]);
+                }
+                if !supports_receipt_count(key.chain_id) {
+                    return Err(SyscallExecutionError::InternalError(
+                        format!("the receipts of chain {} can not be proven complete", key.chain_id).into(),
+                    ));
+                }
+
+                let receipts = self.block_cache.receipts(key.chain_id, key.block_number).await?;
+                for (transaction_index, receipt) in receipts.iter().enumerate() {
+                    let receipt = match receipt.inner.as_receipt_with_bloom() {
+                        Some(receipt) => CairoReceiptWithBloom::from(receipt.clone()),
+                        None => return Err(SyscallExecutionError::InternalError("Receipt not found".into())),
+                    };
+                    if let Some(log_index) = receipt.matching_logs(key.address, key.topic0).next() {
+                        return Err(SyscallExecutionError::InternalError(
+                            format!(
+                                "matching log found in block {} at transaction {} log {}",
+                                key.block_number, transaction_index, log_index
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+                Ok(vec![Felt252::from(receipts.len())])
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
+use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{
+        evm::{
+            log::CairoReceiptWithBloom,
+            log_absence::{bloom_may_contain, supports_receipt_count},
+            log_filter::{FunctionId, LogFilterResult, LogPosition},
+        },
+        traits::CairoType,
+    },
+    keys::evm::log_filter::{CairoKey, Key},
+};
+
+use super::block_cache::BlockCache;
+
+#[derive(Debug, Default)]
+pub struct LogsByFilterCallHandler {
+    block_cache: BlockCache,
+}
+
+impl LogsByFilterCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+
+    /// Returns the number of receipts of every block in the filtered range, zero when the header bloom
+    /// rules the log out. The sound run scans these receipts completely.
+    pub async fn transactions_counts(&self, key: &Key) -> SyscallResult<Vec<u64>> {
+        let mut transactions_counts = Vec::new();
+        for header_key in key.header_keys() {
+            let header = self.block_cache.header(header_key.chain_id, header_key.block_number).await?;
+            if !bloom_may_contain(&header.inner.logs_bloom, key.address, key.topic0) {
+                transactions_counts.push(0);
+                continue;
+            }
+            if !supports_receipt_count(key.chain_id) {
+                return Err(SyscallExecutionError::InternalError(
+                    format!("the receipts of chain {} can not be proven complete", key.chain_id).into(),
+                ));
+            }
+            let receipts = self.block_cache.receipts(header_key.chain_id, header_key.block_number).await?;
+            transactions_counts.push(receipts.len() as u64);
+        }
+        Ok(transactions_counts)
+    }
+}
+
+impl CallHandler for LogsByFilterCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?)
+        .ok_or(SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: "Invalid function identifier".to_string(),
+        })
+    }
+
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        match function_id {
+            FunctionId::Logs => {
+                let transactions_counts = self.transactions_counts(&key).await?;
+                let mut positions = Vec::new();
+                for (header_key, transactions_count) in key.header_keys().zip(&transactions_counts) {
+                    if *transactions_count == 0 {
+                        continue;
+                    }
+                    let receipts = self.block_cache.receipts(header_key.chain_id, header_key.block_number).await?;
+                    for (transaction_index, receipt) in receipts.iter().enumerate() {
+                        let receipt = match receipt.inner.as_receipt_with_bloom() {
+                            Some(receipt) => CairoReceiptWithBloom::from(receipt.clone()),
+                            None => return Err(SyscallExecutionError::InternalError("Receipt not found".into())),
+                        };
+                        positions.extend(receipt.matching_logs(key.address, key.topic0).map(|log_index| LogPosition {
+                            block_number: header_key.block_number,
+                            transaction_index: transaction_index as u64,
+                            log_index,
+                        }));
+                    }
+                }
+                Ok(LogFilterResult {
+                    positions,
+                    transactions_counts,
+                }
+                .to_felts())
+            }
+        }
+    }
+}
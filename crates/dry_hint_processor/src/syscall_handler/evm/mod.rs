@@ -1,14 +1,19 @@
 pub mod account;
+pub mod block_cache;
 pub mod header;
+pub mod header_range;
 pub mod log;
+pub mod log_absence;
+pub mod log_filter;
 pub mod receipt;
 pub mod storage;
 pub mod transaction;
 
-use std::{collections::HashSet, hash::Hash};
+use std::{collections::HashSet, future::Future, hash::Hash};
 
+use alloy::{network::Ethereum, providers::RootProvider, transports::http::reqwest::Url};
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use strum_macros::FromRepr;
 use syscall_handler::{
     felt_from_ptr,
@@ -20,9 +25,39 @@ use types::{
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
     },
+    chain_registry::ChainRegistry,
     keys::evm,
 };
 
+use self::block_cache::BlockCache;
+use crate::rpc_fixture::recorded;
+
+/// Runs `f` with a provider for each rpc url registered for `chain_id` until one succeeds.
+pub async fn with_provider<T, F, Fut>(chain_id: u128, mut f: F) -> SyscallResult<T>
+where
+    F: FnMut(RootProvider<Ethereum>) -> Fut,
+    Fut: Future<Output = SyscallResult<T>>,
+{
+    ChainRegistry::global()
+        .with_rpc_fallback(chain_id, |rpc_url| {
+            let fut = Url::parse(&rpc_url).map(RootProvider::<Ethereum>::new_http).map(&mut f);
+            async move { fut.map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?.await }
+        })
+        .await
+        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+}
+
+/// Like [`with_provider`], with the response recorded to or replayed from the rpc fixture.
+pub async fn with_recorded_provider<T, P, F, Fut>(chain_id: u128, method: &str, params: P, f: F) -> SyscallResult<T>
+where
+    T: Serialize + DeserializeOwned,
+    P: Serialize,
+    F: FnMut(RootProvider<Ethereum>) -> Fut,
+    Fut: Future<Output = SyscallResult<T>>,
+{
+    recorded(chain_id, method, params, with_provider(chain_id, f)).await
+}
+
 #[derive(FromRepr)]
 pub enum CallHandlerId {
     Header = 0,
@@ -31,11 +66,16 @@ pub enum CallHandlerId {
     Transaction = 3,
     Receipt = 4,
     Log = 5,
+    HeaderRange = 6,
+    LogsByFilter = 7,
+    LogAbsence = 8,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct CallContractHandler {
     pub key_set: HashSet<DryRunKey>,
+    #[serde(skip)]
+    pub block_cache: BlockCache,
 }
 
 impl SyscallHandler for CallContractHandler {
@@ -69,7 +109,9 @@ impl SyscallHandler for CallContractHandler {
             CallHandlerId::Header => {
                 let key = header::HeaderCallHandler::derive_key(vm, &mut calldata)?;
                 let function_id = header::HeaderCallHandler::derive_id(request.selector)?;
-                let result = header::HeaderCallHandler.handle(key.clone(), function_id, vm).await?;
+                let result = header::HeaderCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
                 self.key_set.insert(DryRunKey::Header(key));
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
@@ -90,24 +132,70 @@ impl SyscallHandler for CallContractHandler {
             CallHandlerId::Transaction => {
                 let key = transaction::TransactionCallHandler::derive_key(vm, &mut calldata)?;
                 let function_id = transaction::TransactionCallHandler::derive_id(request.selector)?;
-                let result = transaction::TransactionCallHandler.handle(key.clone(), function_id, vm).await?;
+                let result = transaction::TransactionCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
                 self.key_set.insert(DryRunKey::Tx(key));
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
             CallHandlerId::Receipt => {
                 let key = receipt::ReceiptCallHandler::derive_key(vm, &mut calldata)?;
                 let function_id = receipt::ReceiptCallHandler::derive_id(request.selector)?;
-                let result = receipt::ReceiptCallHandler.handle(key.clone(), function_id, vm).await?;
+                let result = receipt::ReceiptCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
                 self.key_set.insert(DryRunKey::Receipt(key));
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
             CallHandlerId::Log => {
                 let key = log::LogCallHandler::derive_key(vm, &mut calldata)?;
                 let function_id = log::LogCallHandler::derive_id(request.selector)?;
-                let result = log::LogCallHandler.handle(key.clone(), function_id, vm).await?;
+                let result = log::LogCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
                 self.key_set.insert(DryRunKey::Receipt(key.into()));
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
+            CallHandlerId::HeaderRange => {
+                let key = header_range::HeaderRangeCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = header_range::HeaderRangeCallHandler::derive_id(request.selector)?;
+                let result = header_range::HeaderRangeCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
+                self.key_set.extend(key.header_keys().map(DryRunKey::Header));
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
+            CallHandlerId::LogsByFilter => {
+                let key = log_filter::LogsByFilterCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = log_filter::LogsByFilterCallHandler::derive_id(request.selector)?;
+                let mut handler = log_filter::LogsByFilterCallHandler::new(self.block_cache.clone());
+                let result = handler.handle(key.clone(), function_id, vm).await?;
+                // Every header of the range is proven, with every receipt of the blocks its bloom does not rule out,
+                // so the sound run can rescan whole blocks
+                let transactions_counts = handler.transactions_counts(&key).await?;
+                self.key_set.extend(key.header_keys().map(DryRunKey::Header));
+                for (header_key, transactions_count) in key.header_keys().zip(transactions_counts) {
+                    self.key_set.extend(
+                        key.receipt_keys(header_key.block_number, transactions_count)
+                            .map(DryRunKey::Receipt),
+                    );
+                }
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
+            CallHandlerId::LogAbsence => {
+                let key = log_absence::LogAbsenceCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = log_absence::LogAbsenceCallHandler::derive_id(request.selector)?;
+                let result = log_absence::LogAbsenceCallHandler::new(self.block_cache.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
+                // The header proves the absence through its bloom, otherwise every receipt of the block is scanned
+                let transactions_count: u64 = result[0]
+                    .try_into()
+                    .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
+                self.key_set.insert(DryRunKey::Header(key.header_key()));
+                self.key_set.extend(key.receipt_keys(transactions_count).map(DryRunKey::Receipt));
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
         }
 
         Ok(Self::Response {
@@ -1,9 +1,3 @@
-use alloy::{
-    eips::{BlockId, BlockNumberOrTag},
-    network::Ethereum,
-    providers::{Provider, RootProvider},
-    transports::http::reqwest::Url,
-};
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
@@ -11,14 +5,21 @@ use types::{
         evm::receipt::{CairoReceiptWithBloom, FunctionId},
         traits::CairoType,
     },
-    keys::evm::{
-        get_corresponding_rpc_url,
-        receipt::{CairoKey, Key},
-    },
+    keys::evm::receipt::{CairoKey, Key},
 };
 
+use super::block_cache::BlockCache;
+
 #[derive(Debug, Default)]
-pub struct ReceiptCallHandler;
+pub struct ReceiptCallHandler {
+    block_cache: BlockCache,
+}
+
+impl ReceiptCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+}
 
 impl CallHandler for ReceiptCallHandler {
     type Key = Key;
@@ -44,14 +45,7 @@ impl CallHandler for ReceiptCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-
-        let receipts = provider
-            .get_block_receipts(BlockId::Number(BlockNumberOrTag::Number(key.block_number)))
-            .await
-            .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
-            .unwrap();
+        let receipts = self.block_cache.receipts(key.chain_id, key.block_number).await?;
 
         let tx_idx: usize = key.transaction_index.try_into().unwrap();
         let receipt = match receipts[tx_idx].inner.as_receipt_with_bloom() {
@@ -1,18 +1,13 @@
-use alloy::{
-    network::Ethereum,
-    providers::{Provider, RootProvider},
-    transports::http::reqwest::Url,
-};
+use alloy::providers::Provider;
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
     cairo::{evm::storage::FunctionId, structs::Uint256, traits::CairoType},
-    keys::evm::{
-        get_corresponding_rpc_url,
-        storage::{CairoKey, Key},
-    },
+    keys::evm::storage::{CairoKey, Key},
 };
 
+use super::with_recorded_provider;
+
 #[derive(Debug, Default)]
 pub struct StorageCallHandler;
 
@@ -41,16 +36,18 @@ impl CallHandler for StorageCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        let value = match function_id {
-            FunctionId::Storage => provider
-                .get_storage_at(key.address, key.storage_slot.into())
-                .block_id(key.block_number.into())
-                .await
-                .map(Uint256::from),
+        let key = &key;
+        let params = (key.address, key.storage_slot, key.block_number);
+        match function_id {
+            FunctionId::Storage => with_recorded_provider(key.chain_id, "eth_getStorageAt", params, |provider| async move {
+                provider
+                    .get_storage_at(key.address, key.storage_slot.into())
+                    .block_id(key.block_number.into())
+                    .await
+                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+            })
+            .await
+            .map(Uint256::from),
         }
-        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        Ok(value)
     }
 }
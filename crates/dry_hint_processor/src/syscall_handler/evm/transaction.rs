@@ -1,9 +1,3 @@
-use alloy::{
-    eips::{BlockId, BlockNumberOrTag},
-    network::Ethereum,
-    providers::{Provider, RootProvider},
-    transports::http::reqwest::Url,
-};
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
@@ -12,14 +6,21 @@ use types::{
         structs::Uint256,
         traits::CairoType,
     },
-    keys::evm::{
-        get_corresponding_rpc_url,
-        transaction::{CairoKey, Key},
-    },
+    keys::evm::transaction::{CairoKey, Key},
 };
 
+use super::block_cache::BlockCache;
+
 #[derive(Debug, Default)]
-pub struct TransactionCallHandler;
+pub struct TransactionCallHandler {
+    block_cache: BlockCache,
+}
+
+impl TransactionCallHandler {
+    pub fn new(block_cache: BlockCache) -> Self {
+        Self { block_cache }
+    }
+}
 
 impl CallHandler for TransactionCallHandler {
     type Key = Key;
@@ -45,15 +46,7 @@ impl CallHandler for TransactionCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-
-        let block = provider
-            .get_block(BlockId::Number(BlockNumberOrTag::Number(key.block_number)))
-            .full()
-            .await
-            .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
-            .unwrap();
+        let block = self.block_cache.block(key.chain_id, key.block_number).await?;
         let tx = block
             .transactions
             .txns()
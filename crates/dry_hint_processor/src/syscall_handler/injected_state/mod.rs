@@ -8,9 +8,13 @@ use cairo_vm::{
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use starknet_crypto::poseidon_hash_many;
-use state_server::api::{
-    read::{ReadRequest, ReadResponse},
-    write::{WriteRequest, WriteResponse},
+use state_server::{
+    api::{
+        delete::{DeleteRequest, DeleteResponse},
+        read::{ReadRequest, ReadResponse},
+        write::{WriteRequest, WriteResponse},
+    },
+    auth::with_api_key,
 };
 use strum_macros::FromRepr;
 use syscall_handler::{memorizer::Memorizer, traits::SyscallHandler, SyscallExecutionError, SyscallResult, WriteResponseResult};
@@ -22,7 +26,7 @@ use types::{
         traits::CairoType,
     },
     keys,
-    proofs::injected_state::{Action, ActionRead, ActionWrite},
+    proofs::injected_state::{Action, ActionDelete, ActionRead, ActionWrite},
     Felt252,
 };
 
@@ -31,6 +35,7 @@ pub enum CallHandlerId {
     ReadTrieRoot = 0,
     Read = 1,
     Write = 2,
+    Delete = 3,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -147,10 +152,13 @@ impl SyscallHandler for CallContractHandler {
 
                     let client = reqwest::Client::new();
                     let endpoint = format!("{}/read", Self::get_base_url());
-                    let response = client.get(&endpoint).query(&request_payload).send().await.map_err(|e| {
-                        error!("State server unavailable: {}", e);
-                        SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into())
-                    })?;
+                    let response = with_api_key(client.get(&endpoint).query(&request_payload))
+                        .send()
+                        .await
+                        .map_err(|e| {
+                            error!("State server unavailable: {}", e);
+                            SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into())
+                        })?;
 
                     match response.status() {
                         StatusCode::OK => {
@@ -231,14 +239,18 @@ impl SyscallHandler for CallContractHandler {
                     trie_label: pathfinder_crypto::Felt::from(key.trie_label.to_bytes_be()),
                     key: pathfinder_crypto::Felt::from(key.key.to_bytes_be()),
                     value: pathfinder_crypto::Felt::from(key.value.to_bytes_be()),
+                    tag: None,
                 };
 
                 let client = reqwest::Client::new();
                 let endpoint = format!("{}/write", Self::get_base_url());
-                let response = client.post(&endpoint).json(&request_payload).send().await.map_err(|e| {
-                    error!("State server unavailable: {}", e);
-                    SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into())
-                })?;
+                let response = with_api_key(client.post(&endpoint).json(&request_payload))
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        error!("State server unavailable: {}", e);
+                        SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into())
+                    })?;
 
                 match response.status() {
                     StatusCode::OK => {
@@ -265,8 +277,9 @@ impl SyscallHandler for CallContractHandler {
                                 trie_label: key.trie_label,
                                 key: key.key,
                             },
+                            // Writing zero removes the key from the trie
                             CacheEntry {
-                                exists: true,
+                                exists: response.value != pathfinder_crypto::Felt::ZERO,
                                 value: Felt252::from_bytes_be(&response.value.to_be_bytes()),
                             },
                         );
@@ -286,6 +299,78 @@ impl SyscallHandler for CallContractHandler {
                     }
                 }
             }
+            CallHandlerId::Delete => {
+                let key = keys::injected_state::delete::CairoKey::from_memory(vm, calldata)?;
+
+                let trie_root = self
+                    .get_trie_root(&memorizer, key.trie_label)?
+                    .ok_or(HintError::NoValueForKey(Box::new(key.trie_label.into())))?;
+
+                let request_payload = DeleteRequest {
+                    trie_root: pathfinder_crypto::Felt::from(trie_root.to_bytes_be()),
+                    trie_label: pathfinder_crypto::Felt::from(key.trie_label.to_bytes_be()),
+                    key: pathfinder_crypto::Felt::from(key.key.to_bytes_be()),
+                    tag: None,
+                };
+
+                let client = reqwest::Client::new();
+                let endpoint = format!("{}/delete", Self::get_base_url());
+                let response = with_api_key(client.post(&endpoint).json(&request_payload))
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        error!("State server unavailable: {}", e);
+                        SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into())
+                    })?;
+
+                match response.status() {
+                    StatusCode::OK => {
+                        let response = response
+                            .json::<DeleteResponse>()
+                            .await
+                            .map_err(|e| SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into()))?;
+
+                        memorizer.set_key(
+                            &MaybeRelocatable::Int(poseidon_hash_many(&[LABEL_RUNTIME, key.trie_label])),
+                            &MaybeRelocatable::Int(Felt252::from_bytes_be(&response.trie_root.to_be_bytes())),
+                            self.dict_manager.clone(),
+                        )?;
+
+                        self.key_set.entry(key.trie_label).or_default().push(Action::Delete(ActionDelete {
+                            trie_root: pathfinder_crypto::Felt::from(trie_root.to_bytes_be()),
+                            trie_label: pathfinder_crypto::Felt::from(key.trie_label.to_bytes_be()),
+                            key: pathfinder_crypto::Felt::from(key.key.to_bytes_be()),
+                        }));
+
+                        self.read_cache.insert(
+                            CacheKey {
+                                trie_label: key.trie_label,
+                                key: key.key,
+                            },
+                            CacheEntry {
+                                exists: false,
+                                value: Felt252::ZERO,
+                            },
+                        );
+
+                        let result = write::Response {
+                            trie_root: Felt252::from_bytes_be(&response.trie_root.to_be_bytes()),
+                        };
+
+                        retdata_end = result.to_memory(vm, retdata_end)?;
+                    }
+                    StatusCode::NOT_FOUND => Err(SyscallExecutionError::InternalError(
+                        format!("Cannot delete key {:#x}, it is not set in trie {:#x}", key.key, key.trie_label).into(),
+                    ))?,
+                    status => {
+                        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                        error!("State server error: {} - {}", status, error_text);
+                        Err(SyscallExecutionError::InternalError(
+                            format!("Network request failed: {}: {}", status, error_text).into(),
+                        ))?;
+                    }
+                }
+            }
         }
 
         Ok(Self::Response {
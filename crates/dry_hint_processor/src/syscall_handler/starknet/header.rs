@@ -10,6 +10,8 @@ use types::{
     keys::starknet::header::{CairoKey, Key},
 };
 
+use crate::rpc_fixture::recorded;
+
 #[derive(Debug, Default)]
 pub struct HeaderCallHandler;
 
@@ -40,17 +42,21 @@ impl CallHandler for HeaderCallHandler {
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
         let provider = Indexer::default();
 
-        // Fetch proof response
-        let response = provider
-            .get_blocks(blocks::IndexerQuery::new(
-                key.chain_id,
-                key.block_number.into(),
-                key.block_number.into(),
-            ))
-            .await
-            .map_err(|e| SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into()))?;
+        // Fetch block header fields
+        let fields = recorded(key.chain_id, "indexer_getBlocks", key.block_number, async {
+            provider
+                .get_blocks(blocks::IndexerQuery::new(
+                    key.chain_id,
+                    key.block_number.into(),
+                    key.block_number.into(),
+                ))
+                .await
+                .map(|response| response.fields)
+                .map_err(|e| SyscallExecutionError::InternalError(format!("Network request failed: {}", e).into()))
+        })
+        .await?;
 
         // Create block and handle function
-        Ok(StarknetBlock::from_hash_fields(response.fields).handle(function_id))
+        Ok(StarknetBlock::from_hash_fields(fields).handle(function_id))
     }
 }
@@ -1,8 +1,12 @@
-use std::{collections::HashSet, hash::Hash};
+use std::{collections::HashSet, future::Future, hash::Hash};
 
+use ::starknet::providers::{
+    jsonrpc::{HttpTransport, JsonRpcClient},
+    Url,
+};
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use header::HeaderCallHandler;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use storage::StorageCallHandler;
 use strum_macros::FromRepr;
 use syscall_handler::{
@@ -15,11 +19,43 @@ use types::{
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
     },
+    chain_registry::ChainRegistry,
     keys::starknet,
 };
+
+use crate::rpc_fixture::recorded;
+
 pub mod header;
 pub mod storage;
 
+/// Runs `f` with a client for each rpc url registered for `chain_id` until one succeeds.
+pub async fn with_provider<T, F, Fut>(chain_id: u128, mut f: F) -> SyscallResult<T>
+where
+    F: FnMut(JsonRpcClient<HttpTransport>) -> Fut,
+    Fut: Future<Output = SyscallResult<T>>,
+{
+    ChainRegistry::global()
+        .with_rpc_fallback(chain_id, |rpc_url| {
+            let fut = Url::parse(&rpc_url)
+                .map(|url| JsonRpcClient::new(HttpTransport::new(url)))
+                .map(&mut f);
+            async move { fut.map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?.await }
+        })
+        .await
+        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+}
+
+/// Like [`with_provider`], with the response recorded to or replayed from the rpc fixture.
+pub async fn with_recorded_provider<T, P, F, Fut>(chain_id: u128, method: &str, params: P, f: F) -> SyscallResult<T>
+where
+    T: Serialize + DeserializeOwned,
+    P: Serialize,
+    F: FnMut(JsonRpcClient<HttpTransport>) -> Fut,
+    Fut: Future<Output = SyscallResult<T>>,
+{
+    recorded(chain_id, method, params, with_provider(chain_id, f)).await
+}
+
 #[derive(FromRepr)]
 pub enum CallHandlerId {
     Header = 0,
@@ -1,20 +1,16 @@
 use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine, Felt252};
 use starknet::{
     core::types::{BlockId, Felt},
-    providers::{
-        jsonrpc::{HttpTransport, JsonRpcClient},
-        Provider, Url,
-    },
+    providers::Provider,
 };
 use syscall_handler::{traits::CallHandler, SyscallExecutionError, SyscallResult};
 use types::{
     cairo::{evm::storage::FunctionId, structs::CairoFelt, traits::CairoType},
-    keys::starknet::{
-        get_corresponding_rpc_url,
-        storage::{CairoKey, Key},
-    },
+    keys::starknet::storage::{CairoKey, Key},
 };
 
+use super::with_recorded_provider;
+
 #[derive(Debug, Default)]
 pub struct StorageCallHandler;
 
@@ -43,21 +39,22 @@ impl CallHandler for StorageCallHandler {
     }
 
     async fn handle(&mut self, key: Self::Key, function_id: Self::Id, _vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
-        let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-        let provider = JsonRpcClient::new(HttpTransport::new(Url::parse(&rpc_url).unwrap()));
         let block_id = BlockId::Number(key.block_number);
-        let value = match function_id {
-            FunctionId::Storage => provider
-                .get_storage_at::<Felt, Felt, BlockId>(
-                    Felt::from_bytes_be(&key.address.to_bytes_be()),
-                    Felt::from_bytes_be(&key.storage_slot.to_bytes_be()),
-                    block_id,
-                )
-                .await
-                .map(|f| CairoFelt::from(Felt252::from_bytes_be(&f.to_bytes_be()))),
+        let key = &key;
+        let params = (key.address, key.storage_slot, key.block_number);
+        match function_id {
+            FunctionId::Storage => with_recorded_provider(key.chain_id, "starknet_getStorageAt", params, |provider| async move {
+                provider
+                    .get_storage_at::<Felt, Felt, BlockId>(
+                        Felt::from_bytes_be(&key.address.to_bytes_be()),
+                        Felt::from_bytes_be(&key.storage_slot.to_bytes_be()),
+                        block_id,
+                    )
+                    .await
+                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+            })
+            .await
+            .map(|f| CairoFelt::from(Felt252::from_bytes_be(&f.to_bytes_be()))),
         }
-        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-
-        Ok(value)
     }
 }
@@ -1,11 +1,10 @@
 use std::{collections::HashSet, hash::Hash};
 
-use alloy::{
-    network::Ethereum,
-    providers::{Provider, RootProvider},
+use alloy::providers::Provider;
+use cairo_vm::{
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::vm_core::VirtualMachine,
 };
-use cairo_vm::{types::relocatable::Relocatable, vm::vm_core::VirtualMachine};
-use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use strum_macros::FromRepr;
 use syscall_handler::{felt_from_ptr, traits::SyscallHandler, SyscallExecutionError, SyscallResult, WriteResponseResult};
@@ -13,15 +12,21 @@ use types::{
     cairo::{
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
-        unconstrained::bytecode::BytecodeLeWords,
+        unconstrained::{
+            bytecode::BytecodeLeWords,
+            oracle::{OracleKey, OracleValue},
+        },
     },
-    keys::{self, evm::get_corresponding_rpc_url},
-    Felt252,
+    keys, Felt252,
 };
 
+use super::evm::with_recorded_provider;
+use crate::oracle::OracleRegistry;
+
 #[derive(FromRepr, Debug)]
 pub enum CallHandlerId {
     Bytecode = 0,
+    Oracle = 1,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -61,18 +66,35 @@ impl SyscallHandler for CallContractHandler {
                 let key: keys::evm::account::Key = keys::evm::account::CairoKey::from_memory(vm, calldata)?
                     .try_into()
                     .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
-                let rpc_url = get_corresponding_rpc_url(&key).map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
-                let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-                let result = provider
-                    .get_code_at(key.address)
-                    .block_id(key.block_number.into())
-                    .await
-                    .map(BytecodeLeWords::from)
-                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
+                let params = (key.address, key.block_number);
+                let result = with_recorded_provider(key.chain_id, "eth_getCode", params, |provider| async move {
+                    provider
+                        .get_code_at(key.address)
+                        .block_id(key.block_number.into())
+                        .await
+                        .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))
+                })
+                .await
+                .map(BytecodeLeWords::from)?;
 
                 self.key_set.insert(DryRunKey::Bytecode(key));
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
+            CallHandlerId::Oracle => {
+                let oracle_id = felt_from_ptr(vm, &mut calldata)?;
+                let input_len: usize = felt_from_ptr(vm, &mut calldata)?
+                    .try_into()
+                    .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
+                let input: Vec<Felt252> = vm.get_integer_range(calldata, input_len)?.into_iter().map(|e| *e).collect();
+
+                let value = OracleRegistry::global().resolve(oracle_id, &input).await?;
+
+                retdata_end = vm.load_data(retdata_end, &value.0.iter().map(MaybeRelocatable::from).collect::<Vec<_>>())?;
+                self.key_set.insert(DryRunKey::Oracle {
+                    key: OracleKey::new(oracle_id, input),
+                    value,
+                });
+            }
         }
 
         Ok(Self::Response {
@@ -104,10 +126,15 @@ impl TryFrom<Felt252> for CallHandlerId {
 #[serde(rename_all = "lowercase")]
 pub enum DryRunKey {
     Bytecode(keys::evm::account::Key),
+    Oracle { key: OracleKey, value: OracleValue },
 }
 
 impl DryRunKey {
     pub fn is_bytecode(&self) -> bool {
         matches!(self, Self::Bytecode(_))
     }
+
+    pub fn is_oracle(&self) -> bool {
+        matches!(self, Self::Oracle { .. })
+    }
 }
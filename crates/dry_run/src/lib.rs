@@ -3,7 +3,7 @@
 #![warn(unused_crate_dependencies)]
 #![forbid(unsafe_code)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub use cairo_vm::types::{layout_name::LayoutName, program::Program};
 use cairo_vm::{
@@ -13,6 +13,7 @@ use cairo_vm::{
 use clap::Parser;
 use dotenvy as _;
 use dry_hint_processor::{
+    rpc_fixture::{FixtureMode, RpcFixture},
     syscall_handler::{evm, injected_state, starknet, unconstrained},
     CustomHintProcessor,
 };
@@ -22,7 +23,13 @@ use syscall_handler::{SyscallHandler, SyscallHandlerWrapper};
 use tokio as _;
 use tracing::{debug, info};
 use tracing_subscriber as _;
-use types::{error::Error, param::Param, CasmContractClass, HDPDryRunInput, HDPDryRunOutput, InjectedState};
+use types::{
+    budget::ResourceBudget,
+    error::Error,
+    param::Param,
+    report::{OutputFormat, RunArtifacts, RunReport},
+    CasmContractClass, HDPDryRunInput, HDPDryRunOutput, InjectedState,
+};
 
 pub const DRY_RUN_COMPILED_JSON: &str = env!("DRY_RUN_COMPILED_JSON");
 
@@ -51,17 +58,48 @@ pub struct Args {
     #[arg(
         long = "print_output",
         default_value_t = false,
-        help = "Print program output to stdout [default: false]"
+        help = "Print the run report to stdout [default: false]"
     )]
     pub print_output: bool,
+    #[arg(
+        long = "output-format",
+        default_value = "pretty",
+        help = "Format of the run report printed with --print_output: `json` on a single line, or `pretty`"
+    )]
+    pub output_format: OutputFormat,
     #[structopt(long = "allow_missing_builtins")]
     pub allow_missing_builtins: Option<bool>,
+    #[arg(
+        long = "rpc-fixture",
+        help = "Path to the rpc fixture folder responses are recorded to or replayed from"
+    )]
+    pub rpc_fixture: Option<PathBuf>,
+    #[arg(
+        long = "rpc-fixture-mode",
+        default_value = "replay",
+        requires = "rpc_fixture",
+        help = "`record` rpc responses to the fixture, or `replay` them without network access"
+    )]
+    pub rpc_fixture_mode: FixtureMode,
+    #[command(flatten)]
+    pub budget: ResourceBudget,
+}
+
+/// Routes the rpc calls of the dry run and the proof fetching through the fixture at `path`, if given.
+pub fn init_rpc_fixture(path: Option<&Path>, mode: FixtureMode) -> Result<(), Error> {
+    match path {
+        Some(path) => RpcFixture::open(path, mode)
+            .and_then(RpcFixture::init_global)
+            .map_err(|e| Error::RpcFixture(e.to_string())),
+        None => Ok(()),
+    }
 }
 
 #[allow(clippy::type_complexity)]
 pub fn run(
     program_path: PathBuf,
     input: HDPDryRunInput,
+    budget: ResourceBudget,
 ) -> Result<
     (
         SyscallHandler<
@@ -70,7 +108,7 @@ pub fn run(
             injected_state::CallContractHandler,
             unconstrained::CallContractHandler,
         >,
-        HDPDryRunOutput,
+        RunReport<HDPDryRunOutput>,
     ),
     Error,
 > {
@@ -85,9 +123,12 @@ pub fn run(
     let program_file = std::fs::read(program_path).map_err(Error::IO)?;
     let program = Program::from_bytes(&program_file, Some(cairo_run_config.entrypoint))?;
 
-    let mut hint_processor = CustomHintProcessor::new(input);
-    let mut cairo_runner = cairo_run_program(&program, &cairo_run_config, &mut hint_processor).map_err(Box::new)?;
-    debug!("{:?}", cairo_runner.get_execution_resources());
+    let mut hint_processor = CustomHintProcessor::new(input).with_budget(budget);
+    let mut cairo_runner = cairo_run_program(&program, &cairo_run_config, &mut hint_processor)
+        .map_err(|e| hint_processor.budget().exceeded().unwrap_or_else(|| Box::new(e).into()))?;
+    let resources = cairo_runner.get_execution_resources()?;
+    debug!("{:?}", resources);
+    hint_processor.budget().check_builtins(&resources)?;
 
     let syscall_handler = cairo_runner
         .exec_scopes
@@ -112,12 +153,14 @@ pub fn run(
         .map(|v| v.clone().unwrap().get_int().unwrap());
 
     let output = HDPDryRunOutput::from_iter(iter);
+    let report = RunReport::new(output, &cairo_runner, RunArtifacts::default())?;
 
-    Ok((syscall_handler, output))
+    Ok((syscall_handler, report))
 }
 
 pub async fn run_with_args(args: Args) -> Result<(), Error> {
     info!("Starting dry run execution...");
+    init_rpc_fixture(args.rpc_fixture.as_deref(), args.rpc_fixture_mode)?;
     info!("Reading compiled module from: {}", args.compiled_module.display());
     let compiled_class: CasmContractClass = serde_json::from_slice(&std::fs::read(args.compiled_module).map_err(Error::IO)?)?;
     let params: Vec<Param> = if let Some(path) = args.inputs {
@@ -132,21 +175,18 @@ pub async fn run_with_args(args: Args) -> Result<(), Error> {
     };
 
     info!("Executing program...");
-    let (syscall_handler, output) = run(
+    let (syscall_handler, mut report) = run(
         args.program.unwrap_or(PathBuf::from(DRY_RUN_COMPILED_JSON)),
         HDPDryRunInput {
             compiled_class,
             params,
             injected_state,
         },
+        args.budget,
     )?;
 
-    if args.print_output {
-        println!("{:#?}", output);
-    }
-
     std::fs::write(
-        args.output,
+        &args.output,
         serde_json::to_vec::<
             SyscallHandler<
                 evm::CallContractHandler,
@@ -159,6 +199,11 @@ pub async fn run_with_args(args: Args) -> Result<(), Error> {
     )
     .map_err(Error::IO)?;
 
+    if args.print_output {
+        report.artifacts.dry_run_output = Some(args.output);
+        report.print(args.output_format)?;
+    }
+
     info!("Dry run completed successfully.");
 
     Ok(())
@@ -12,14 +12,18 @@ use serde_json as _;
 use syscall_handler as _;
 use tracing as _;
 use tracing_subscriber::EnvFilter;
-use types::error::Error;
+use types::{chain_registry::ChainRegistry, error::Error};
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 1)]
 async fn main() -> Result<(), Error> {
     dotenvy::dotenv().ok();
-    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
 
     let args = Args::try_parse_from(std::env::args()).map_err(Error::Cli)?;
+    ChainRegistry::init_global_from(None)?;
 
     dry_run::run_with_args(args).await
 }
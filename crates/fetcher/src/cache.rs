@@ -0,0 +1,219 @@
+use std::{future::Future, path::Path};
+
+use dry_hint_processor::rpc_fixture::recorded;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use tracing::debug;
+
+use crate::FetcherError;
+
+/// Identifies a cached proof by the chain and block it was fetched at.
+///
+/// `key` holds the remaining request parameters (address, slot, MMR source, ...) so that
+/// every distinct request maps to its own entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub kind: &'static str,
+    pub chain_id: u128,
+    pub block_number: u64,
+    pub key: String,
+}
+
+impl CacheKey {
+    pub fn new(kind: &'static str, chain_id: u128, block_number: u64, key: impl Into<String>) -> Self {
+        Self {
+            kind,
+            chain_id,
+            block_number,
+            key: key.into(),
+        }
+    }
+}
+
+/// On-disk store of fetched proofs, shared across fetcher runs.
+///
+/// Entries are never invalidated, so the cache must only be used for finalized blocks.
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    pool: Pool<SqliteConnectionManager>,
+}
+
+impl ProofCache {
+    /// Opens the cache stored at `path`, creating the file and its parent folders if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FetcherError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        debug!("using proof cache: {}", path.display());
+        Self::with_manager(SqliteConnectionManager::file(path))
+    }
+
+    /// Creates a cache living in memory, dropped with the last clone.
+    pub fn memory() -> Result<Self, FetcherError> {
+        Self::with_manager(SqliteConnectionManager::memory())
+    }
+
+    fn with_manager(manager: SqliteConnectionManager) -> Result<Self, FetcherError> {
+        // A single connection keeps in-memory caches shared and serializes writers
+        let pool = Pool::builder().max_size(1).build(manager)?;
+        pool.get()?.execute(
+            "CREATE TABLE IF NOT EXISTS proofs (
+                kind TEXT NOT NULL,
+                chain_id TEXT NOT NULL,
+                block_number INTEGER NOT NULL,
+                key TEXT NOT NULL,
+                value BLOB NOT NULL,
+                PRIMARY KEY (kind, chain_id, block_number, key)
+            )",
+            [],
+        )?;
+
+        Ok(Self { pool })
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &CacheKey) -> Result<Option<T>, FetcherError> {
+        let value: Option<Vec<u8>> = self
+            .pool
+            .get()?
+            .query_row(
+                "SELECT value FROM proofs WHERE kind = ?1 AND chain_id = ?2 AND block_number = ?3 AND key = ?4",
+                params![key.kind, format!("{:#x}", key.chain_id), key.block_number, key.key],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(value.map(|value| serde_json::from_slice(&value)).transpose()?)
+    }
+
+    pub fn insert<T: Serialize>(&self, key: &CacheKey, value: &T) -> Result<(), FetcherError> {
+        self.pool.get()?.execute(
+            "INSERT OR REPLACE INTO proofs (kind, chain_id, block_number, key, value) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                key.kind,
+                format!("{:#x}", key.chain_id),
+                key.block_number,
+                key.key,
+                serde_json::to_vec(value)?
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Returns the cached value for `key`, or awaits `fetch` and stores its result.
+///
+/// The value is recorded to or replayed from the rpc fixture first, so a replayed run never touches the cache.
+pub async fn cached<T, Fut>(cache: Option<&ProofCache>, key: CacheKey, fetch: Fut) -> Result<T, FetcherError>
+where
+    T: Serialize + DeserializeOwned,
+    Fut: Future<Output = Result<T, FetcherError>>,
+{
+    let params = (key.block_number, key.key.clone());
+    recorded(key.chain_id, key.kind, params, async move {
+        let Some(cache) = cache else {
+            return fetch.await;
+        };
+
+        if let Some(value) = cache.get(&key)? {
+            return Ok(value);
+        }
+
+        let value = fetch.await?;
+        cache.insert(&key, &value)?;
+
+        Ok(value)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    async fn fetch_counted(calls: &AtomicUsize, value: u64) -> Result<u64, FetcherError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn cached_serves_hits_without_fetching() {
+        let cache = ProofCache::memory().unwrap();
+        let calls = AtomicUsize::new(0);
+        let key = CacheKey::new("evm_account", 1, 100, "0x01");
+
+        assert_eq!(cached(Some(&cache), key.clone(), fetch_counted(&calls, 7)).await.unwrap(), 7);
+        assert_eq!(cached(Some(&cache), key, fetch_counted(&calls, 8)).await.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn cached_misses_on_other_block_chain_or_key() {
+        let cache = ProofCache::memory().unwrap();
+        let calls = AtomicUsize::new(0);
+
+        for key in [
+            CacheKey::new("evm_account", 1, 100, "0x01"),
+            CacheKey::new("evm_account", 1, 101, "0x01"),
+            CacheKey::new("evm_account", 10, 100, "0x01"),
+            CacheKey::new("evm_account", 1, 100, "0x02"),
+            CacheKey::new("evm_storage", 1, 100, "0x01"),
+        ] {
+            cached(Some(&cache), key, fetch_counted(&calls, 7)).await.unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn cached_always_fetches_without_cache() {
+        let calls = AtomicUsize::new(0);
+        let key = CacheKey::new("evm_account", 1, 100, "0x01");
+
+        cached(None, key.clone(), fetch_counted(&calls, 7)).await.unwrap();
+        cached(None, key, fetch_counted(&calls, 7)).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_not_cached() {
+        let cache = ProofCache::memory().unwrap();
+        let key = CacheKey::new("evm_account", 1, 100, "0x01");
+
+        let failed: Result<u64, _> = cached(Some(&cache), key.clone(), async {
+            Err(FetcherError::InternalError("rpc down".into()))
+        })
+        .await;
+        assert!(failed.is_err());
+        assert_eq!(cache.get::<u64>(&key).unwrap(), None);
+
+        let calls = AtomicUsize::new(0);
+        assert_eq!(cached(Some(&cache), key, fetch_counted(&calls, 7)).await.unwrap(), 7);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn insert_replaces_stale_entry() {
+        let cache = ProofCache::memory().unwrap();
+        let key = CacheKey::new("evm_account", 1, 100, "0x01");
+
+        cache.insert(&key, &7u64).unwrap();
+        cache.insert(&key, &8u64).unwrap();
+        assert_eq!(cache.get::<u64>(&key).unwrap(), Some(8));
+    }
+
+    #[test]
+    fn entries_persist_across_reopen() {
+        let path = std::env::temp_dir().join(format!("hdp_proof_cache_{}.db", std::process::id()));
+        let key = CacheKey::new("evm_account", 1, 100, "0x01");
+
+        ProofCache::open(&path).unwrap().insert(&key, &7u64).unwrap();
+        assert_eq!(ProofCache::open(&path).unwrap().get::<u64>(&key).unwrap(), Some(7));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
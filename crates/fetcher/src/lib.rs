@@ -4,7 +4,7 @@
 #![forbid(unsafe_code)]
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     fs,
     num::ParseIntError,
     ops::RangeInclusive,
@@ -12,12 +12,16 @@ use std::{
 };
 
 use alloy::{hex::FromHexError, primitives::Bytes};
+use cache::ProofCache;
 use clap::Parser;
 use dotenvy as _;
-use dry_hint_processor::syscall_handler::{
-    evm,
-    injected_state::{self},
-    starknet, unconstrained,
+use dry_hint_processor::{
+    rpc_fixture::{recorded, FixtureMode, RpcFixture, RpcFixtureError},
+    syscall_handler::{
+        evm,
+        injected_state::{self},
+        starknet, unconstrained,
+    },
 };
 use eth_trie_proofs::{tx_receipt_trie::TxReceiptsMptHandler, tx_trie::TxsMptHandler};
 use futures::StreamExt;
@@ -32,7 +36,10 @@ use proof_keys::{
 };
 use reqwest::Url;
 use starknet_types_core::felt::FromStrError;
-use state_server::api::proof::{GetStateProofsRequest, GetStateProofsResponse};
+use state_server::{
+    api::proof::{GetStateProofsRequest, GetStateProofsResponse},
+    auth::with_api_key,
+};
 use syscall_handler::SyscallHandler;
 use thiserror::Error;
 use tokio as _;
@@ -40,6 +47,7 @@ use tracing::info;
 use tracing_subscriber as _;
 use types::{
     cairo::unconstrained::UnconstrainedStateValue,
+    chain_registry::ChainRegistryError,
     keys::evm::get_corresponding_rpc_url,
     proofs::{
         evm::{
@@ -51,10 +59,10 @@ use types::{
         mmr::MmrMeta,
         starknet::{header::Header as StarknetHeader, storage::Storage as StarknetStorage, Proofs as StarknetProofs},
     },
-    ChainProofs, HashingFunction, ProofsData, UnconstrainedState, ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID,
-    OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID, STARKNET_MAINNET_CHAIN_ID, STARKNET_TESTNET_CHAIN_ID,
+    ChainProofs, HashingFunction, ProofsData, UnconstrainedState,
 };
 
+pub mod cache;
 pub mod proof_keys;
 
 #[derive(Parser, Debug)]
@@ -85,6 +93,25 @@ pub struct Args {
         help = "Path to JSON file containing fetcher config - mapping chain_id -> to chain_id"
     )]
     pub mmr_deployment_config: Option<PathBuf>,
+
+    #[arg(
+        long = "proof-cache",
+        help = "Path to the SQLite proof cache reused across runs, only use with finalized blocks (e.g. db/proof_cache.db)"
+    )]
+    pub proof_cache: Option<PathBuf>,
+
+    #[arg(
+        long = "rpc-fixture",
+        help = "Path to the rpc fixture folder proofs are recorded to or replayed from"
+    )]
+    pub rpc_fixture: Option<PathBuf>,
+    #[arg(
+        long = "rpc-fixture-mode",
+        default_value = "replay",
+        requires = "rpc_fixture",
+        help = "`record` fetched proofs to the fixture, or `replay` them without network access"
+    )]
+    pub rpc_fixture_mode: FixtureMode,
 }
 
 #[derive(Error, Debug)]
@@ -109,6 +136,14 @@ pub enum FetcherError {
     RequestError(#[from] reqwest::Error),
     #[error("JSON deserialization error: {0}")]
     JsonDeserializationError(String),
+    #[error(transparent)]
+    ChainRegistry(#[from] ChainRegistryError),
+    #[error("Proof cache error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Proof cache pool error: {0}")]
+    Pool(#[from] r2d2::Error),
+    #[error(transparent)]
+    RpcFixture(#[from] RpcFixtureError),
 }
 
 impl From<FromStrError> for FetcherError {
@@ -194,6 +229,7 @@ pub struct Fetcher<'a> {
     proof_keys: &'a ProofKeys,
     mmr_hasher_config: MMRHasherConfig,
     mmr_deployment_config: MMRDeploymentConfig,
+    proof_cache: Option<ProofCache>,
     #[cfg(feature = "progress_bars")]
     progress_bars: ProgressBars,
 }
@@ -203,22 +239,44 @@ impl<'a> Fetcher<'a> {
             proof_keys,
             mmr_hasher_config,
             mmr_deployment_config,
+            proof_cache: None,
             #[cfg(feature = "progress_bars")]
             progress_bars: ProgressBars::new(proof_keys),
         }
     }
 
+    /// Serves header, account and storage proofs from `proof_cache` when present, storing the fetched ones.
+    ///
+    /// A cached header proof stays verifiable against the MMR meta it was fetched with, which is output with it.
+    pub fn with_proof_cache(mut self, proof_cache: ProofCache) -> Self {
+        self.proof_cache = Some(proof_cache);
+        self
+    }
+
+    /// The chain the MMR of `chain_id` is deployed on and its hashing function.
+    fn mmr_config(&self, chain_id: u128) -> Result<(u128, HashingFunction), FetcherError> {
+        match (self.mmr_deployment_config.get(&chain_id), self.mmr_hasher_config.get(&chain_id)) {
+            (Some(deployed_on_chain_id), Some(mmr_hasher)) => Ok((*deployed_on_chain_id, *mmr_hasher)),
+            _ => Err(ChainRegistryError::UnknownChain(chain_id).into()),
+        }
+    }
+
     async fn collect_evm_headers_proofs(
         &self,
         flattened_keys: &HashSet<FlattenedKey>,
     ) -> Result<HashMap<MmrMeta, Vec<EvmHeader>>, FetcherError> {
         let mut headers_with_mmr = HashMap::default();
-        let mut header_fut = futures::stream::iter(flattened_keys.iter().map(|key| {
+        let keys = flattened_keys
+            .iter()
+            .map(|key| Ok((key, self.mmr_config(key.chain_id)?)))
+            .collect::<Result<Vec<_>, FetcherError>>()?;
+        let mut header_fut = futures::stream::iter(keys.into_iter().map(|(key, (deployed_on_chain_id, mmr_hasher))| {
             EvmProofKeys::fetch_header_proof(
-                *self.mmr_deployment_config.get(&key.chain_id).unwrap(),
+                deployed_on_chain_id,
                 key.chain_id,
                 key.block_number,
-                *self.mmr_hasher_config.get(&key.chain_id).unwrap(),
+                mmr_hasher,
+                self.proof_cache.as_ref(),
             )
         }))
         .buffer_unordered(BUFFER_UNORDERED)
@@ -254,9 +312,10 @@ impl<'a> Fetcher<'a> {
 
         // Collect account proofs
         let chain_account_keys_iter = self.proof_keys.evm.account_keys.iter().filter(|key| key.chain_id == chain_id);
-        let mut account_fut = futures::stream::iter(chain_account_keys_iter.map(EvmProofKeys::fetch_account_proof))
-            .buffer_unordered(BUFFER_UNORDERED)
-            .boxed();
+        let mut account_fut =
+            futures::stream::iter(chain_account_keys_iter.map(|key| EvmProofKeys::fetch_account_proof(key, self.proof_cache.as_ref())))
+                .buffer_unordered(BUFFER_UNORDERED)
+                .boxed();
 
         while let Some(result) = account_fut.next().await {
             accounts.insert(result?);
@@ -270,9 +329,10 @@ impl<'a> Fetcher<'a> {
 
         // Collect storage proofs
         let chain_storage_keys_iter = self.proof_keys.evm.storage_keys.iter().filter(|key| key.chain_id == chain_id);
-        let mut storage_fut = futures::stream::iter(chain_storage_keys_iter.map(EvmProofKeys::fetch_storage_proof))
-            .buffer_unordered(BUFFER_UNORDERED)
-            .boxed();
+        let mut storage_fut =
+            futures::stream::iter(chain_storage_keys_iter.map(|key| EvmProofKeys::fetch_storage_proof(key, self.proof_cache.as_ref())))
+                .buffer_unordered(BUFFER_UNORDERED)
+                .boxed();
 
         while let Some(result) = storage_fut.next().await {
             let (account, storage) = result?;
@@ -286,70 +346,83 @@ impl<'a> Fetcher<'a> {
         #[cfg(feature = "progress_bars")]
         self.progress_bars.evm_storage.safe_finish_with_message();
 
-        // For each block, we need to create a mpt_handler
+        // For each block, we need to create a mpt_handler, unless all its receipts are replayed from the rpc fixture
         let mut receipt_mpt_handlers: HashMap<u64, TxReceiptsMptHandler> = HashMap::default();
         let chain_receipt_keys_iter = self.proof_keys.evm.receipt_keys.iter().filter(|key| key.chain_id == chain_id);
-        for key in chain_receipt_keys_iter.clone() {
-            if let std::collections::hash_map::Entry::Vacant(entry) = receipt_mpt_handlers.entry(key.block_number) {
-                let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-                let mut mpt_handler =
-                    TxReceiptsMptHandler::new(Url::parse(&rpc_url).unwrap()).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-
-                mpt_handler
-                    .build_tx_receipts_tree_from_block(key.block_number)
-                    .await
-                    .map_err(|e| FetcherError::InternalError(e.to_string()))?;
-
-                entry.insert(mpt_handler);
-            }
+        for key in chain_receipt_keys_iter {
+            let receipt = recorded(key.chain_id, "fetcher_evm_receipt", key, async {
+                let mpt_handler = match receipt_mpt_handlers.entry(key.block_number) {
+                    std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                        let url = Url::parse(&rpc_url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                        let mut mpt_handler = TxReceiptsMptHandler::new(url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+
+                        mpt_handler
+                            .build_tx_receipts_tree_from_block(key.block_number)
+                            .await
+                            .map_err(|e| FetcherError::InternalError(e.to_string()))?;
+
+                        entry.insert(mpt_handler)
+                    }
+                };
+                EvmProofKeys::compute_receipt_proof(key, mpt_handler)
+            })
+            .await?;
+            receipts.insert(receipt);
 
             #[cfg(feature = "progress_bars")]
             self.progress_bars.evm_receipts.safe_inc();
         }
 
-        receipts.extend(
-            chain_receipt_keys_iter
-                .map(|key| EvmProofKeys::compute_receipt_proof(key, receipt_mpt_handlers.get_mut(&key.block_number).unwrap()).unwrap()),
-        );
-
         #[cfg(feature = "progress_bars")]
         self.progress_bars.evm_receipts.safe_finish_with_message();
 
-        // For each tx block, we need to create a mpt_handler
+        // For each tx block, we need to create a mpt_handler, unless all its transactions are replayed from the rpc fixture
         let mut tx_mpt_handlers: HashMap<u64, TxsMptHandler> = HashMap::default();
         let chain_tx_keys_iter = self.proof_keys.evm.transaction_keys.iter().filter(|key| key.chain_id == chain_id);
-        for key in chain_tx_keys_iter.clone() {
-            if let std::collections::hash_map::Entry::Vacant(entry) = tx_mpt_handlers.entry(key.block_number) {
-                let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-                let mut mpt_handler =
-                    TxsMptHandler::new(Url::parse(&rpc_url).unwrap()).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-
-                mpt_handler
-                    .build_tx_tree_from_block(key.block_number)
-                    .await
-                    .map_err(|e| FetcherError::InternalError(e.to_string()))?;
-
-                entry.insert(mpt_handler);
-            }
+        for key in chain_tx_keys_iter {
+            let transaction = recorded(key.chain_id, "fetcher_evm_transaction", key, async {
+                let mpt_handler = match tx_mpt_handlers.entry(key.block_number) {
+                    std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                    std::collections::hash_map::Entry::Vacant(entry) => {
+                        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                        let url = Url::parse(&rpc_url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                        let mut mpt_handler = TxsMptHandler::new(url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+
+                        mpt_handler
+                            .build_tx_tree_from_block(key.block_number)
+                            .await
+                            .map_err(|e| FetcherError::InternalError(e.to_string()))?;
+
+                        entry.insert(mpt_handler)
+                    }
+                };
+                EvmProofKeys::compute_transaction_proof(key, mpt_handler)
+            })
+            .await?;
+            transactions.insert(transaction);
 
             #[cfg(feature = "progress_bars")]
             self.progress_bars.evm_transactions.safe_inc();
         }
 
-        transactions.extend(
-            chain_tx_keys_iter
-                .map(|key| EvmProofKeys::compute_transaction_proof(key, tx_mpt_handlers.get_mut(&key.block_number).unwrap()).unwrap()),
-        );
-
         #[cfg(feature = "progress_bars")]
         self.progress_bars.evm_transactions.safe_finish_with_message();
 
+        let accounts = sort_accounts(accounts);
+        let storages = sort_storages(storages);
+        let mut receipts: Vec<_> = receipts.into_iter().collect();
+        receipts.sort_by_key(|receipt| (receipt.proof.block_number, receipt.key));
+        let mut transactions: Vec<_> = transactions.into_iter().collect();
+        transactions.sort_by_key(|transaction| (transaction.proof.block_number, transaction.key));
+
         Ok(EvmProofs {
-            headers_with_mmr: process_headers(headers_with_mmr),
-            accounts: accounts.into_iter().collect(),
-            storages: storages.into_iter().collect(),
-            transaction_receipts: receipts.into_iter().collect(),
-            transactions: transactions.into_iter().collect(),
+            headers_with_mmr: process_headers(headers_with_mmr, |header| header.proof.leaf_idx),
+            accounts,
+            storages,
+            transaction_receipts: receipts,
+            transactions,
         })
     }
 
@@ -358,12 +431,17 @@ impl<'a> Fetcher<'a> {
         flattened_keys: &HashSet<FlattenedKey>,
     ) -> Result<HashMap<MmrMeta, Vec<StarknetHeader>>, FetcherError> {
         let mut headers_with_mmr = HashMap::default();
-        let mut header_fut = futures::stream::iter(flattened_keys.iter().map(|key| {
+        let keys = flattened_keys
+            .iter()
+            .map(|key| Ok((key, self.mmr_config(key.chain_id)?)))
+            .collect::<Result<Vec<_>, FetcherError>>()?;
+        let mut header_fut = futures::stream::iter(keys.into_iter().map(|(key, (deployed_on_chain_id, mmr_hasher))| {
             StarknetProofKeys::fetch_header_proof(
-                *self.mmr_deployment_config.get(&key.chain_id).unwrap(),
+                deployed_on_chain_id,
                 key.chain_id,
                 key.block_number,
-                *self.mmr_hasher_config.get(&key.chain_id).unwrap(),
+                mmr_hasher,
+                self.proof_cache.as_ref(),
             )
         }))
         .buffer_unordered(BUFFER_UNORDERED)
@@ -395,9 +473,11 @@ impl<'a> Fetcher<'a> {
 
         // Collect storage proofs
         let chain_storage_keys_iter = self.proof_keys.starknet.storage_keys.iter().filter(|key| key.chain_id == chain_id);
-        let mut storage_fut = futures::stream::iter(chain_storage_keys_iter.map(StarknetProofKeys::fetch_storage_proof))
-            .buffer_unordered(BUFFER_UNORDERED)
-            .boxed();
+        let mut storage_fut = futures::stream::iter(
+            chain_storage_keys_iter.map(|key| StarknetProofKeys::fetch_storage_proof(key, self.proof_cache.as_ref())),
+        )
+        .buffer_unordered(BUFFER_UNORDERED)
+        .boxed();
 
         while let Some(result) = storage_fut.next().await {
             storages.insert(result?);
@@ -408,9 +488,12 @@ impl<'a> Fetcher<'a> {
         #[cfg(feature = "progress_bars")]
         self.progress_bars.starknet_storage.safe_finish_with_message();
 
+        let mut storages: Vec<_> = storages.into_iter().collect();
+        storages.sort_by_key(|storage| (storage.block_number, storage.contract_address, storage.storage_addresses.clone()));
+
         Ok(StarknetProofs {
-            headers_with_mmr: process_headers(headers_with_mmr),
-            storages: storages.into_iter().collect(),
+            headers_with_mmr: process_headers(headers_with_mmr, |header| header.proof.leaf_idx),
+            storages,
         })
     }
 
@@ -438,6 +521,14 @@ impl<'a> Fetcher<'a> {
         #[cfg(feature = "progress_bars")]
         self.progress_bars.unconstrained_bytecode.safe_finish_with_message();
 
+        data.extend(
+            self.proof_keys
+                .unconstrained
+                .oracles
+                .iter()
+                .map(|(key, value)| (key.hash(), UnconstrainedStateValue::Oracle(value.clone()))),
+        );
+
         Ok(UnconstrainedState(data))
     }
 
@@ -451,12 +542,15 @@ impl<'a> Fetcher<'a> {
         for (_trie_label, actions) in actions.into_iter() {
             let request_payload = GetStateProofsRequest { actions };
 
-            let response = client
-                .post(format!("{}/get_state_proofs", state_server_url))
-                .header("content-type", "application/json")
-                .json(&request_payload)
-                .send()
-                .await?;
+            let response = with_api_key(
+                client
+                    .post(format!("{}/get_state_proofs", state_server_url))
+                    .header("content-type", "application/json")
+                    .json(&request_payload),
+            )
+            .send()
+            .await?
+            .error_for_status()?;
 
             let response_body: GetStateProofsResponse = response.json().await?;
             let state_proofs = response_body.state_proofs;
@@ -465,22 +559,81 @@ impl<'a> Fetcher<'a> {
 
         Ok(result)
     }
+
+    pub async fn collect_proofs(&self) -> Result<ProofsData, FetcherError> {
+        // Only chains referenced by the collected keys get a proofs entry, ordered by chain id so the proofs file and the
+        // program output layout do not change between runs
+        let evm_proofs =
+            futures::future::try_join_all(required_chain_ids_for_evm(self.proof_keys).into_iter().map(|chain_id| async move {
+                Ok::<_, FetcherError>(ChainProofs::Evm {
+                    chain_id,
+                    proofs: self.collect_evm_proofs(chain_id).await?,
+                })
+            }));
+        let starknet_proofs = futures::future::try_join_all(required_chain_ids_for_starknet(self.proof_keys).into_iter().map(
+            |chain_id| async move {
+                Ok::<_, FetcherError>(ChainProofs::Starknet {
+                    chain_id,
+                    proofs: self.collect_starknet_proofs(chain_id).await?,
+                })
+            },
+        ));
+
+        let (evm_proofs, starknet_proofs, unconstrained, state_proofs) = tokio::try_join!(
+            evm_proofs,
+            starknet_proofs,
+            self.collect_unconstrained_data(),
+            self.collect_state_proofs(),
+        )?;
+        let chain_proofs = evm_proofs.into_iter().chain(starknet_proofs).collect();
+
+        Ok(ProofsData {
+            chain_proofs,
+            unconstrained,
+            state_proofs,
+        })
+    }
 }
 
-pub fn process_headers<H>(headers_with_mmr: HashMap<MmrMeta, Vec<H>>) -> Vec<HeaderMmrMeta<H>>
+/// Orders account proofs by address, then block, as the same account can be proven at several blocks.
+fn sort_accounts(accounts: HashSet<Account>) -> Vec<Account> {
+    let mut accounts: Vec<_> = accounts.into_iter().collect();
+    accounts.sort_by_key(|account| (account.address, account.proofs.first().map(|proof| proof.block_number)));
+    accounts
+}
+
+/// Orders storage proofs by address, slot, then block, as the same slot can be proven at several blocks.
+fn sort_storages(storages: HashSet<Storage>) -> Vec<Storage> {
+    let mut storages: Vec<_> = storages.into_iter().collect();
+    storages.sort_by_key(|storage| {
+        (
+            storage.address,
+            storage.slot,
+            storage.proofs.first().map(|proof| proof.block_number),
+        )
+    });
+    storages
+}
+
+/// Deduplicates the headers of each MMR, ordering MMRs by id and size and headers by `sort_key` so the output is stable.
+pub fn process_headers<H, K>(headers_with_mmr: HashMap<MmrMeta, Vec<H>>, sort_key: impl Fn(&H) -> K) -> Vec<HeaderMmrMeta<H>>
 where
     H: Eq + std::hash::Hash + Clone,
+    K: Ord,
 {
-    headers_with_mmr
+    let mut headers_with_mmr: Vec<_> = headers_with_mmr
         .into_iter()
         .map(|(mmr_meta, headers)| {
-            let unique_headers: Vec<_> = headers.into_iter().collect::<HashSet<_>>().into_iter().collect();
+            let mut unique_headers: Vec<_> = headers.into_iter().collect::<HashSet<_>>().into_iter().collect();
+            unique_headers.sort_by_key(&sort_key);
             HeaderMmrMeta {
                 headers: unique_headers,
                 mmr_meta,
             }
         })
-        .collect()
+        .collect();
+    headers_with_mmr.sort_by(|a, b| (&a.mmr_meta.id, a.mmr_meta.size).cmp(&(&b.mmr_meta.id, b.mmr_meta.size)));
+    headers_with_mmr
 }
 
 pub fn parse_syscall_handler(
@@ -519,8 +672,13 @@ pub fn parse_syscall_handler(
 
     for key in syscall_handler.call_contract_handler.unconstrained_call_contract_handler.key_set {
         match key {
-            unconstrained::DryRunKey::Bytecode(value) => proof_keys.unconstrained.bytecode.insert(value),
-        };
+            unconstrained::DryRunKey::Bytecode(value) => {
+                proof_keys.unconstrained.bytecode.insert(value);
+            }
+            unconstrained::DryRunKey::Oracle { key, value } => {
+                proof_keys.unconstrained.oracles.insert(key, value);
+            }
+        }
     }
 
     Ok(proof_keys)
@@ -536,7 +694,7 @@ fn covers_all(ranges: &[RangeInclusive<u64>], blocks: &HashSet<u64>) -> bool {
     blocks.iter().all(|b| in_ranges(ranges, *b))
 }
 
-fn required_chain_ids_for_evm(proof_keys: &ProofKeys) -> HashSet<u128> {
+fn required_chain_ids_for_evm(proof_keys: &ProofKeys) -> BTreeSet<u128> {
     proof_keys
         .evm
         .header_keys
@@ -549,7 +707,7 @@ fn required_chain_ids_for_evm(proof_keys: &ProofKeys) -> HashSet<u128> {
         .collect()
 }
 
-fn required_chain_ids_for_starknet(proof_keys: &ProofKeys) -> HashSet<u128> {
+fn required_chain_ids_for_starknet(proof_keys: &ProofKeys) -> BTreeSet<u128> {
     proof_keys
         .starknet
         .header_keys
@@ -631,11 +789,31 @@ pub async fn infer_mmr_sources_from_indexer(proof_keys: &ProofKeys) -> Result<Ha
     evm_iter.chain(starknet_iter).collect()
 }
 
+/// Reads the optional MMR hasher and deployment configs, falling back to empty maps.
+pub fn load_mmr_configs(
+    mmr_hasher_config: Option<&PathBuf>,
+    mmr_deployment_config: Option<&PathBuf>,
+) -> Result<(MMRHasherConfig, MMRDeploymentConfig), FetcherError> {
+    let mmr_hasher_config = mmr_hasher_config
+        .map(|path| Ok::<MMRHasherConfig, crate::FetcherError>(serde_json::from_slice(&fs::read(path)?)?))
+        .transpose()?;
+
+    let mmr_deployment_config = mmr_deployment_config
+        .map(|path| Ok::<MMRDeploymentConfig, crate::FetcherError>(serde_json::from_slice(&fs::read(path)?)?))
+        .transpose()?;
+
+    Ok((mmr_hasher_config.unwrap_or_default(), mmr_deployment_config.unwrap_or_default()))
+}
+
 pub async fn run_with_args(args: Args) -> Result<(), FetcherError> {
     info!("Starting fetcher execution...");
     info!("Reading input file from: {}", args.inputs.display());
     let input_file = fs::read(&args.inputs)?;
 
+    if let Some(path) = &args.rpc_fixture {
+        RpcFixture::init_global(RpcFixture::open(path, args.rpc_fixture_mode)?)?;
+    }
+
     let syscall_handler: SyscallHandler<
         evm::CallContractHandler,
         starknet::CallContractHandler,
@@ -644,67 +822,64 @@ pub async fn run_with_args(args: Args) -> Result<(), FetcherError> {
     > = serde_json::from_slice(&input_file)?;
     let proof_keys = parse_syscall_handler(syscall_handler)?;
 
-    let mmr_hasher_config = args
-        .mmr_hasher_config
-        .as_ref()
-        .map(|path| Ok::<MMRHasherConfig, crate::FetcherError>(serde_json::from_slice(&fs::read(path)?)?))
-        .transpose()?;
-
-    let mmr_deployment_config = args
-        .mmr_deployment_config
-        .as_ref()
-        .map(|path| Ok::<MMRDeploymentConfig, crate::FetcherError>(serde_json::from_slice(&fs::read(path)?)?))
-        .transpose()?;
+    let (mmr_hasher_config, mmr_deployment_config) =
+        load_mmr_configs(args.mmr_hasher_config.as_ref(), args.mmr_deployment_config.as_ref())?;
 
-    let fetcher = Fetcher::new(
-        &proof_keys,
-        mmr_hasher_config.unwrap_or_default(),
-        mmr_deployment_config.unwrap_or_default(),
-    );
-    let (
-        eth_proofs_mainnet,
-        eth_proofs_sepolia,
-        starknet_proofs_mainnet,
-        starknet_proofs_sepolia,
-        optimism_proofs_mainnet,
-        optimism_proofs_sepolia,
-        unconstrained,
-        state_proofs,
-    ) = tokio::try_join!(
-        fetcher.collect_evm_proofs(ETHEREUM_MAINNET_CHAIN_ID),
-        fetcher.collect_evm_proofs(ETHEREUM_TESTNET_CHAIN_ID),
-        fetcher.collect_starknet_proofs(STARKNET_MAINNET_CHAIN_ID),
-        fetcher.collect_starknet_proofs(STARKNET_TESTNET_CHAIN_ID),
-        fetcher.collect_evm_proofs(OPTIMISM_MAINNET_CHAIN_ID),
-        fetcher.collect_evm_proofs(OPTIMISM_TESTNET_CHAIN_ID),
-        fetcher.collect_unconstrained_data(),
-        fetcher.collect_state_proofs(),
-    )?;
-    let chain_proofs = vec![
-        ChainProofs::EthereumMainnet(eth_proofs_mainnet),
-        ChainProofs::EthereumSepolia(eth_proofs_sepolia),
-        ChainProofs::StarknetMainnet(starknet_proofs_mainnet),
-        ChainProofs::StarknetSepolia(starknet_proofs_sepolia),
-        ChainProofs::OptimismMainnet(optimism_proofs_mainnet),
-        ChainProofs::OptimismSepolia(optimism_proofs_sepolia),
-    ];
+    let mut fetcher = Fetcher::new(&proof_keys, mmr_hasher_config, mmr_deployment_config);
+    if let Some(path) = &args.proof_cache {
+        fetcher = fetcher.with_proof_cache(ProofCache::open(path)?);
+    }
+    let proofs_data = fetcher.collect_proofs().await?;
 
     info!("Writing proofs to: {}", args.output.display());
 
     fs::write(
         args.output,
-        serde_json::to_string_pretty(
-            &(ProofsData {
-                chain_proofs,
-                unconstrained,
-                state_proofs,
-            }),
-        )
-        .map_err(|e| crate::FetcherError::IO(e.into()))?
-        .as_bytes(),
+        serde_json::to_string_pretty(&proofs_data)
+            .map_err(|e| crate::FetcherError::IO(e.into()))?
+            .as_bytes(),
     )?;
 
     info!("Proofs have been saved successfully.");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::{Address, B256};
+    use types::proofs::mpt::MPTProof;
+
+    use super::*;
+
+    fn proof(block_number: u64) -> Vec<MPTProof> {
+        vec![MPTProof::new(block_number, vec![Bytes::from(block_number.to_be_bytes().to_vec())])]
+    }
+
+    #[test]
+    fn accounts_at_several_blocks_sort_by_block() {
+        let address = Address::repeat_byte(0x11);
+        for blocks in [[7692344, 7692345], [7692345, 7692344]] {
+            let accounts: HashSet<Account> = blocks.iter().map(|block| Account::new(address, proof(*block))).collect();
+            let blocks: Vec<_> = sort_accounts(accounts)
+                .iter()
+                .map(|account| account.proofs[0].block_number)
+                .collect();
+            assert_eq!(blocks, vec![7692344, 7692345]);
+        }
+    }
+
+    #[test]
+    fn storages_at_several_blocks_sort_by_block() {
+        let address = Address::repeat_byte(0x11);
+        let slot = B256::with_last_byte(1);
+        for blocks in [[7692344, 7692345], [7692345, 7692344]] {
+            let storages: HashSet<Storage> = blocks.iter().map(|block| Storage::new(address, slot, proof(*block))).collect();
+            let blocks: Vec<_> = sort_storages(storages)
+                .iter()
+                .map(|storage| storage.proofs[0].block_number)
+                .collect();
+            assert_eq!(blocks, vec![7692344, 7692345]);
+        }
+    }
+}
@@ -13,7 +13,11 @@ use fetcher::{Args, FetcherError};
 use futures as _;
 use indexer_client::{self as _};
 use indicatif as _;
+use r2d2 as _;
+use r2d2_sqlite as _;
 use reqwest as _;
+use rusqlite as _;
+use serde as _;
 use serde_json as _;
 use starknet_types_core as _;
 use state_server as _;
@@ -21,14 +25,18 @@ use syscall_handler as _;
 use thiserror as _;
 use tracing as _;
 use tracing_subscriber::EnvFilter;
-use types as _;
+use types::chain_registry::ChainRegistry;
 
 #[tokio::main]
 async fn main() -> Result<(), FetcherError> {
     dotenvy::dotenv().ok();
-    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
 
     let args = Args::try_parse_from(std::env::args()).map_err(FetcherError::Args)?;
+    ChainRegistry::init_global_from(None)?;
 
     fetcher::run_with_args(args).await
 }
@@ -10,7 +10,8 @@ use alloy::{
 use eth_trie_proofs::{tx_receipt_trie::TxReceiptsMptHandler, tx_trie::TxsMptHandler};
 use indexer_client::models::BlockHeader;
 use types::{
-    keys::{self, evm::get_corresponding_rpc_url},
+    chain_registry::ChainRegistry,
+    keys,
     proofs::{
         evm::{account::Account, header::Header, receipt::Receipt, storage::Storage, transaction::Transaction},
         header::{HeaderMmrMeta, HeaderProof},
@@ -20,7 +21,10 @@ use types::{
 };
 
 use super::FlattenedKey;
-use crate::FetcherError;
+use crate::{
+    cache::{cached, CacheKey, ProofCache},
+    FetcherError,
+};
 
 #[derive(Debug, Default)]
 pub struct ProofKeys {
@@ -42,6 +46,27 @@ impl ProofKeys {
         accumulates_chain_id: u128,
         block_number: u64,
         mmr_hashing_function: HashingFunction,
+        cache: Option<&ProofCache>,
+    ) -> Result<HeaderMmrMeta<Header>, FetcherError> {
+        let cache_key = CacheKey::new(
+            "evm_header",
+            accumulates_chain_id,
+            block_number,
+            format!("{:#x}:{}", deployed_on_chain_id, serde_json::to_string(&mmr_hashing_function)?),
+        );
+        cached(
+            cache,
+            cache_key,
+            Self::fetch_header_proof_uncached(deployed_on_chain_id, accumulates_chain_id, block_number, mmr_hashing_function),
+        )
+        .await
+    }
+
+    async fn fetch_header_proof_uncached(
+        deployed_on_chain_id: u128,
+        accumulates_chain_id: u128,
+        block_number: u64,
+        mmr_hashing_function: HashingFunction,
     ) -> Result<HeaderMmrMeta<Header>, FetcherError> {
         let (mmr_proof, mmr_meta) =
             super::ProofKeys::fetch_mmr_proof(deployed_on_chain_id, accumulates_chain_id, block_number, mmr_hashing_function).await?;
@@ -76,37 +101,66 @@ impl ProofKeys {
         })
     }
 
-    pub async fn fetch_account_proof(key: &keys::evm::account::Key) -> Result<Account, FetcherError> {
-        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        let value = provider
-            .get_proof(key.address, vec![])
-            .block_id(key.block_number.into())
-            .await
-            .map_err(|e| FetcherError::InternalError(e.to_string()))?;
+    pub async fn fetch_account_proof(key: &keys::evm::account::Key, cache: Option<&ProofCache>) -> Result<Account, FetcherError> {
+        let cache_key = CacheKey::new("evm_account", key.chain_id, key.block_number, key.address.to_string());
+        cached(cache, cache_key, Self::fetch_account_proof_uncached(key)).await
+    }
+
+    async fn fetch_account_proof_uncached(key: &keys::evm::account::Key) -> Result<Account, FetcherError> {
+        let value = ChainRegistry::global()
+            .with_rpc_fallback(key.chain_id, |rpc_url| async move {
+                let url = Url::parse(&rpc_url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                let provider = RootProvider::<Ethereum>::new_http(url);
+                provider
+                    .get_proof(key.address, vec![])
+                    .block_id(key.block_number.into())
+                    .await
+                    .map_err(|e| FetcherError::InternalError(e.to_string()))
+            })
+            .await?;
         Ok(Account::new(
             value.address,
             vec![MPTProof::new(key.block_number, value.account_proof)],
         ))
     }
 
-    pub async fn fetch_storage_proof(key: &keys::evm::storage::Key) -> Result<(Account, Storage), FetcherError> {
-        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        let value = provider
-            .get_proof(key.address, vec![key.storage_slot])
-            .block_id(key.block_number.into())
-            .await
-            .map_err(|e| FetcherError::InternalError(e.to_string()))?;
+    pub async fn fetch_storage_proof(
+        key: &keys::evm::storage::Key,
+        cache: Option<&ProofCache>,
+    ) -> Result<(Account, Storage), FetcherError> {
+        let cache_key = CacheKey::new(
+            "evm_storage",
+            key.chain_id,
+            key.block_number,
+            format!("{}:{}", key.address, key.storage_slot),
+        );
+        cached(cache, cache_key, Self::fetch_storage_proof_uncached(key)).await
+    }
+
+    async fn fetch_storage_proof_uncached(key: &keys::evm::storage::Key) -> Result<(Account, Storage), FetcherError> {
+        let value = ChainRegistry::global()
+            .with_rpc_fallback(key.chain_id, |rpc_url| async move {
+                let url = Url::parse(&rpc_url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                let provider = RootProvider::<Ethereum>::new_http(url);
+                provider
+                    .get_proof(key.address, vec![key.storage_slot])
+                    .block_id(key.block_number.into())
+                    .await
+                    .map_err(|e| FetcherError::InternalError(e.to_string()))
+            })
+            .await?;
+        // The proof of an unset slot is its exclusion path, it is empty if the account has no storage at all
+        let storage_proof = value
+            .storage_proof
+            .into_iter()
+            .find(|proof| proof.key.as_b256() == key.storage_slot)
+            .ok_or_else(|| FetcherError::InternalError(format!("missing storage proof for slot {}", key.storage_slot)))?;
         Ok((
             Account::new(value.address, vec![MPTProof::new(key.block_number, value.account_proof)]),
             Storage::new(
                 value.address,
                 key.storage_slot,
-                vec![MPTProof::new(
-                    key.block_number,
-                    value.storage_proof.into_iter().flat_map(|f| f.proof).collect(),
-                )],
+                vec![MPTProof::new(key.block_number, storage_proof.proof)],
             ),
         ))
     }
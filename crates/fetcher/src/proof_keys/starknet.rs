@@ -6,7 +6,8 @@ use indexer_client::models::BlockHeader;
 use reqwest::Url;
 use starknet_types_core::felt::FromStrError;
 use types::{
-    keys::{self, starknet::get_corresponding_rpc_url},
+    chain_registry::ChainRegistry,
+    keys,
     proofs::{
         header::{HeaderMmrMeta, HeaderProof},
         starknet::{self, header::Header, storage::Storage},
@@ -15,7 +16,10 @@ use types::{
 };
 
 use super::FlattenedKey;
-use crate::FetcherError;
+use crate::{
+    cache::{cached, CacheKey, ProofCache},
+    FetcherError,
+};
 
 #[derive(Debug, Default)]
 pub struct ProofKeys {
@@ -35,6 +39,27 @@ impl ProofKeys {
         accumulates_chain_id: u128,
         block_number: u64,
         mmr_hashing_function: HashingFunction,
+        cache: Option<&ProofCache>,
+    ) -> Result<HeaderMmrMeta<Header>, FetcherError> {
+        let cache_key = CacheKey::new(
+            "starknet_header",
+            accumulates_chain_id,
+            block_number,
+            format!("{:#x}:{}", deployed_on_chain_id, serde_json::to_string(&mmr_hashing_function)?),
+        );
+        cached(
+            cache,
+            cache_key,
+            Self::fetch_header_proof_uncached(deployed_on_chain_id, accumulates_chain_id, block_number, mmr_hashing_function),
+        )
+        .await
+    }
+
+    async fn fetch_header_proof_uncached(
+        deployed_on_chain_id: u128,
+        accumulates_chain_id: u128,
+        block_number: u64,
+        mmr_hashing_function: HashingFunction,
     ) -> Result<HeaderMmrMeta<Header>, FetcherError> {
         let (mmr_proof, meta) =
             super::ProofKeys::fetch_mmr_proof(deployed_on_chain_id, accumulates_chain_id, block_number, mmr_hashing_function).await?;
@@ -66,25 +91,41 @@ impl ProofKeys {
         }
     }
 
-    pub async fn fetch_storage_proof(key: &keys::starknet::storage::Key) -> Result<Storage, FetcherError> {
-        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-        let response = reqwest::Client::new()
-            .post(Url::parse(&rpc_url).unwrap().join("/rpc/v0_9").unwrap())
-            .json(&serde_json::json!({
-                "jsonrpc": "2.0",
-                "method": "starknet_getStorageProof",
-                "params": [
-                    {"block_number": key.block_number},
-                    [],
-                    [key.address],
-                    [{"contract_address": key.address, "storage_keys": [key.storage_slot]}]
-                ],
-                "id": 1
-            }))
-            .send()
-            .await?;
+    pub async fn fetch_storage_proof(key: &keys::starknet::storage::Key, cache: Option<&ProofCache>) -> Result<Storage, FetcherError> {
+        let cache_key = CacheKey::new(
+            "starknet_storage",
+            key.chain_id,
+            key.block_number,
+            format!("{:#x}:{:#x}", key.address, key.storage_slot),
+        );
+        cached(cache, cache_key, Self::fetch_storage_proof_uncached(key)).await
+    }
 
-        let response_text = response.text().await?;
+    async fn fetch_storage_proof_uncached(key: &keys::starknet::storage::Key) -> Result<Storage, FetcherError> {
+        let response_text = ChainRegistry::global()
+            .with_rpc_fallback(key.chain_id, |rpc_url| async move {
+                let url = Url::parse(&rpc_url)
+                    .and_then(|url| url.join("/rpc/v0_9"))
+                    .map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                let response = reqwest::Client::new()
+                    .post(url)
+                    .json(&serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "method": "starknet_getStorageProof",
+                        "params": [
+                            {"block_number": key.block_number},
+                            [],
+                            [key.address],
+                            [{"contract_address": key.address, "storage_keys": [key.storage_slot]}]
+                        ],
+                        "id": 1
+                    }))
+                    .send()
+                    .await?;
+
+                Ok::<_, FetcherError>(response.text().await?)
+            })
+            .await?;
 
         let json_rpc_response: serde_json::Value =
             serde_json::from_str(&response_text).map_err(|e| FetcherError::JsonDeserializationError(e.to_string()))?;
@@ -1,28 +1,44 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use alloy::{
     network::Ethereum,
     primitives::Bytes,
     providers::{Provider, RootProvider},
 };
+use dry_hint_processor::rpc_fixture::recorded;
 use reqwest::Url;
-use types::keys::{self, evm::get_corresponding_rpc_url};
+use types::{
+    cairo::unconstrained::oracle::{OracleKey, OracleValue},
+    chain_registry::ChainRegistry,
+    keys,
+};
 
 use crate::FetcherError;
 
 #[derive(Debug, Default)]
 pub struct ProofKeys {
     pub bytecode: HashSet<keys::evm::account::Key>,
+    /// Oracle values are resolved during the dry run, so they are carried over as is.
+    pub oracles: HashMap<OracleKey, OracleValue>,
 }
 
 impl ProofKeys {
     pub async fn fetch_bytecode(key: &keys::evm::account::Key) -> Result<Bytes, FetcherError> {
-        let rpc_url = get_corresponding_rpc_url(key).map_err(|e| FetcherError::InternalError(e.to_string()))?;
-        let provider = RootProvider::<Ethereum>::new_http(Url::parse(&rpc_url).unwrap());
-        provider
-            .get_code_at(key.address)
-            .block_id(key.block_number.into())
-            .await
-            .map_err(|e| FetcherError::InternalError(e.to_string()))
+        recorded(key.chain_id, "fetcher_evm_bytecode", key, async {
+            Ok::<_, FetcherError>(
+                ChainRegistry::global()
+                    .with_rpc_fallback(key.chain_id, |rpc_url| async move {
+                        let url = Url::parse(&rpc_url).map_err(|e| FetcherError::InternalError(e.to_string()))?;
+                        let provider = RootProvider::<Ethereum>::new_http(url);
+                        provider
+                            .get_code_at(key.address)
+                            .block_id(key.block_number.into())
+                            .await
+                            .map_err(|e| FetcherError::InternalError(e.to_string()))
+                    })
+                    .await?,
+            )
+        })
+        .await
     }
 }
@@ -170,6 +170,12 @@ pub fn unconstrained_write_lists(
                 BytecodeLeWords::from(data).to_memory(vm, segment)?;
                 values_to_write.push(segment.into());
             }
+            UnconstrainedStateValue::Oracle(value) => {
+                keys_to_write.push(key.into());
+                let segment = vm.add_memory_segment();
+                value.to_memory(vm, segment)?;
+                values_to_write.push(segment.into());
+            }
         }
     }
 
@@ -10,6 +10,7 @@ use cairo_vm::{
     Felt252,
 };
 use num_bigint::BigUint;
+use tracing::info;
 
 use crate::vars;
 
@@ -35,13 +36,13 @@ pub fn hint_target_task_hash(
 
     let target_task_hash = task_hash[0].to_biguint() + FELT_TWO_POW_128.to_biguint() * task_hash[1].to_biguint();
 
-    println!("Task Hash: 0x{:x}", target_task_hash);
+    info!("Task Hash: 0x{:x}", target_task_hash);
 
     Ok(())
 }
 
 pub const HINT_IS_LEFT_SMALLER: &str =
-    "def flip_endianess(val):\n    val_hex = hex(val)[2:]\n\n    if len(val_hex) % 2:\n        val_hex = '0' + val_hex\n\n    # Convert hex string to bytes\n    byte_data = bytes.fromhex(val_hex)\n    num = int.from_bytes(byte_data, byteorder=\"little\")\n\n    return num\n\n# In LE Uint256, the low and high are reversed\nleft = flip_endianess(ids.left.low) * 2**128 + flip_endianess(ids.left.high)\nright = flip_endianess(ids.right.low) * 2**128 + flip_endianess(ids.right.high)\n\n# Compare the values to derive correct hashing order\nif left < right:\n    ids.is_left_smaller = 1\n    #print(f\"H({hex(left)}, {hex(right)})\")\nelse:\n    #print(f\"H({hex(right)}, {hex(left)})\")\n    ids.is_left_smaller = 0";
+    "def flip_endianess(val):\n    # Reverse all 16 bytes of the Uint256 half, so leading zero bytes keep their weight\n    return int.from_bytes(val.to_bytes(16, \"big\"), byteorder=\"little\")\n\n# In LE Uint256, the low and high are reversed\nleft = flip_endianess(ids.left.low) * 2**128 + flip_endianess(ids.left.high)\nright = flip_endianess(ids.right.low) * 2**128 + flip_endianess(ids.right.high)\n\n# Compare the values to derive correct hashing order\nif left < right:\n    ids.is_left_smaller = 1\n    #print(f\"H({hex(left)}, {hex(right)})\")\nelse:\n    #print(f\"H({hex(right)}, {hex(left)})\")\n    ids.is_left_smaller = 0";
 
 pub fn hint_is_left_smaller(
     vm: &mut VirtualMachine,
@@ -49,30 +50,12 @@ pub fn hint_is_left_smaller(
     hint_data: &HintProcessorData,
     _constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
-    let left = vm
-        .get_continuous_range(
-            get_relocatable_from_var_name(vars::ids::LEFT, vm, &hint_data.ids_data, &hint_data.ap_tracking)?,
-            2,
-        )?
-        .into_iter()
-        .map(|x| x.get_int().unwrap())
-        .collect::<Vec<Felt252>>();
-
-    let right = vm
-        .get_continuous_range(
-            get_relocatable_from_var_name(vars::ids::RIGHT, vm, &hint_data.ids_data, &hint_data.ap_tracking)?,
-            2,
-        )?
-        .into_iter()
-        .map(|x| x.get_int().unwrap())
-        .collect::<Vec<Felt252>>();
-
-    let left_flipped =
-        BigUint::from_bytes_le(&left[0].to_bytes_be()) * FELT_TWO_POW_128.to_biguint() + BigUint::from_bytes_le(&left[1].to_bytes_be());
-    let right_flipped =
-        BigUint::from_bytes_le(&right[0].to_bytes_be()) * FELT_TWO_POW_128.to_biguint() + BigUint::from_bytes_le(&right[1].to_bytes_be());
+    let uint256 = |name: &str| -> Result<[Felt252; 2], HintError> {
+        let ptr = get_relocatable_from_var_name(name, vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
+        Ok([vm.get_integer(ptr)?.into_owned(), vm.get_integer((ptr + 1)?)?.into_owned()])
+    };
 
-    let insert = if left_flipped < right_flipped {
+    let insert = if is_left_smaller(uint256(vars::ids::LEFT)?, uint256(vars::ids::RIGHT)?) {
         Felt252::ONE
     } else {
         Felt252::ZERO
@@ -86,3 +69,12 @@ pub fn hint_is_left_smaller(
         &hint_data.ap_tracking,
     )
 }
+
+/// Compares two little endian `(low, high)` Uint256 as the big endian digests they encode, which is the byte order
+/// `hash_pair` sorts a pair by. Each half is reversed over its full 16 bytes, so leading zero bytes are kept.
+pub fn is_left_smaller(left: [Felt252; 2], right: [Felt252; 2]) -> bool {
+    let flip = |half: &Felt252| BigUint::from_bytes_le(&half.to_bytes_be()[16..]);
+    let digest = |value: &[Felt252; 2]| flip(&value[0]) * FELT_TWO_POW_128.to_biguint() + flip(&value[1]);
+
+    digest(&left) < digest(&right)
+}
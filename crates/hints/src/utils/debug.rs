@@ -16,7 +16,7 @@ pub fn print_felt_hex(
     _constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
     let value = get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-    println!("Value: {}", value.to_hex_string());
+    eprintln!("Value: {}", value.to_hex_string());
     Ok(())
 }
 
@@ -29,7 +29,7 @@ pub fn print_felt(
     _constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
     let value = get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
-    println!("Value: {}", value);
+    eprintln!("Value: {}", value);
     Ok(())
 }
 
@@ -44,7 +44,7 @@ pub fn print_string(
     let value = get_integer_from_var_name("value", vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
     let bytes = value.to_bytes_be();
     let ascii = String::from_utf8_lossy(&bytes);
-    println!("String: {}", ascii);
+    eprintln!("String: {}", ascii);
     Ok(())
 }
 
@@ -56,6 +56,6 @@ pub fn print_debug_breakpoint(
     _hint_data: &HintProcessorData,
     _constants: &HashMap<String, Felt252>,
 ) -> Result<(), HintError> {
-    println!("DEBUG BREAKPOINT");
+    eprintln!("DEBUG BREAKPOINT");
     Ok(())
 }
@@ -101,6 +101,8 @@ pub mod ids {
     pub const Q: &str = "q";
     pub const R: &str = "r";
     pub const RESULT: &str = "result";
+    pub const RETDATA_SIZE: &str = "retdata_size";
+    pub const RETDATA: &str = "retdata";
     pub const RETURN_BUILTIN_PTRS: &str = "return_builtin_ptrs";
     pub const RIGHT: &str = "right";
     pub const RLP_LEN: &str = "rlp_len";
@@ -33,6 +33,7 @@ pub fn hint_state_proof_enter_scope(
     let state_proof: Box<dyn Any> = match state_proofs[idx - 1].to_owned() {
         StateProof::Read(state_proof) => Box::new(state_proof),
         StateProof::Write(state_proof) => Box::new(state_proof),
+        StateProof::Delete(state_proof) => Box::new(StateProofWrite::from(state_proof)),
     };
     let dict_manager: Box<dyn Any> = Box::new(exec_scopes.get_dict_manager()?);
 
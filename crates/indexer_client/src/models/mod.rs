@@ -3,10 +3,7 @@ use std::{num::ParseIntError, ops::Deref};
 use alloy::primitives::map::HashMap;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use types::{
-    HashingFunction, ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID, OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID,
-    STARKNET_MAINNET_CHAIN_ID, STARKNET_TESTNET_CHAIN_ID,
-};
+use types::{chain_registry::ChainRegistry, HashingFunction};
 
 pub mod accumulators;
 pub mod blocks;
@@ -24,18 +21,13 @@ pub struct MMRHasherConfig(HashMap<u128, HashingFunction>);
 
 impl Default for MMRHasherConfig {
     fn default() -> Self {
-        Self(
-            [
-                (ETHEREUM_MAINNET_CHAIN_ID, HashingFunction::Poseidon),
-                (ETHEREUM_TESTNET_CHAIN_ID, HashingFunction::Poseidon),
-                (STARKNET_MAINNET_CHAIN_ID, HashingFunction::Poseidon),
-                (STARKNET_TESTNET_CHAIN_ID, HashingFunction::Poseidon),
-                (OPTIMISM_MAINNET_CHAIN_ID, HashingFunction::Poseidon),
-                (OPTIMISM_TESTNET_CHAIN_ID, HashingFunction::Poseidon),
-            ]
-            .into_iter()
-            .collect(),
-        )
+        Self::from(ChainRegistry::global())
+    }
+}
+
+impl From<&ChainRegistry> for MMRHasherConfig {
+    fn from(registry: &ChainRegistry) -> Self {
+        Self(registry.chains.iter().map(|chain| (chain.chain_id, chain.mmr_hasher)).collect())
     }
 }
 
@@ -51,17 +43,18 @@ pub struct MMRDeploymentConfig(HashMap<u128, u128>);
 
 impl Default for MMRDeploymentConfig {
     fn default() -> Self {
+        Self::from(ChainRegistry::global())
+    }
+}
+
+impl From<&ChainRegistry> for MMRDeploymentConfig {
+    fn from(registry: &ChainRegistry) -> Self {
         Self(
-            [
-                (ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_MAINNET_CHAIN_ID),
-                (ETHEREUM_TESTNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID),
-                (STARKNET_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID),
-                (STARKNET_TESTNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID),
-                (OPTIMISM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID),
-                (OPTIMISM_TESTNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID),
-            ]
-            .into_iter()
-            .collect(),
+            registry
+                .chains
+                .iter()
+                .map(|chain| (chain.chain_id, chain.mmr_deployment_chain_id()))
+                .collect(),
         )
     }
 }
@@ -1,19 +1,26 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
 use cairo_vm::{
-    hint_processor::builtin_hint_processor::{
-        builtin_hint_processor_definition::HintProcessorData, hint_utils::get_relocatable_from_var_name,
+    hint_processor::{
+        builtin_hint_processor::{
+            builtin_hint_processor_definition::HintProcessorData,
+            hint_utils::{get_integer_from_var_name, get_ptr_from_var_name, get_relocatable_from_var_name},
+        },
+        hint_processor_utils::felt_to_usize,
     },
     types::exec_scope::ExecutionScopes,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
     Felt252,
 };
 use hints::vars;
+use tracing::info;
 
 use super::CustomHintProcessor;
 
 pub const HINT_OUTPUT: &str = "print(\"result\", [hex(ids.result.low), hex(ids.result.high)])";
 
+pub const HINT_MODULE_RESULTS: &str = "module_results = [memory[ids.retdata + i] for i in range(ids.retdata_size)]";
+
 impl CustomHintProcessor {
     pub fn hint_output(
         &mut self,
@@ -25,12 +32,31 @@ impl CustomHintProcessor {
         let result_ptr = get_relocatable_from_var_name(vars::ids::RESULT, vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
 
         let result = vm
-            .get_continuous_range(result_ptr, 2)?
+            .get_integer_range(result_ptr, 2)?
             .into_iter()
-            .map(|v| v.get_int().unwrap())
+            .map(Cow::into_owned)
             .collect::<Vec<Felt252>>();
 
-        println!("result: {}, {}", result[0], result[1]);
+        info!("result: {}, {}", result[0], result[1]);
+        Ok(())
+    }
+
+    pub fn hint_module_results(
+        &mut self,
+        vm: &mut VirtualMachine,
+        _exec_scopes: &mut ExecutionScopes,
+        hint_data: &HintProcessorData,
+        _constants: &HashMap<String, Felt252>,
+    ) -> Result<(), HintError> {
+        let retdata = get_ptr_from_var_name(vars::ids::RETDATA, vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
+        let retdata_size = get_integer_from_var_name(vars::ids::RETDATA_SIZE, vm, &hint_data.ids_data, &hint_data.ap_tracking)?;
+
+        // A non integer return value fails the run instead of panicking
+        self.module_results = vm
+            .get_integer_range(retdata, felt_to_usize(&retdata_size)?)?
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect();
         Ok(())
     }
 }
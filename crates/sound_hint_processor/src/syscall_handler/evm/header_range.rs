@@ -0,0 +1,69 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cairo_vm::{
+    hint_processor::builtin_hint_processor::dict_manager::DictManager, types::relocatable::Relocatable, vm::vm_core::VirtualMachine,
+    Felt252,
+};
+use syscall_handler::{memorizer::Memorizer, traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{evm::header::FunctionId, traits::CairoType},
+    keys::evm::{
+        header,
+        header_range::{CairoKey, Key},
+    },
+};
+
+use super::header::HeaderCallHandler;
+
+#[derive(Debug)]
+pub struct HeaderRangeCallHandler {
+    pub memorizer: Memorizer,
+    pub dict_manager: Rc<RefCell<DictManager>>,
+}
+
+impl HeaderRangeCallHandler {
+    pub fn new(memorizer: Memorizer, dict_manager: Rc<RefCell<DictManager>>) -> Self {
+        Self { memorizer, dict_manager }
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl CallHandler for HeaderRangeCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        match Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?) {
+            // Only fields fitting a single Uint256 can be packed into the range result
+            Some(FunctionId::Bloom | FunctionId::ExtraData) => Err(SyscallExecutionError::InvalidSyscallInput {
+                input: selector,
+                info: "Variable length header fields are not supported in header ranges".to_string(),
+            }),
+            Some(function_id) => Ok(function_id),
+            None => Err(SyscallExecutionError::InvalidSyscallInput {
+                input: selector,
+                info: "Invalid function identifier".to_string(),
+            }),
+        }
+    }
+
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        let mut result = Vec::new();
+        for header_key in key.header_keys() {
+            let mut handler = HeaderCallHandler::new(Memorizer::new(self.memorizer.dict_ptr), self.dict_manager.clone());
+            result.extend(handler.handle(header::CairoKey::from(header_key), function_id, vm).await?);
+        }
+        Ok(result)
+    }
+}
@@ -54,35 +54,38 @@ impl CallHandler for LogsCallHandler {
         let ptr = self
             .memorizer
             .read_key_ptr(&MaybeRelocatable::Int(key.hash()), self.dict_manager.clone())?;
+        let key = log::Key::try_from(key).unwrap();
 
-        // data is the rlp-encoded receipt (injected by the verified mpt proof Cairo0 memorizer)
-        let mut data = vm.get_integer(ptr)?.to_bytes_le().to_vec();
-        let tx_type = data[0];
-        let mut extra_len = 0;
-        // If not a legacy tx, remove the tx type from the receipt
-        if tx_type > 0 && tx_type < 4 {
-            // Pop the tx type from the receipt, rest will be valid rlp
-            data.remove(0);
-            extra_len = 1;
-        }
+        Ok(decode_receipt(vm, ptr)?.handle(function_id, key.log_index))
+    }
+}
 
-        data.resize(128000, 0); // 128kb is max tx size
-        let header =
-            alloy_rlp::Header::decode(&mut data.as_slice()).map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
-        let length = header.length_with_payload() + extra_len;
-        let rlp = vm
-            .get_integer_range(ptr, length.div_ceil(8))?
-            .into_iter()
-            .flat_map(|f| f.to_bytes_le().into_iter().take(8))
-            .take(length)
-            .collect::<Vec<u8>>();
+/// Decodes the rlp-encoded receipt at `ptr` (injected by the verified mpt proof Cairo0 memorizer).
+pub(crate) fn decode_receipt(vm: &VirtualMachine, ptr: Relocatable) -> SyscallResult<CairoReceiptWithBloom> {
+    let mut data = vm.get_integer(ptr)?.to_bytes_le().to_vec();
+    let tx_type = data[0];
+    let mut extra_len = 0;
+    // If not a legacy tx, remove the tx type from the receipt
+    if tx_type > 0 && tx_type < 4 {
+        // Pop the tx type from the receipt, rest will be valid rlp
+        data.remove(0);
+        extra_len = 1;
+    }
 
-        let key = log::Key::try_from(key).unwrap();
+    data.resize(128000, 0); // 128kb is max tx size
+    let header =
+        alloy_rlp::Header::decode(&mut data.as_slice()).map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
+    let length = header.length_with_payload() + extra_len;
+    let rlp = vm
+        .get_integer_range(ptr, length.div_ceil(8))?
+        .into_iter()
+        .flat_map(|f| f.to_bytes_le().into_iter().take(8))
+        .take(length)
+        .collect::<Vec<u8>>();
 
-        if extra_len != 0 {
-            Ok(CairoReceiptWithBloom::rlp_decode(&rlp[1..]).handle(function_id, key.log_index))
-        } else {
-            Ok(CairoReceiptWithBloom::rlp_decode(&rlp).handle(function_id, key.log_index))
-        }
+    if extra_len != 0 {
+        Ok(CairoReceiptWithBloom::rlp_decode(&rlp[1..]))
+    } else {
+        Ok(CairoReceiptWithBloom::rlp_decode(&rlp))
     }
 }
@@ -0,0 +1,102 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cairo_vm::{
+    hint_processor::builtin_hint_processor::dict_manager::DictManager,
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::vm_core::VirtualMachine,
+    Felt252,
+};
+use syscall_handler::{memorizer::Memorizer, traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{
+        evm::{
+            header,
+            log_absence::{bloom_from_chunks, bloom_may_contain, FunctionId},
+        },
+        traits::CairoType,
+    },
+    keys::evm::{
+        log_absence::{CairoKey, Key},
+        receipt,
+    },
+};
+
+use super::{header::HeaderCallHandler, log::decode_receipt};
+
+#[derive(Debug)]
+pub struct LogAbsenceCallHandler {
+    pub memorizer: Memorizer,
+    pub dict_manager: Rc<RefCell<DictManager>>,
+}
+
+impl LogAbsenceCallHandler {
+    pub fn new(memorizer: Memorizer, dict_manager: Rc<RefCell<DictManager>>) -> Self {
+        Self { memorizer, dict_manager }
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl CallHandler for LogAbsenceCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?)
+        .ok_or(SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: "Invalid function identifier".to_string(),
+        })
+    }
+
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        match function_id {
+            FunctionId::NoMatchingLog => {
+                let bloom_chunks = HeaderCallHandler::new(Memorizer::new(self.memorizer.dict_ptr), self.dict_manager.clone())
+                    .handle(key.header_key().into(), header::FunctionId::Bloom, vm)
+                    .await?;
+                let bloom = bloom_from_chunks(&bloom_chunks);
+                if !bloom_may_contain(&bloom, key.address, key.topic0) {
+                    return Ok(vec![Felt252::ZERO]);
+                }
+
+                // The dry run memorizes every receipt of the block, so the scan stops at the first missing index
+                let mut transactions_count = 0;
+                loop {
+                    let receipt_key = receipt::CairoKey::from(receipt::Key {
+                        chain_id: key.chain_id,
+                        block_number: key.block_number,
+                        transaction_index: transactions_count,
+                    });
+                    let Ok(ptr) = self
+                        .memorizer
+                        .read_key_ptr(&MaybeRelocatable::Int(receipt_key.hash()), self.dict_manager.clone())
+                    else {
+                        break;
+                    };
+                    if decode_receipt(vm, ptr)?.matching_logs(key.address, key.topic0).next().is_some() {
+                        return Err(SyscallExecutionError::InternalError(
+                            format!(
+                                "matching log found in block {} at transaction {}",
+                                key.block_number, transactions_count
+                            )
+                            .into(),
+                        ));
+                    }
+                    transactions_count += 1;
+                }
+                Ok(vec![Felt252::from(transactions_count)])
+            }
+        }
+    }
+}
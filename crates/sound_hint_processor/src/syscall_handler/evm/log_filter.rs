@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cairo_vm::{
+    hint_processor::builtin_hint_processor::dict_manager::DictManager,
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::vm_core::VirtualMachine,
+    Felt252,
+};
+use syscall_handler::{memorizer::Memorizer, traits::CallHandler, SyscallExecutionError, SyscallResult};
+use types::{
+    cairo::{
+        evm::{
+            header,
+            log_absence::{bloom_from_chunks, bloom_may_contain},
+            log_filter::{FunctionId, LogFilterResult, LogPosition},
+        },
+        traits::CairoType,
+    },
+    keys::evm::{
+        log_filter::{CairoKey, Key},
+        receipt,
+    },
+};
+
+use super::{header::HeaderCallHandler, log::decode_receipt};
+
+#[derive(Debug)]
+pub struct LogsByFilterCallHandler {
+    pub memorizer: Memorizer,
+    pub dict_manager: Rc<RefCell<DictManager>>,
+}
+
+impl LogsByFilterCallHandler {
+    pub fn new(memorizer: Memorizer, dict_manager: Rc<RefCell<DictManager>>) -> Self {
+        Self { memorizer, dict_manager }
+    }
+}
+
+#[allow(refining_impl_trait)]
+impl CallHandler for LogsByFilterCallHandler {
+    type Key = Key;
+    type Id = FunctionId;
+    type CallHandlerResult = Vec<Felt252>;
+
+    fn derive_key(vm: &VirtualMachine, ptr: &mut Relocatable) -> SyscallResult<Self::Key> {
+        let ret = CairoKey::from_memory(vm, *ptr)?;
+        *ptr = (*ptr + CairoKey::n_fields(vm, *ptr)?)?;
+        ret.try_into()
+            .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))
+    }
+
+    fn derive_id(selector: Felt252) -> SyscallResult<Self::Id> {
+        Self::Id::from_repr(selector.try_into().map_err(|e| SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: format!("{}", e),
+        })?)
+        .ok_or(SyscallExecutionError::InvalidSyscallInput {
+            input: selector,
+            info: "Invalid function identifier".to_string(),
+        })
+    }
+
+    async fn handle(&mut self, key: Self::Key, function_id: Self::Id, vm: &VirtualMachine) -> SyscallResult<Self::CallHandlerResult> {
+        match function_id {
+            FunctionId::Logs => {
+                let mut positions = Vec::new();
+                let mut transactions_counts = Vec::new();
+                for header_key in key.header_keys() {
+                    let bloom_chunks = HeaderCallHandler::new(Memorizer::new(self.memorizer.dict_ptr), self.dict_manager.clone())
+                        .handle(header_key.clone().into(), header::FunctionId::Bloom, vm)
+                        .await?;
+                    if !bloom_may_contain(&bloom_from_chunks(&bloom_chunks), key.address, key.topic0) {
+                        transactions_counts.push(0);
+                        continue;
+                    }
+
+                    // The dry run memorizes every receipt of the block, so the scan stops at the first missing index.
+                    // Cairo proves the count against the gas used of the header.
+                    let mut transactions_count = 0;
+                    for transaction_index in 0.. {
+                        let receipt_key = receipt::CairoKey::from(receipt::Key {
+                            chain_id: header_key.chain_id,
+                            block_number: header_key.block_number,
+                            transaction_index,
+                        });
+                        let Ok(ptr) = self
+                            .memorizer
+                            .read_key_ptr(&MaybeRelocatable::Int(receipt_key.hash()), self.dict_manager.clone())
+                        else {
+                            break;
+                        };
+                        let receipt = decode_receipt(vm, ptr)?;
+                        positions.extend(receipt.matching_logs(key.address, key.topic0).map(|log_index| LogPosition {
+                            block_number: header_key.block_number,
+                            transaction_index,
+                            log_index,
+                        }));
+                        transactions_count += 1;
+                    }
+                    transactions_counts.push(transactions_count);
+                }
+                Ok(LogFilterResult {
+                    positions,
+                    transactions_counts,
+                }
+                .to_felts())
+            }
+        }
+    }
+}
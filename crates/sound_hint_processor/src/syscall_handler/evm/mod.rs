@@ -1,6 +1,9 @@
 pub mod account;
 pub mod header;
+pub mod header_range;
 pub mod log;
+pub mod log_absence;
+pub mod log_filter;
 pub mod receipt;
 pub mod storage;
 pub mod transaction;
@@ -30,6 +33,9 @@ pub enum CallHandlerId {
     Transaction = 3,
     Receipt = 4,
     Logs = 5,
+    HeaderRange = 6,
+    LogsByFilter = 7,
+    LogAbsence = 8,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -111,6 +117,30 @@ impl traits::SyscallHandler for CallContractHandler {
                     .await?;
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
+            CallHandlerId::HeaderRange => {
+                let key = header_range::HeaderRangeCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = header_range::HeaderRangeCallHandler::derive_id(request.selector)?;
+                let result = header_range::HeaderRangeCallHandler::new(memorizer, self.dict_manager.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
+            CallHandlerId::LogsByFilter => {
+                let key = log_filter::LogsByFilterCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = log_filter::LogsByFilterCallHandler::derive_id(request.selector)?;
+                let result = log_filter::LogsByFilterCallHandler::new(memorizer, self.dict_manager.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
+            CallHandlerId::LogAbsence => {
+                let key = log_absence::LogAbsenceCallHandler::derive_key(vm, &mut calldata)?;
+                let function_id = log_absence::LogAbsenceCallHandler::derive_id(request.selector)?;
+                let result = log_absence::LogAbsenceCallHandler::new(memorizer, self.dict_manager.clone())
+                    .handle(key.clone(), function_id, vm)
+                    .await?;
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
         }
 
         Ok(Self::Response {
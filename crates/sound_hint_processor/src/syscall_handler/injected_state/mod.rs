@@ -11,7 +11,7 @@ use strum_macros::FromRepr;
 use syscall_handler::{memorizer::Memorizer, traits::SyscallHandler, SyscallExecutionError, SyscallResult, WriteResponseResult};
 use types::{
     cairo::{
-        injected_state::{label, read, write, INCLUSION, LABEL_RUNTIME, NON_INCLUSION, WRITE},
+        injected_state::{label, read, write, DELETE, INCLUSION, LABEL_RUNTIME, NON_INCLUSION, WRITE},
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
         FELT_0, FELT_1,
@@ -24,6 +24,7 @@ pub enum CallHandlerId {
     Label = 0,
     Read = 1,
     Write = 2,
+    Delete = 3,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -146,6 +147,28 @@ impl SyscallHandler for CallContractHandler {
 
                 let result = write::Response { trie_root: new_root };
 
+                retdata_end = result.to_memory(vm, retdata_end)?;
+            }
+            CallHandlerId::Delete => {
+                let key = keys::injected_state::delete::CairoKey::from_memory(vm, calldata)?;
+
+                let trie_root = self
+                    .get_trie_root(&memorizer, key.trie_label)?
+                    .ok_or(HintError::NoValueForKey(Box::new(key.trie_label.into())))?;
+
+                let new_root = *vm.get_integer(memorizer.read_key_ptr(
+                    &MaybeRelocatable::Int(poseidon_hash_many([&DELETE, &key.trie_label, &trie_root, &key.key])),
+                    self.dict_manager.clone(),
+                )?)?;
+
+                memorizer.set_key(
+                    &MaybeRelocatable::Int(poseidon_hash_many([&LABEL_RUNTIME, &key.trie_label])),
+                    &MaybeRelocatable::Int(new_root),
+                    self.dict_manager.clone(),
+                )?;
+
+                let result = write::Response { trie_root: new_root };
+
                 retdata_end = result.to_memory(vm, retdata_end)?;
             }
         }
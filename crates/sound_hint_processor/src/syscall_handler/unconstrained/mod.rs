@@ -8,12 +8,15 @@ use cairo_vm::{
 };
 use serde::{Deserialize, Serialize};
 use strum_macros::FromRepr;
-use syscall_handler::{memorizer::Memorizer, traits, SyscallExecutionError, SyscallResult, WriteResponseResult};
+use syscall_handler::{felt_from_ptr, memorizer::Memorizer, traits, SyscallExecutionError, SyscallResult, WriteResponseResult};
 use types::{
     cairo::{
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
-        unconstrained::bytecode::BytecodeLeWords,
+        unconstrained::{
+            bytecode::BytecodeLeWords,
+            oracle::{OracleKey, OracleValue},
+        },
     },
     keys,
 };
@@ -21,6 +24,7 @@ use types::{
 #[derive(FromRepr)]
 pub enum CallHandlerId {
     Bytecode = 0,
+    Oracle = 1,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -62,6 +66,24 @@ impl traits::SyscallHandler for CallContractHandler {
 
                 retdata_end = BytecodeLeWords::from_memory(vm, ptr.get_relocatable().unwrap())?.to_memory(vm, retdata_end)?;
             }
+            CallHandlerId::Oracle => {
+                let oracle_id = felt_from_ptr(vm, &mut calldata)?;
+                let input_len: usize = felt_from_ptr(vm, &mut calldata)?
+                    .try_into()
+                    .map_err(|e| SyscallExecutionError::InternalError(format!("{}", e).into()))?;
+                let input: Vec<Felt252> = vm.get_integer_range(calldata, input_len)?.into_iter().map(|e| *e).collect();
+
+                let key = OracleKey::new(oracle_id, input);
+                let ptr = vm
+                    .get_maybe(&memorizer.read_key_ptr(&MaybeRelocatable::Int(key.hash()), self.dict_manager.clone())?)
+                    .ok_or(SyscallExecutionError::InternalError("No key for pointer".into()))?;
+
+                let ptr = ptr.get_relocatable().ok_or(SyscallExecutionError::InternalError(
+                    "Oracle value pointer is not relocatable".into(),
+                ))?;
+                let value = OracleValue::from_memory(vm, ptr)?;
+                retdata_end = vm.load_data(retdata_end, &value.0.iter().map(MaybeRelocatable::from).collect::<Vec<_>>())?;
+            }
         }
 
         Ok(Self::Response {
@@ -3,7 +3,10 @@
 #![warn(unused_crate_dependencies)]
 #![forbid(unsafe_code)]
 
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use cairo_vm::{
     cairo_run::{cairo_run_program, CairoRunConfig},
@@ -17,7 +20,14 @@ use sound_hint_processor::CustomHintProcessor;
 use tokio as _;
 use tracing::info;
 use tracing_subscriber as _;
-use types::{error::Error, param::Param, CasmContractClass, HDPInput, HDPOutput, InjectedState, ProofsData};
+use types::{
+    budget::ResourceBudget,
+    error::Error,
+    param::Param,
+    report::{OutputFormat, RunArtifacts, RunReport},
+    results::HDPResults,
+    CasmContractClass, HDPInput, HDPOutput, InjectedState, ProofsData,
+};
 
 use crate::prove::prover_input_from_runner;
 pub mod prove;
@@ -48,9 +58,21 @@ pub struct Args {
     #[arg(
         long = "print_output",
         default_value_t = false,
-        help = "Print program output to stdout [default: false]"
+        help = "Print the run report to stdout [default: false]"
     )]
     pub print_output: bool,
+    #[arg(
+        long = "output-format",
+        default_value = "pretty",
+        help = "Format of the run report printed with --print_output: `json` on a single line, or `pretty`"
+    )]
+    pub output_format: OutputFormat,
+    #[arg(
+        long = "results",
+        default_value = "results.json",
+        help = "Path where the module results and their inclusion proofs against the output tree root will be written"
+    )]
+    pub results: PathBuf,
     #[arg(long = "proof_mode", conflicts_with = "cairo_pie", help = "Configure runner in proof mode")]
     pub proof_mode: bool,
 
@@ -70,16 +92,26 @@ pub struct Args {
         help = "Path where the STWO prover input file will be written"
     )]
     pub stwo_prover_input: Option<PathBuf>,
+    #[command(flatten)]
+    pub budget: ResourceBudget,
 }
 
-pub fn run(program_path: PathBuf, cairo_run_config: CairoRunConfig, input: HDPInput) -> Result<(CairoRunner, HDPOutput), Error> {
+pub fn run(
+    program_path: PathBuf,
+    cairo_run_config: CairoRunConfig,
+    input: HDPInput,
+    budget: ResourceBudget,
+) -> Result<(CairoRunner, HDPOutput, HDPResults), Error> {
     info!("Program path: {}", program_path.display());
     let program_file = std::fs::read(program_path).map_err(Error::IO)?;
     let program = Program::from_bytes(&program_file, Some(cairo_run_config.entrypoint))?;
 
-    let mut hint_processor = CustomHintProcessor::new(input);
-    let mut cairo_runner = cairo_run_program(&program, &cairo_run_config, &mut hint_processor).map_err(Box::new)?;
-    info!("{:?}", cairo_runner.get_execution_resources());
+    let mut hint_processor = CustomHintProcessor::new(input).with_budget(budget);
+    let mut cairo_runner = cairo_run_program(&program, &cairo_run_config, &mut hint_processor)
+        .map_err(|e| hint_processor.budget().exceeded().unwrap_or_else(|| Box::new(e).into()))?;
+    let resources = cairo_runner.get_execution_resources()?;
+    info!("{:?}", resources);
+    hint_processor.budget().check_builtins(&resources)?;
 
     let segment_index = cairo_runner.vm.get_output_builtin_mut()?.base();
     let segment_size = cairo_runner.vm.segments.compute_effective_sizes()[segment_index];
@@ -90,8 +122,57 @@ pub fn run(program_path: PathBuf, cairo_run_config: CairoRunConfig, input: HDPIn
         .map(|v| v.clone().unwrap().get_int().unwrap());
 
     let output = HDPOutput::from_iter(iter);
+    let results = HDPResults::new(&output, hint_processor.module_results().to_vec())?;
+
+    Ok((cairo_runner, output, results))
+}
 
-    Ok((cairo_runner, output))
+pub fn cairo_run_config(proof_mode: bool) -> CairoRunConfig<'static> {
+    CairoRunConfig {
+        layout: LayoutName::all_cairo_stwo,
+        secure_run: Some(true),
+        allow_missing_builtins: Some(false),
+        relocate_mem: true,
+        trace_enabled: true,
+        proof_mode,
+        ..Default::default()
+    }
+}
+
+/// Logs the step count and writes the requested Cairo PIE and STWO prover input files.
+pub fn write_artifacts(
+    cairo_runner: &CairoRunner,
+    proof_mode: bool,
+    cairo_pie: Option<&Path>,
+    stwo_prover_input: Option<&Path>,
+) -> Result<(), Error> {
+    if let Some(ref relocated_trace) = cairo_runner.relocated_trace {
+        info!(
+            "Step count ({}): {:?}",
+            if proof_mode { "stwo" } else { "pie" },
+            relocated_trace.len()
+        );
+    }
+
+    if let Some(file_name) = cairo_pie {
+        let pie = cairo_runner.get_cairo_pie().map_err(|e| Error::CairoPie(e.to_string()))?;
+        pie.write_zip_file(file_name, true)?;
+    }
+
+    if let Some(file_name) = stwo_prover_input {
+        let stwo_prover_input = prover_input_from_runner(cairo_runner);
+        std::fs::write(file_name, serde_json::to_string(&stwo_prover_input)?)?;
+        info!("Prover Input saved to: {:?}", file_name);
+    }
+
+    Ok(())
+}
+
+/// Writes the module results, their results tree leaves and inclusion proofs.
+pub fn write_results(results: &HDPResults, path: &Path) -> Result<(), Error> {
+    std::fs::write(path, serde_json::to_string_pretty(results)?)?;
+    info!("Results saved to: {:?}", path);
+    Ok(())
 }
 
 pub fn get_program_path() -> String {
@@ -116,19 +197,9 @@ pub async fn run_with_args(args: Args) -> Result<(), Error> {
     };
     let proofs_data: ProofsData = serde_json::from_slice(&std::fs::read(args.proofs).map_err(Error::IO)?)?;
 
-    let cairo_run_config = CairoRunConfig {
-        layout: LayoutName::all_cairo_stwo,
-        secure_run: Some(true),
-        allow_missing_builtins: Some(false),
-        relocate_mem: true,
-        trace_enabled: true,
-        proof_mode: args.proof_mode,
-        ..Default::default()
-    };
-
-    let (cairo_runner, output) = run(
+    let (cairo_runner, output, results) = run(
         args.program.unwrap_or(PathBuf::from(HDP_COMPILED_JSON)),
-        cairo_run_config,
+        cairo_run_config(args.proof_mode),
         HDPInput {
             chain_proofs: proofs_data.chain_proofs,
             compiled_class,
@@ -137,29 +208,25 @@ pub async fn run_with_args(args: Args) -> Result<(), Error> {
             injected_state,
             unconstrained: proofs_data.unconstrained,
         },
+        args.budget,
     )?;
 
-    if args.print_output {
-        println!("{:#?}", output);
-    }
-
-    if let Some(ref relocated_trace) = cairo_runner.relocated_trace {
-        info!(
-            "Step count ({}): {:?}",
-            if args.proof_mode { "stwo" } else { "pie" },
-            relocated_trace.len()
-        );
-    }
-
-    if let Some(ref file_name) = args.cairo_pie {
-        let pie = cairo_runner.get_cairo_pie().map_err(|e| Error::CairoPie(e.to_string()))?;
-        pie.write_zip_file(file_name, true)?;
-    }
+    write_results(&results, &args.results)?;
+    write_artifacts(
+        &cairo_runner,
+        args.proof_mode,
+        args.cairo_pie.as_deref(),
+        args.stwo_prover_input.as_deref(),
+    )?;
 
-    if let Some(ref file_name) = args.stwo_prover_input {
-        let stwo_prover_input = prover_input_from_runner(&cairo_runner);
-        std::fs::write(file_name, serde_json::to_string(&stwo_prover_input)?)?;
-        info!("Prover Input saved to: {:?}", file_name);
+    if args.print_output {
+        let artifacts = RunArtifacts {
+            results: Some(args.results),
+            cairo_pie: args.cairo_pie,
+            stwo_prover_input: args.stwo_prover_input,
+            ..Default::default()
+        };
+        RunReport::new(output, &cairo_runner, artifacts)?.print(args.output_format)?;
     }
 
     info!("Sound run completed successfully.");
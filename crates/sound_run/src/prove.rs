@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
 use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+#[cfg(feature = "stwo")]
+pub use stwo::{proof_output, proof_program_hash, prove, verify, ExpectedOutput, StwoProof};
 use stwo_cairo_adapter::{
     builtins::MemorySegmentAddresses,
     memory::{MemoryBuilder, MemoryConfig, MemoryEntry},
@@ -58,3 +60,195 @@ pub fn prover_input_from_runner(runner: &CairoRunner) -> ProverInput {
     debug!("Builtins: {:#?}", input.builtins_segments.get_counts());
     input
 }
+
+/// Local CPU proving and verification of a sound run with the stwo-cairo prover.
+#[cfg(feature = "stwo")]
+mod stwo {
+    use cairo_vm::{
+        program_hash::compute_program_hash_chain,
+        types::{program::Program, relocatable::MaybeRelocatable},
+        vm::runners::cairo_pie::StrippedProgram,
+        Felt252,
+    };
+    use stwo_cairo_adapter::ProverInput;
+    use stwo_cairo_air::{verifier::verify_cairo, CairoProof, PreProcessedTraceVariant};
+    use stwo_cairo_prover::{
+        prover::prove_cairo,
+        stwo_prover::core::{
+            pcs::PcsConfig,
+            vcs::blake2_merkle::{Blake2sMerkleChannel, Blake2sMerkleHasher},
+        },
+    };
+    use tracing::info;
+    use types::{error::Error, results::HDPResults, HDPOutput};
+
+    /// Proof artifact written by `hdp prove`.
+    pub type StwoProof = CairoProof<Blake2sMerkleHasher>;
+
+    /// What the sound run says the proof must output.
+    pub enum ExpectedOutput {
+        /// The output of the sound run report.
+        Output(HDPOutput),
+        /// The results file of the sound run, pinning the task hash and the results root.
+        Results(HDPResults),
+    }
+
+    impl ExpectedOutput {
+        fn check(&self, output: &HDPOutput) -> Result<(), Error> {
+            let matches = match self {
+                Self::Output(expected) => expected == output,
+                // Rebuilding the results against the proven output checks the results root, comparing checks the task hash
+                Self::Results(results) => &HDPResults::new(output, results.results.clone())? == results,
+            };
+            if !matches {
+                return Err(Error::ProofOutputMismatch);
+            }
+            Ok(())
+        }
+    }
+
+    /// Proves the prover input of a proof mode sound run.
+    pub fn prove(input: ProverInput) -> Result<StwoProof, Error> {
+        info!("Proving the run...");
+        let proof = prove_cairo::<Blake2sMerkleChannel>(input, PcsConfig::default(), PreProcessedTraceVariant::Canonical)
+            .map_err(|e| Error::Prove(e.to_string()))?;
+        info!("Proof generated successfully.");
+
+        Ok(proof)
+    }
+
+    /// Verifies the proof, checks that it ran `program` and that its public output is the one the sound run expects,
+    /// returning that output.
+    pub fn verify(proof: StwoProof, program: &Program, expected: &ExpectedOutput) -> Result<HDPOutput, Error> {
+        let stripped = program.get_stripped_program()?;
+        let expected_hash = compute_program_hash_chain(&stripped, 0).map_err(|e| Error::ProofVerification(e.to_string()))?;
+        let proven_hash = proof_program_hash(&proof, &stripped)?;
+        if proven_hash != expected_hash {
+            return Err(Error::ProgramHashMismatch {
+                expected: expected_hash,
+                proven: proven_hash,
+            });
+        }
+
+        let output = proof_output(&proof)?;
+        expected.check(&output)?;
+
+        info!("Verifying the proof...");
+        verify_cairo::<Blake2sMerkleChannel>(proof, PreProcessedTraceVariant::Canonical)
+            .map_err(|e| Error::ProofVerification(e.to_string()))?;
+        info!("Proof verified successfully.");
+
+        Ok(output)
+    }
+
+    /// Decodes the output segment of the proven public memory, stored as little endian u32 limbs.
+    pub fn proof_output(proof: &StwoProof) -> Result<HDPOutput, Error> {
+        output_from_limbs(proof.claim.public_data.public_memory.output.iter().map(|(_, value)| *value))
+    }
+
+    fn output_from_limbs(values: impl IntoIterator<Item = [u32; 8]>) -> Result<HDPOutput, Error> {
+        HDPOutput::try_from_felts(values.into_iter().map(felt_from_limbs))
+    }
+
+    /// Reads a public memory value, eight u32 limbs with the least significant first.
+    fn felt_from_limbs(value: [u32; 8]) -> Felt252 {
+        Felt252::from_bytes_le(&bytemuck::cast(value))
+    }
+
+    /// Hashes the program segment of the proven public memory like `program-hash` hashes a compiled program, taking
+    /// the builtins and entrypoint of `program` so that the hashes only differ when the bytecode does.
+    pub fn proof_program_hash(proof: &StwoProof, program: &StrippedProgram) -> Result<Felt252, Error> {
+        let proven = StrippedProgram {
+            data: proof
+                .claim
+                .public_data
+                .public_memory
+                .program
+                .iter()
+                .map(|(_, value)| MaybeRelocatable::from(felt_from_limbs(*value)))
+                .collect(),
+            builtins: program.builtins.clone(),
+            main: program.main,
+            prime: (),
+        };
+        compute_program_hash_chain(&proven, 0).map_err(|e| Error::ProofVerification(e.to_string()))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use types::{results::EMPTY_RESULTS_ROOT, MmrMetaOutput};
+
+        use super::*;
+
+        fn limbs(felt: Felt252) -> [u32; 8] {
+            bytemuck::cast(felt.to_bytes_le())
+        }
+
+        /// An output committing to no results.
+        fn output() -> HDPOutput {
+            HDPOutput {
+                task_hash_low: Felt252::from(1_u64),
+                task_hash_high: Felt252::from(2_u64),
+                output_tree_root_low: Felt252::from_bytes_be_slice(&EMPTY_RESULTS_ROOT[16..]),
+                output_tree_root_high: Felt252::from_bytes_be_slice(&EMPTY_RESULTS_ROOT[..16]),
+                mmr_metas: vec![MmrMetaOutput::Keccak {
+                    id: Felt252::from(3_u64),
+                    size: Felt252::from(4_u64),
+                    chain_id: Felt252::from(11155111_u64),
+                    root_low: Felt252::from(5_u64),
+                    root_high: Felt252::from(6_u64),
+                }],
+            }
+        }
+
+        #[test]
+        fn limbs_are_little_endian() {
+            assert_eq!(felt_from_limbs([1, 0, 0, 0, 0, 0, 0, 0]), Felt252::from(1_u64));
+            assert_eq!(felt_from_limbs([0, 1, 0, 0, 0, 0, 0, 0]), Felt252::from(1_u64 << 32));
+            assert_eq!(felt_from_limbs([0x04030201, 0, 0, 0, 0, 0, 0, 0]), Felt252::from(0x04030201_u64));
+            assert_eq!(
+                felt_from_limbs([0, 0, 0, 0, 0, 0, 0, 0x100]),
+                Felt252::from_hex_unchecked("0x10000000000000000000000000000000000000000000000000000000000"),
+                "the last limb holds the most significant bits"
+            );
+
+            let felt = Felt252::from_hex_unchecked("0x7a1e28143646422982f817d6612f7b477d66591ff96a9e064bcc98a0102");
+            assert_eq!(felt_from_limbs(limbs(felt)), felt);
+        }
+
+        #[test]
+        fn output_is_decoded_from_limbs() {
+            let values: Vec<[u32; 8]> = output().to_felt_vec().into_iter().map(limbs).collect();
+            assert_eq!(output_from_limbs(values.clone()).unwrap(), output());
+
+            assert!(matches!(
+                output_from_limbs(values[..values.len() - 1].to_vec()),
+                Err(Error::MalformedOutput(_))
+            ));
+        }
+
+        #[test]
+        fn mismatching_output_is_rejected() {
+            let expected = ExpectedOutput::Output(output());
+            expected.check(&output()).unwrap();
+
+            let mut proven = output();
+            proven.mmr_metas.clear();
+            assert!(matches!(expected.check(&proven), Err(Error::ProofOutputMismatch)));
+        }
+
+        #[test]
+        fn mismatching_results_are_rejected() {
+            let expected = ExpectedOutput::Results(HDPResults::new(&output(), vec![]).unwrap());
+            expected.check(&output()).unwrap();
+
+            let mut proven = output();
+            proven.output_tree_root_low = proven.output_tree_root_low + Felt252::from(1_u64);
+            assert!(matches!(expected.check(&proven), Err(Error::ResultsRootMismatch { .. })));
+
+            let mut proven = output();
+            proven.task_hash_low = Felt252::from(7_u64);
+            assert!(matches!(expected.check(&proven), Err(Error::ProofOutputMismatch)));
+        }
+    }
+}
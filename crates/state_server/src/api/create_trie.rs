@@ -1,9 +1,10 @@
-use axum::{extract::State, Json};
+use axum::{extract::State, Extension, Json};
 use pathfinder_crypto::Felt;
 use serde::{Deserialize, Serialize};
 use types::proofs::injected_state::leaf::TrieLeaf;
 
 use crate::{
+    auth::{Caller, Scope},
     mpt::{error::Error as MptError, trie::Trie},
     AppState,
 };
@@ -13,6 +14,9 @@ pub struct CreateTrieRequest {
     pub trie_label: Felt,
     pub keys: Vec<Felt>,
     pub values: Vec<Felt>,
+    /// Optional tag recorded with the resulting root.
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,27 +26,34 @@ pub struct CreateTrieResponse {
 
 pub async fn create_trie(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Json(payload): Json<CreateTrieRequest>,
 ) -> Result<Json<CreateTrieResponse>, MptError> {
-    let conn = state.get_connection(payload.trie_label)?;
-
-    let (storage, mut trie, root_idx) = Trie::create_empty(&conn)?;
-
-    let leaves = payload
-        .keys
-        .into_iter()
-        .zip(payload.values.into_iter())
-        .map(|(k, v)| TrieLeaf::new(k, v))
-        .collect::<Vec<_>>();
-
-    for leaf in &leaves {
-        trie.set(&storage, leaf.get_path(), leaf.data.value)?;
-    }
-
-    let update = trie.commit(&storage)?;
-    Trie::persist_updates(&storage, &update, &leaves, Some(u64::from(root_idx)))?;
-
-    Ok(Json(CreateTrieResponse {
-        trie_root: update.root_commitment,
-    }))
+    caller.authorize(payload.trie_label, Scope::Write)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, mut trie, root_idx) = Trie::create_empty(store)?;
+
+            let leaves = payload
+                .keys
+                .into_iter()
+                .zip(payload.values.into_iter())
+                .map(|(k, v)| TrieLeaf::new(k, v))
+                .collect::<Vec<_>>();
+
+            for leaf in &leaves {
+                trie.set(&storage, leaf.get_path(), leaf.data.value)?;
+            }
+
+            let update = trie.commit(&storage)?;
+            let write = Trie::prepare_updates(&storage, &update, &leaves, Some(u64::from(root_idx)))?
+                .with_root(Some(Felt::ZERO), payload.tag)
+                .with_head(Felt::ZERO);
+            storage.write(&write)?;
+
+            Ok(Json(CreateTrieResponse {
+                trie_root: update.root_commitment,
+            }))
+        })
+        .await
 }
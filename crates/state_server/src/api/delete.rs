@@ -0,0 +1,65 @@
+use axum::{extract::State, Extension, Json};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+use types::proofs::injected_state::leaf::TrieLeaf;
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{error::Error as MptError, trie::Trie},
+    AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteRequest {
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+    pub key: Felt,
+    /// Optional tag recorded with the resulting root.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeleteResponse {
+    pub trie_id: u64,
+    pub trie_root: Felt,
+    pub key: Felt,
+}
+
+/// Removes `key` from the trie, failing with `404 Not Found` if it is not set under `trie_root`.
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Json(payload): Json<DeleteRequest>,
+) -> Result<Json<DeleteResponse>, MptError> {
+    caller.authorize(payload.trie_label, Scope::Write)?;
+    if payload.trie_root == Felt::ZERO {
+        return Err(MptError::LeafNotFound);
+    }
+
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, mut trie, root_idx) = Trie::load_from_root(payload.trie_root, store)?;
+
+            if storage.get_leaf_at(payload.key, u64::from(root_idx))?.is_none() {
+                return Err(MptError::LeafNotFound);
+            }
+
+            // A zero leaf removes the key from the trie and is kept in the leafs table as a tombstone
+            let leaf = TrieLeaf::empty(payload.key);
+            trie.set(&storage, leaf.get_path(), leaf.data.value)?;
+
+            let update = trie.commit(&storage)?;
+            let write = Trie::prepare_updates(&storage, &update, &[leaf], Some(u64::from(root_idx)))?
+                .with_root(Some(payload.trie_root), payload.tag)
+                .with_head(payload.trie_root);
+            storage.write(&write)?;
+
+            Ok(Json(DeleteResponse {
+                trie_id: write.root_idx,
+                trie_root: update.root_commitment,
+                key: leaf.key,
+            }))
+        })
+        .await
+}
@@ -0,0 +1,80 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+    Extension,
+};
+use futures::{stream, StreamExt};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{
+        error::Error as MptError,
+        snapshot::{self, SnapshotHeader, SnapshotLeaf, SNAPSHOT_CHUNK_SIZE},
+    },
+    AppState,
+};
+
+pub const SNAPSHOT_CONTENT_TYPE: &str = "application/x-ndjson";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+}
+
+/// Streams every leaf set under `trie_root` as a JSON lines snapshot, walking the trie one page at a time.
+///
+/// The first page is walked before responding, so an unknown root fails with `404 Not Found`. A later failure
+/// aborts the response, leaving a truncated snapshot whose import fails.
+pub async fn export(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<ExportRequest>,
+) -> Result<impl IntoResponse, MptError> {
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    let (trie_label, trie_root) = (payload.trie_label, payload.trie_root);
+
+    let first_page = state
+        .with_store(trie_label, move |store| {
+            snapshot::export_page(store, trie_root, None, SNAPSHOT_CHUNK_SIZE)
+        })
+        .await?;
+
+    let mut header_line = vec![];
+    snapshot::write_line(&mut header_line, &SnapshotHeader::new(trie_label, trie_root))?;
+
+    let pages = stream::try_unfold(Some(first_page), move |page| {
+        let state = state.clone();
+        async move {
+            let Some((leaves, next_cursor)) = page else {
+                return Ok(None);
+            };
+            let next_page = match next_cursor {
+                Some(cursor) => Some(
+                    state
+                        .with_store(trie_label, move |store| {
+                            snapshot::export_page(store, trie_root, Some(cursor), SNAPSHOT_CHUNK_SIZE)
+                        })
+                        .await?,
+                ),
+                None => None,
+            };
+            Ok::<_, MptError>(Some((page_lines(&leaves)?, next_page)))
+        }
+    });
+    let body = Body::from_stream(stream::once(async { Ok(header_line) }).chain(pages));
+
+    Ok(([(header::CONTENT_TYPE, SNAPSHOT_CONTENT_TYPE)], body))
+}
+
+fn page_lines(leaves: &[SnapshotLeaf]) -> Result<Vec<u8>, MptError> {
+    let mut lines = vec![];
+    for leaf in leaves {
+        snapshot::write_line(&mut lines, leaf)?;
+    }
+    Ok(lines)
+}
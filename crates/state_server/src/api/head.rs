@@ -0,0 +1,70 @@
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{db::trie::TrieDB, error::Error as MptError},
+    AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetHeadRequest {
+    pub trie_label: Felt,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapHeadRequest {
+    pub trie_label: Felt,
+    /// The head the caller last saw, `None` if the trie is not tracking one yet.
+    pub expected: Option<Felt>,
+    /// The new head, `None` to stop tracking.
+    pub head: Option<Felt>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeadResponse {
+    pub trie_label: Felt,
+    /// The tracked head root, `None` if writes to the trie are not checked against a head.
+    pub head: Option<Felt>,
+}
+
+/// Returns the tracked head root of a trie.
+pub async fn get_head(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<GetHeadRequest>,
+) -> Result<Json<HeadResponse>, MptError> {
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            Ok(Json(HeadResponse {
+                trie_label: payload.trie_label,
+                head: TrieDB::new(store).get_head()?,
+            }))
+        })
+        .await
+}
+
+/// Moves the tracked head of a trie if it is still `expected`, failing with `409 Conflict` and the current head
+/// otherwise. Once a trie tracks a head, writes must build on it and advance it.
+pub async fn swap_head(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Json(payload): Json<SwapHeadRequest>,
+) -> Result<Json<HeadResponse>, MptError> {
+    caller.authorize(payload.trie_label, Scope::Write)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            TrieDB::new(store).swap_head(payload.expected, payload.head)?;
+
+            Ok(Json(HeadResponse {
+                trie_label: payload.trie_label,
+                head: payload.head,
+            }))
+        })
+        .await
+}
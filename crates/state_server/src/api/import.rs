@@ -0,0 +1,122 @@
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    Extension, Json,
+};
+use http_body_util::BodyExt;
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{
+        error::Error as MptError,
+        snapshot::{self, SnapshotHeader, SNAPSHOT_CHUNK_SIZE},
+    },
+    AppState,
+};
+
+/// Largest snapshot body accepted by `/import`. Snapshots are imported a chunk at a time, so the limit does not bound
+/// memory use, only the size of a single import.
+pub const MAX_SNAPSHOT_BYTES: usize = 1 << 30;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportRequest {
+    /// Imports under this label instead of the one recorded in the snapshot.
+    pub trie_label: Option<Felt>,
+    /// Optional tag recorded with the resulting root.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportResponse {
+    pub trie_id: u64,
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+    pub leaves: u64,
+}
+
+/// Rebuilds a trie from a JSON lines snapshot streamed in the request body, failing with `400 Bad Request` if the
+/// snapshot is malformed or its leaves do not hash to the recorded root.
+pub async fn import(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<ImportRequest>,
+    body: Body,
+) -> Result<Json<ImportResponse>, MptError> {
+    let mut lines = BodyLines::new(body);
+    let header = SnapshotHeader::read(&mut std::iter::once(lines.next().await).flatten())?;
+    let trie_label = payload.trie_label.unwrap_or(header.trie_label);
+
+    caller.authorize(trie_label, Scope::Write)?;
+
+    // The body is read here and handed over line by line to the import running on the blocking thread pool
+    let (sender, mut receiver) = mpsc::channel(SNAPSHOT_CHUNK_SIZE);
+    tokio::spawn(async move {
+        while let Some(line) = lines.next().await {
+            if sender.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    state
+        .with_store(trie_label, move |store| {
+            let imported = snapshot::import(store, &header, std::iter::from_fn(|| receiver.blocking_recv()), payload.tag)?;
+
+            Ok(Json(ImportResponse {
+                trie_id: u64::from(imported.trie_id),
+                trie_label,
+                trie_root: header.trie_root,
+                leaves: imported.leaves,
+            }))
+        })
+        .await
+}
+
+/// Splits a request body into its non-empty lines as its frames arrive.
+struct BodyLines {
+    body: Body,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl BodyLines {
+    fn new(body: Body) -> Self {
+        Self {
+            body,
+            buffer: vec![],
+            done: false,
+        }
+    }
+
+    async fn next(&mut self) -> Option<Result<String, MptError>> {
+        loop {
+            let line = match self.buffer.iter().position(|byte| *byte == b'\n') {
+                Some(end) => self.buffer.drain(..=end).collect::<Vec<_>>(),
+                None if self.done && !self.buffer.is_empty() => std::mem::take(&mut self.buffer),
+                None if self.done => return None,
+                None => {
+                    match self.body.frame().await {
+                        Some(Ok(frame)) => {
+                            if let Ok(data) = frame.into_data() {
+                                self.buffer.extend_from_slice(&data);
+                            }
+                        }
+                        Some(Err(e)) => return Some(Err(MptError::InvalidRequest(format!("invalid snapshot body: {}", e)))),
+                        None => self.done = true,
+                    }
+                    continue;
+                }
+            };
+
+            match String::from_utf8(line) {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(Ok(line.trim_end().to_string())),
+                Err(e) => return Some(Err(MptError::InvalidRequest(format!("invalid snapshot line: {}", e)))),
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::ops::Bound;
+
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{error::Error as MptError, trie::Trie},
+    AppState,
+};
+
+pub const DEFAULT_LEAVES_LIMIT: usize = 100;
+pub const MAX_LEAVES_LIMIT: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetLeavesRequest {
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+    /// Resumes after this key, as returned in `next_cursor`.
+    pub cursor: Option<Felt>,
+    pub limit: Option<usize>,
+    /// Lowest key to return, inclusive.
+    pub start_key: Option<Felt>,
+    /// Highest key to return, exclusive.
+    pub end_key: Option<Felt>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeafEntry {
+    pub key: Felt,
+    pub value: Felt,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetLeavesResponse {
+    pub leaves: Vec<LeafEntry>,
+    /// Key to pass as `cursor` to fetch the next page, `None` on the last page.
+    pub next_cursor: Option<Felt>,
+}
+
+/// Lists the leaves set under `trie_root` in ascending key order by walking the trie nodes.
+pub async fn get_leaves(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<GetLeavesRequest>,
+) -> Result<Json<GetLeavesResponse>, MptError> {
+    let limit = payload.limit.unwrap_or(DEFAULT_LEAVES_LIMIT);
+    if limit == 0 || limit > MAX_LEAVES_LIMIT {
+        return Err(MptError::InvalidRequest(format!(
+            "limit must be between 1 and {}",
+            MAX_LEAVES_LIMIT
+        )));
+    }
+
+    let start = match (payload.cursor, payload.start_key) {
+        (Some(cursor), _) => Bound::Excluded(cursor),
+        (None, Some(start_key)) => Bound::Included(start_key),
+        (None, None) => Bound::Unbounded,
+    };
+    let end = payload.end_key.map_or(Bound::Unbounded, Bound::Excluded);
+
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, _trie, _root_idx) = if payload.trie_root == Felt::ZERO {
+                Trie::create_empty(store)?
+            } else {
+                Trie::load_from_root(payload.trie_root, store)?
+            };
+
+            // Fetch one extra leaf to tell whether another page follows
+            let mut leaves = Trie::get_leaves(&storage, payload.trie_root, (start, end), limit + 1)?;
+            let next_cursor = if leaves.len() > limit {
+                leaves.truncate(limit);
+                leaves.last().map(|leaf| leaf.key)
+            } else {
+                None
+            };
+
+            Ok(Json(GetLeavesResponse {
+                leaves: leaves
+                    .into_iter()
+                    .map(|leaf| LeafEntry {
+                        key: leaf.key,
+                        value: leaf.data.value,
+                    })
+                    .collect(),
+                next_cursor,
+            }))
+        })
+        .await
+}
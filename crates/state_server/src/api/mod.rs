@@ -1,5 +1,13 @@
 pub mod create_trie;
+pub mod delete;
+pub mod export;
+pub mod head;
+pub mod import;
+pub mod leaves;
 pub mod proof;
+pub mod prune;
 pub mod read;
 pub mod root_to_node_idx;
+pub mod roots;
 pub mod write;
+pub mod write_batch;
@@ -0,0 +1,47 @@
+use axum::{extract::State, Extension, Json};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{
+        error::Error as MptError,
+        prune::{self, PruneStats, RetainPolicy},
+    },
+    AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PruneRequest {
+    pub trie_label: Felt,
+    #[serde(default)]
+    pub keep_roots: Vec<Felt>,
+    #[serde(default)]
+    pub keep_last: Option<usize>,
+}
+
+impl PruneRequest {
+    /// Exactly one of `keep_roots` and `keep_last` selects the roots to retain.
+    pub fn retain_policy(&self) -> Result<RetainPolicy, MptError> {
+        match (self.keep_roots.is_empty(), self.keep_last) {
+            (false, None) => Ok(RetainPolicy::Roots(self.keep_roots.clone())),
+            (true, Some(n)) => Ok(RetainPolicy::Last(n)),
+            _ => Err(MptError::InvalidRequest(
+                "exactly one of keep_roots and keep_last must be provided".to_string(),
+            )),
+        }
+    }
+}
+
+/// Deletes the nodes and leaves of every root of the trie that is not retained.
+pub async fn prune(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Json(payload): Json<PruneRequest>,
+) -> Result<Json<PruneStats>, MptError> {
+    let policy = payload.retain_policy()?;
+    caller.authorize(payload.trie_label, Scope::Admin)?;
+    state
+        .with_store(payload.trie_label, move |store| Ok(Json(prune::prune(store, &policy)?)))
+        .await
+}
@@ -1,11 +1,12 @@
 use axum::{
     extract::{Query, State},
-    Json,
+    Extension, Json,
 };
 use pathfinder_crypto::Felt;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::{Caller, Scope},
     mpt::{error::Error as MptError, trie::Trie},
     AppState,
 };
@@ -23,17 +24,25 @@ pub struct ReadResponse {
     pub value: Option<Felt>,
 }
 
-pub async fn read(State(state): State<AppState>, Query(payload): Query<ReadRequest>) -> Result<Json<ReadResponse>, MptError> {
-    let conn = state.get_connection(payload.trie_label)?;
-    let (storage, _trie, root_idx) = if payload.trie_root == Felt::ZERO {
-        Trie::create_empty(&conn)?
-    } else {
-        Trie::load_from_root(payload.trie_root, &conn)?
-    };
-    let leaf = storage.get_leaf_at(payload.key, u64::from(root_idx))?;
+pub async fn read(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<ReadRequest>,
+) -> Result<Json<ReadResponse>, MptError> {
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, _trie, root_idx) = if payload.trie_root == Felt::ZERO {
+                Trie::create_empty(store)?
+            } else {
+                Trie::load_from_root(payload.trie_root, store)?
+            };
+            let leaf = storage.get_leaf_at(payload.key, u64::from(root_idx))?;
 
-    Ok(Json(ReadResponse {
-        key: payload.key,
-        value: leaf.map(|leaf| leaf.data.value),
-    }))
+            Ok(Json(ReadResponse {
+                key: payload.key,
+                value: leaf.map(|leaf| leaf.data.value),
+            }))
+        })
+        .await
 }
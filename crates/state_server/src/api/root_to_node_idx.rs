@@ -1,11 +1,12 @@
 use axum::{
     extract::{Query, State},
-    Json,
+    Extension, Json,
 };
 use pathfinder_crypto::Felt;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::{Caller, Scope},
     mpt::{db::trie::TrieDB, error::Error as MptError},
     AppState,
 };
@@ -24,23 +25,24 @@ pub struct GetIdResponse {
 
 pub async fn get_trie_root_node_idx(
     State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
     Query(payload): Query<GetIdRequest>,
 ) -> Result<Json<GetIdResponse>, MptError> {
-    let conn = state.get_connection(payload.trie_label)?;
-    if payload.trie_root == Felt::ZERO {
-        return Ok(Json(GetIdResponse {
-            trie_root_node_idx: 0,
-            trie_root: Felt::ZERO,
-        }));
-    }
-    let trie_root_node_idx = match TrieDB::new(&conn).get_node_idx_by_hash(payload.trie_root) {
-        Result::Ok(v) => Ok(v),
-        Result::Err(MptError::Database(rusqlite::Error::QueryReturnedNoRows)) => Err(MptError::MissingNodeIndex),
-        Result::Err(error) => Err(error),
-    }?;
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            if payload.trie_root == Felt::ZERO {
+                return Ok(Json(GetIdResponse {
+                    trie_root_node_idx: 0,
+                    trie_root: Felt::ZERO,
+                }));
+            }
+            let trie_root_node_idx = TrieDB::new(store).get_node_idx_by_hash(payload.trie_root)?;
 
-    Ok(Json(GetIdResponse {
-        trie_root_node_idx,
-        trie_root: payload.trie_root,
-    }))
+            Ok(Json(GetIdResponse {
+                trie_root_node_idx,
+                trie_root: payload.trie_root,
+            }))
+        })
+        .await
 }
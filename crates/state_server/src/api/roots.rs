@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Query, State},
+    Extension, Json,
+};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{
+        db::trie::{RootRecord, TrieDB},
+        error::Error as MptError,
+    },
+    AppState,
+};
+
+pub const DEFAULT_ROOTS_LIMIT: usize = 100;
+pub const MAX_ROOTS_LIMIT: usize = 1000;
+
+fn check_limit(limit: Option<usize>) -> Result<usize, MptError> {
+    let limit = limit.unwrap_or(DEFAULT_ROOTS_LIMIT);
+    if limit == 0 || limit > MAX_ROOTS_LIMIT {
+        return Err(MptError::InvalidRequest(format!("limit must be between 1 and {}", MAX_ROOTS_LIMIT)));
+    }
+    Ok(limit)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRootsRequest {
+    pub trie_label: Felt,
+    /// Resumes after this record id, as returned in `next_cursor`.
+    pub cursor: Option<u64>,
+    pub limit: Option<usize>,
+    /// Only returns roots recorded with this tag.
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRootsResponse {
+    pub roots: Vec<RootRecord>,
+    /// Record id to pass as `cursor` to fetch the next page, `None` on the last page.
+    pub next_cursor: Option<u64>,
+}
+
+/// Lists the roots recorded for a trie in the order they were created.
+pub async fn get_roots(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<GetRootsRequest>,
+) -> Result<Json<GetRootsResponse>, MptError> {
+    let limit = check_limit(payload.limit)?;
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            // Fetch one extra root to tell whether another page follows
+            let mut roots = TrieDB::new(store).get_roots(payload.cursor.unwrap_or(0), limit + 1, payload.tag.as_deref())?;
+            let next_cursor = if roots.len() > limit {
+                roots.truncate(limit);
+                roots.last().map(|root| root.id)
+            } else {
+                None
+            };
+
+            Ok(Json(GetRootsResponse { roots, next_cursor }))
+        })
+        .await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRootLineageRequest {
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetRootLineageResponse {
+    /// The record of `trie_root` followed by the records of its ancestors, newest first.
+    pub lineage: Vec<RootRecord>,
+}
+
+/// Follows the parents of `trie_root` back to the empty trie, an imported root or `limit` records,
+/// failing with `404 Not Found` if `trie_root` was never recorded.
+pub async fn get_root_lineage(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Query(payload): Query<GetRootLineageRequest>,
+) -> Result<Json<GetRootLineageResponse>, MptError> {
+    let limit = check_limit(payload.limit)?;
+    caller.authorize(payload.trie_label, Scope::Read)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let storage = TrieDB::new(store);
+
+            let mut lineage: Vec<RootRecord> = vec![];
+            let mut next = Some(payload.trie_root);
+            while let Some(root) = next.filter(|root| *root != Felt::ZERO) {
+                if lineage.len() == limit || lineage.iter().any(|record| record.trie_root == root) {
+                    break;
+                }
+                let Some(record) = storage.get_root_record(root)? else {
+                    break;
+                };
+                next = record.parent_root;
+                lineage.push(record);
+            }
+
+            if lineage.is_empty() && payload.trie_root != Felt::ZERO {
+                return Err(MptError::MissingNodeIndex);
+            }
+
+            Ok(Json(GetRootLineageResponse { lineage }))
+        })
+        .await
+}
@@ -1,9 +1,10 @@
-use axum::{extract::State, Json};
+use axum::{extract::State, Extension, Json};
 use pathfinder_crypto::Felt;
 use serde::{Deserialize, Serialize};
 use types::proofs::injected_state::leaf::TrieLeaf;
 
 use crate::{
+    auth::{Caller, Scope},
     mpt::{error::Error as MptError, trie::Trie},
     AppState,
 };
@@ -14,6 +15,9 @@ pub struct WriteRequest {
     pub trie_root: Felt,
     pub key: Felt,
     pub value: Felt,
+    /// Optional tag recorded with the resulting root.
+    #[serde(default)]
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,24 +28,35 @@ pub struct WriteResponse {
     pub value: Felt,
 }
 
-pub async fn write(State(state): State<AppState>, Json(payload): Json<WriteRequest>) -> Result<Json<WriteResponse>, MptError> {
-    let conn = state.get_connection(payload.trie_label)?;
-    let (storage, mut trie, root_idx) = if payload.trie_root == Felt::ZERO {
-        Trie::create_empty(&conn)?
-    } else {
-        Trie::load_from_root(payload.trie_root, &conn)?
-    };
+pub async fn write(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Json(payload): Json<WriteRequest>,
+) -> Result<Json<WriteResponse>, MptError> {
+    caller.authorize(payload.trie_label, Scope::Write)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, mut trie, root_idx) = if payload.trie_root == Felt::ZERO {
+                Trie::create_empty(store)?
+            } else {
+                Trie::load_from_root(payload.trie_root, store)?
+            };
 
-    let leaf = TrieLeaf::new(payload.key, payload.value);
-    trie.set(&storage, leaf.get_path(), leaf.data.value)?;
+            let leaf = TrieLeaf::new(payload.key, payload.value);
+            trie.set(&storage, leaf.get_path(), leaf.data.value)?;
 
-    let update = trie.commit(&storage)?;
-    let trie_id = Trie::persist_updates(&storage, &update, &vec![leaf], Some(u64::from(root_idx)))?;
+            let update = trie.commit(&storage)?;
+            let write = Trie::prepare_updates(&storage, &update, &[leaf], Some(u64::from(root_idx)))?
+                .with_root(Some(payload.trie_root), payload.tag)
+                .with_head(payload.trie_root);
+            storage.write(&write)?;
 
-    Ok(Json(WriteResponse {
-        trie_id: u64::from(trie_id),
-        trie_root: update.root_commitment,
-        key: leaf.key,
-        value: leaf.data.value,
-    }))
+            Ok(Json(WriteResponse {
+                trie_id: write.root_idx,
+                trie_root: update.root_commitment,
+                key: leaf.key,
+                value: leaf.data.value,
+            }))
+        })
+        .await
 }
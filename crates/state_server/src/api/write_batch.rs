@@ -0,0 +1,90 @@
+use axum::{extract::State, Extension, Json};
+use pathfinder_crypto::Felt;
+use serde::{Deserialize, Serialize};
+use types::proofs::injected_state::leaf::TrieLeaf;
+
+use crate::{
+    auth::{Caller, Scope},
+    mpt::{error::Error as MptError, trie::Trie},
+    AppState,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteBatchItem {
+    pub key: Felt,
+    pub value: Felt,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteBatchRequest {
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+    pub writes: Vec<WriteBatchItem>,
+    /// Batch the writes build on. Defaults to the latest batch that produced `trie_root`.
+    #[serde(default)]
+    pub parent_batch_id: Option<u64>,
+    /// Optional tag recorded with the resulting root.
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WriteBatchResponse {
+    pub trie_id: u64,
+    pub trie_root: Felt,
+    pub batch_id: u64,
+    pub parent_batch_id: Option<u64>,
+}
+
+/// Applies all writes to `trie_root` in a single commit and records them as one batch.
+/// Nothing is persisted if any step fails.
+pub async fn write_batch(
+    State(state): State<AppState>,
+    Extension(caller): Extension<Caller>,
+    Json(payload): Json<WriteBatchRequest>,
+) -> Result<Json<WriteBatchResponse>, MptError> {
+    if payload.writes.is_empty() {
+        return Err(MptError::InvalidRequest("batch must contain at least one write".to_string()));
+    }
+
+    caller.authorize(payload.trie_label, Scope::Write)?;
+    state
+        .with_store(payload.trie_label, move |store| {
+            let (storage, mut trie, root_idx) = if payload.trie_root == Felt::ZERO {
+                Trie::create_empty(store)?
+            } else {
+                Trie::load_from_root(payload.trie_root, store)?
+            };
+
+            let leaves = payload
+                .writes
+                .into_iter()
+                .map(|write| TrieLeaf::new(write.key, write.value))
+                .collect::<Vec<_>>();
+
+            for leaf in &leaves {
+                trie.set(&storage, leaf.get_path(), leaf.data.value)?;
+            }
+
+            let update = trie.commit(&storage)?;
+            let parent_batch_id = match payload.parent_batch_id {
+                Some(id) => Some(id),
+                None => storage.get_batch_id_by_root(payload.trie_root)?,
+            };
+
+            // Nodes, leaves, batch and root record are persisted in a single write
+            let write = Trie::prepare_updates(&storage, &update, &leaves, Some(u64::from(root_idx)))?
+                .with_batch(parent_batch_id)
+                .with_root(Some(payload.trie_root), payload.tag)
+                .with_head(payload.trie_root);
+            let batch_id = storage.write(&write)?.ok_or_else(|| anyhow::anyhow!("batch was not recorded"))?;
+
+            Ok(Json(WriteBatchResponse {
+                trie_id: write.root_idx,
+                trie_root: update.root_commitment,
+                batch_id,
+                parent_batch_id,
+            }))
+        })
+        .await
+}
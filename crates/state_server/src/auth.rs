@@ -0,0 +1,184 @@
+use std::{collections::HashSet, path::Path};
+
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use pathfinder_crypto::Felt;
+use serde::Deserialize;
+
+use crate::{mpt::error::Error as MptError, AppState, Error};
+
+/// Environment variable holding the API key clients send to the state server.
+pub const API_KEY_ENV: &str = "INJECTED_STATE_API_KEY";
+
+/// Header accepted as an alternative to `Authorization: Bearer <token>`.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// What a request does to a trie. Admin access implies write access, which implies read access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Read,
+    Write,
+    /// Irreversible maintenance, like pruning the history of a trie.
+    Admin,
+}
+
+/// A trie label an API key is scoped to, or `*` for every label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum LabelPattern {
+    Any,
+    Label(Felt),
+}
+
+impl TryFrom<String> for LabelPattern {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "*" {
+            return Ok(LabelPattern::Any);
+        }
+        Felt::from_hex_str(&value)
+            .map(LabelPattern::Label)
+            .map_err(|e| format!("invalid trie label {}: {}", value, e))
+    }
+}
+
+impl LabelPattern {
+    fn matches(&self, trie_label: Felt) -> bool {
+        match self {
+            LabelPattern::Any => true,
+            LabelPattern::Label(label) => *label == trie_label,
+        }
+    }
+}
+
+/// An API key and the labels it may read, write and administer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKey {
+    /// Identifies the key in logs, never the token itself.
+    pub name: String,
+    pub token: String,
+    #[serde(default)]
+    pub read: Vec<LabelPattern>,
+    #[serde(default)]
+    pub write: Vec<LabelPattern>,
+    #[serde(default)]
+    pub admin: Vec<LabelPattern>,
+}
+
+impl ApiKey {
+    pub fn allows(&self, trie_label: Felt, scope: Scope) -> bool {
+        let administers = self.admin.iter().any(|pattern| pattern.matches(trie_label));
+        let writes = administers || self.write.iter().any(|pattern| pattern.matches(trie_label));
+        match scope {
+            Scope::Read => writes || self.read.iter().any(|pattern| pattern.matches(trie_label)),
+            Scope::Write => writes,
+            Scope::Admin => administers,
+        }
+    }
+}
+
+/// The API keys accepted by the server, loaded from a TOML file:
+///
+/// ```toml
+/// [[keys]]
+/// name = "hdp-runner"
+/// token = "..."
+/// read = ["*"]
+/// write = ["0x123"]
+/// admin = []
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthConfig {
+    pub keys: Vec<ApiKey>,
+}
+
+impl AuthConfig {
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let config: AuthConfig = toml::from_str(&std::fs::read_to_string(path)?).map_err(|e| Error::AuthConfig(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Rejects empty and duplicate tokens, so a token always resolves to a single key.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut tokens = HashSet::new();
+        for key in &self.keys {
+            if key.token.is_empty() {
+                return Err(Error::AuthConfig(format!("key {} has an empty token", key.name)));
+            }
+            if !tokens.insert(key.token.as_str()) {
+                return Err(Error::AuthConfig(format!("key {} reuses the token of another key", key.name)));
+            }
+        }
+        Ok(())
+    }
+
+    fn key_by_token(&self, token: &str) -> Option<&ApiKey> {
+        // Compare against every key so the response time does not depend on which key matched
+        self.keys.iter().fold(None, |found, key| {
+            found.or(constant_time_eq(key.token.as_bytes(), token.as_bytes()).then_some(key))
+        })
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn request_token(headers: &HeaderMap) -> Option<&str> {
+    if let Some(value) = headers.get(AUTHORIZATION) {
+        return value.to_str().ok()?.strip_prefix("Bearer ");
+    }
+    headers.get(API_KEY_HEADER)?.to_str().ok()
+}
+
+/// The authenticated client of a request, inserted into the request extensions by [`authenticate`].
+#[derive(Debug, Clone)]
+pub enum Caller {
+    /// Authentication is disabled, every label is readable, writable and administrable.
+    Unrestricted,
+    Key(ApiKey),
+}
+
+impl Caller {
+    /// Fails with `403 Forbidden` if the caller may not access `trie_label` with `scope`.
+    pub fn authorize(&self, trie_label: Felt, scope: Scope) -> Result<(), MptError> {
+        match self {
+            Caller::Unrestricted => Ok(()),
+            Caller::Key(key) if key.allows(trie_label, scope) => Ok(()),
+            Caller::Key(key) => Err(MptError::Forbidden(format!(
+                "key {} has no {:?} access to trie {}",
+                key.name, scope, trie_label
+            ))),
+        }
+    }
+}
+
+/// Resolves the caller from the `Authorization: Bearer` or `X-API-Key` header, failing with `401 Unauthorized`
+/// if authentication is enabled and the token is missing or unknown.
+pub async fn authenticate(State(state): State<AppState>, mut request: Request, next: Next) -> Result<Response, MptError> {
+    let caller = match &state.auth {
+        None => Caller::Unrestricted,
+        Some(config) => {
+            let token = request_token(request.headers()).ok_or(MptError::Unauthorized)?;
+            let key = config.key_by_token(token).ok_or(MptError::Unauthorized)?;
+            Caller::Key(key.clone())
+        }
+    };
+
+    request.extensions_mut().insert(caller);
+    Ok(next.run(request).await)
+}
+
+/// Attaches the API key from [`API_KEY_ENV`] to a request to the state server, if set.
+pub fn with_api_key(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match std::env::var(API_KEY_ENV) {
+        Ok(token) if !token.is_empty() => request.bearer_auth(token),
+        _ => request,
+    }
+}
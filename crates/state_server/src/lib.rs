@@ -1,6 +1,8 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
 use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
     routing::{get, post},
     Router,
 };
@@ -8,42 +10,89 @@ use thiserror::Error;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 use crate::{
-    api::{create_trie::create_trie, proof::get_state_proofs, read::read, root_to_node_idx::get_trie_root_node_idx, write::write},
-    mpt::db::ConnectionManager,
+    api::{
+        create_trie::create_trie,
+        delete::delete,
+        export::export,
+        head::{get_head, swap_head},
+        import::{import, MAX_SNAPSHOT_BYTES},
+        leaves::get_leaves,
+        proof::get_state_proofs,
+        prune::prune,
+        read::read,
+        root_to_node_idx::get_trie_root_node_idx,
+        roots::{get_root_lineage, get_roots},
+        write::write,
+        write_batch::write_batch,
+    },
+    auth::{authenticate, AuthConfig},
+    mpt::db::{
+        store::{StoreBackend, TrieStore},
+        ConnectionManager,
+    },
 };
 
 pub mod api;
+pub mod auth;
 pub mod mpt;
 
 #[derive(Debug, Clone)]
 pub struct AppState {
-    pub connection_manager: Arc<Mutex<ConnectionManager>>,
+    pub connection_manager: Arc<ConnectionManager>,
+    /// The accepted API keys, `None` if authentication is disabled.
+    pub auth: Option<Arc<AuthConfig>>,
 }
 
 impl AppState {
-    pub fn new(db_root_path: &str) -> Result<Self, Error> {
-        let connection_manager = ConnectionManager::new(db_root_path);
+    pub fn new(db_root_path: &str, backend: StoreBackend) -> Result<Self, Error> {
+        let connection_manager = ConnectionManager::new(db_root_path, backend);
 
         Ok(Self {
-            connection_manager: Arc::new(Mutex::new(connection_manager)),
+            connection_manager: Arc::new(connection_manager),
+            auth: None,
         })
     }
 
-    /// Creates a new AppState for memory mode using in-memory databases.
+    /// Creates a new AppState for memory mode using in-memory stores.
     pub fn new_memory() -> Result<Self, Error> {
         let connection_manager = ConnectionManager::new_memory();
 
         Ok(Self {
-            connection_manager: Arc::new(Mutex::new(connection_manager)),
+            connection_manager: Arc::new(connection_manager),
+            auth: None,
         })
     }
 
-    pub fn get_connection(
-        &self,
-        trie_label: pathfinder_crypto::Felt,
-    ) -> Result<r2d2::PooledConnection<r2d2_sqlite::SqliteConnectionManager>, mpt::error::Error> {
-        self.connection_manager.lock().unwrap().create_tables_if_not_exists(trie_label)?;
-        self.connection_manager.lock().unwrap().get_connection(trie_label)
+    /// Requires every request to carry one of the API keys of `config`.
+    pub fn with_auth(mut self, config: AuthConfig) -> Self {
+        self.auth = Some(Arc::new(config));
+        self
+    }
+
+    pub fn get_store(&self, trie_label: pathfinder_crypto::Felt) -> Result<Box<dyn TrieStore>, mpt::error::Error> {
+        self.connection_manager.get_store(trie_label)
+    }
+
+    /// Runs `f` on the blocking thread pool, as opening and reading the stores is synchronous file I/O that must
+    /// not stall the async workers.
+    pub async fn blocking<T, F>(&self, f: F) -> Result<T, mpt::error::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AppState) -> Result<T, mpt::error::Error> + Send + 'static,
+    {
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || f(&state))
+            .await
+            .map_err(|e| mpt::error::Error::Any(e.into()))?
+    }
+
+    /// Runs `f` against the store of `trie_label` on the blocking thread pool.
+    pub async fn with_store<T, F>(&self, trie_label: pathfinder_crypto::Felt, f: F) -> Result<T, mpt::error::Error>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn TrieStore) -> Result<T, mpt::error::Error> + Send + 'static,
+    {
+        self.blocking(move |state| f(&*state.get_store(trie_label)?)).await
     }
 }
 
@@ -52,10 +101,20 @@ pub fn create_router(state: AppState) -> Router {
         // GET
         .route("/get_trie_root_node_idx", get(get_trie_root_node_idx))
         .route("/read", get(read))
+        .route("/export", get(export))
+        .route("/leaves", get(get_leaves))
+        .route("/roots", get(get_roots))
+        .route("/root_lineage", get(get_root_lineage))
+        .route("/head", get(get_head).post(swap_head))
         // POST
         .route("/get_state_proofs", post(get_state_proofs))
         .route("/write", post(write))
+        .route("/write_batch", post(write_batch))
+        .route("/delete", post(delete))
+        .route("/prune", post(prune))
+        .route("/import", post(import).layer(DefaultBodyLimit::max(MAX_SNAPSHOT_BYTES)))
         .route("/create_trie", post(create_trie))
+        .layer(middleware::from_fn_with_state(state.clone(), authenticate))
         .layer(CorsLayer::permissive())
         .layer(TraceLayer::new_for_http())
         .with_state(state)
@@ -68,4 +127,7 @@ pub enum Error {
 
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
+
+    #[error("Invalid auth config: {0}")]
+    AuthConfig(String),
 }
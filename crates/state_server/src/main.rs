@@ -1,15 +1,33 @@
-use clap::Parser;
-use state_server::{create_router, AppState, Error};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+use clap::{Parser, Subcommand};
+use pathfinder_crypto::Felt;
+use state_server::{
+    auth::AuthConfig,
+    create_router,
+    mpt::{
+        db::store::StoreBackend,
+        error::Error as MptError,
+        prune::{self, RetainPolicy},
+        snapshot::{self, SnapshotHeader},
+    },
+    AppState, Error,
+};
 use tokio::net::TcpListener;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
 /// Binds to the given host and port and starts the axum server.
-pub async fn start_server(port: u16, host: &str, db_root_path: &str) -> Result<(), Error> {
-    // Initialize the logger/subscriber.
-    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
-
-    let state = AppState::new(db_root_path)?;
+pub async fn start_server(port: u16, host: &str, db_root_path: &str, backend: StoreBackend, auth: Option<AuthConfig>) -> Result<(), Error> {
+    let mut state = AppState::new(db_root_path, backend)?;
+    if let Some(auth) = auth {
+        info!("🔒 Requiring one of {} API keys", auth.keys.len());
+        state = state.with_auth(auth);
+    }
     let app = create_router(state);
     let addr = format!("{}:{}", host, port);
 
@@ -21,6 +39,74 @@ pub async fn start_server(port: u16, host: &str, db_root_path: &str) -> Result<(
     Ok(())
 }
 
+/// Prunes the given tries, or every trie stored under the database root folder if none are given.
+///
+/// Roots belong to a single trie, so retaining explicit roots requires the labels of the tries they belong to.
+pub fn prune_tries(db_root_path: &str, backend: StoreBackend, labels: &[Felt], policy: &RetainPolicy) -> Result<(), Error> {
+    if labels.is_empty() && matches!(policy, RetainPolicy::Roots(_)) {
+        return Err(MptError::InvalidRequest("retaining explicit roots requires the labels of the tries to prune".to_string()).into());
+    }
+    let state = AppState::new(db_root_path, backend)?;
+    let labels = if labels.is_empty() {
+        state.connection_manager.labels()?
+    } else {
+        labels.to_vec()
+    };
+
+    for label in labels {
+        let store = state.get_store(label)?;
+        let stats = prune::prune(&*store, policy)?;
+        info!(
+            "🧹 Pruned trie {}: kept {} roots, deleted {} nodes and {} leafs",
+            label,
+            stats.roots_kept.len(),
+            stats.nodes_deleted,
+            stats.leafs_deleted
+        );
+    }
+
+    Ok(())
+}
+
+/// Writes a snapshot of the trie under `trie_root` to `output`.
+pub fn export_trie(db_root_path: &str, backend: StoreBackend, trie_label: Felt, trie_root: Felt, output: &PathBuf) -> Result<(), Error> {
+    let state = AppState::new(db_root_path, backend)?;
+    let store = state.get_store(trie_label)?;
+    let leaves = snapshot::export(&*store, trie_label, trie_root, BufWriter::new(File::create(output)?))?;
+
+    info!("📦 Exported {} leaves of trie {} at root {}", leaves, trie_label, trie_root);
+
+    Ok(())
+}
+
+/// Rebuilds the trie stored in the snapshot at `input`, under `trie_label` if given.
+pub fn import_trie(
+    db_root_path: &str,
+    backend: StoreBackend,
+    input: &PathBuf,
+    trie_label: Option<Felt>,
+    tag: Option<String>,
+) -> Result<(), Error> {
+    let mut lines = snapshot::read_lines(BufReader::new(File::open(input)?));
+    let header = SnapshotHeader::read(&mut lines)?;
+    let trie_label = trie_label.unwrap_or(header.trie_label);
+
+    let state = AppState::new(db_root_path, backend)?;
+    let store = state.get_store(trie_label)?;
+    let imported = snapshot::import(&*store, &header, lines, tag)?;
+
+    info!(
+        "📥 Imported {} leaves into trie {} at root {}",
+        imported.leaves, trie_label, header.trie_root
+    );
+
+    Ok(())
+}
+
+fn parse_felt(value: &str) -> Result<Felt, String> {
+    Felt::from_hex_str(value).map_err(|e| format!("invalid felt {}: {}", value, e))
+}
+
 /// A stateful API server for managing HDP modules injected states.
 #[derive(Parser, Debug)]
 struct Args {
@@ -33,15 +119,118 @@ struct Args {
     host: String,
 
     /// The path to the database root folder
-    #[arg(long, default_value = "db")]
+    #[arg(long, default_value = "db", global = true)]
     db_root_path: String,
+
+    /// The storage backend holding the tries
+    #[arg(long, value_enum, default_value_t = StoreBackend::Sqlite, global = true)]
+    backend: StoreBackend,
+
+    /// Prune the stored tries before serving, keeping only these roots (repeatable), requires --prune-label
+    #[arg(
+        long = "prune-keep-root",
+        value_parser = parse_felt,
+        conflicts_with = "prune_keep_last",
+        requires = "prune_labels"
+    )]
+    prune_keep_roots: Vec<Felt>,
+
+    /// Prune the stored tries before serving, keeping only the last N roots of each
+    #[arg(long)]
+    prune_keep_last: Option<usize>,
+
+    /// Restrict pruning to these trie labels (repeatable), defaults to every stored trie
+    #[arg(long = "prune-label", value_parser = parse_felt)]
+    prune_labels: Vec<Felt>,
+
+    /// Exit after pruning instead of starting the server
+    #[arg(long)]
+    prune_only: bool,
+
+    /// TOML file listing the accepted API keys and their per-label scopes, leaves the API open if unset
+    #[arg(long)]
+    auth_config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write a snapshot of a trie under a given root to a file
+    Export {
+        /// The label of the trie to export
+        #[arg(long, value_parser = parse_felt)]
+        trie_label: Felt,
+
+        /// The root to export the leaves of
+        #[arg(long, value_parser = parse_felt)]
+        trie_root: Felt,
+
+        /// The snapshot file to write
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Rebuild a trie from a snapshot file, checking its root
+    Import {
+        /// The snapshot file to read
+        #[arg(long)]
+        input: PathBuf,
+
+        /// Import under this label instead of the one recorded in the snapshot
+        #[arg(long, value_parser = parse_felt)]
+        trie_label: Option<Felt>,
+
+        /// Tag recorded with the imported root
+        #[arg(long)]
+        tag: Option<String>,
+    },
+}
+
+impl Args {
+    fn retain_policy(&self) -> Option<RetainPolicy> {
+        match (self.prune_keep_roots.is_empty(), self.prune_keep_last) {
+            (false, _) => Some(RetainPolicy::Roots(self.prune_keep_roots.clone())),
+            (true, Some(n)) => Some(RetainPolicy::Last(n)),
+            (true, None) => None,
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     let args = Args::parse();
 
-    if let Err(e) = start_server(args.port, &args.host, &args.db_root_path).await {
+    // Initialize the logger/subscriber.
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    match &args.command {
+        Some(Command::Export {
+            trie_label,
+            trie_root,
+            output,
+        }) => return export_trie(&args.db_root_path, args.backend, *trie_label, *trie_root, output),
+        Some(Command::Import { input, trie_label, tag }) => {
+            return import_trie(&args.db_root_path, args.backend, input, *trie_label, tag.clone())
+        }
+        None => {}
+    }
+
+    if let Some(policy) = args.retain_policy() {
+        prune_tries(&args.db_root_path, args.backend, &args.prune_labels, &policy)?;
+    } else if args.prune_only {
+        return Err(MptError::InvalidRequest("--prune-only requires --prune-keep-root or --prune-keep-last".to_string()).into());
+    }
+
+    if args.prune_only {
+        return Ok(());
+    }
+
+    let auth = args.auth_config.as_deref().map(AuthConfig::from_file).transpose()?;
+    if let Err(e) = start_server(args.port, &args.host, &args.db_root_path, args.backend, auth).await {
         eprintln!("💥 Server failed to start: {}", e);
         return Err(e);
     }
@@ -1,136 +1,138 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
 
+pub mod store;
 pub mod trie;
 use pathfinder_crypto::Felt;
-use r2d2::{Pool, PooledConnection};
+use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
+use store::{kv::RedbStore, memory::MemoryStore, sqlite::SqliteStore, StoreBackend, TrieStore};
 use tracing::debug;
 
 use crate::mpt::error::Error;
 
+/// The storage of a trie label opened so far, shared by every store handed out for that label.
+#[derive(Debug, Clone)]
+enum OpenStore {
+    Sqlite(Arc<Pool<SqliteConnectionManager>>),
+    Memory(MemoryStore),
+    Redb(RedbStore),
+}
+
+/// Opens the store of each trie label once and hands out handles to it.
+///
+/// Requests only take the read lock once a label is open, and connections are checked out of the pools after
+/// releasing it. Databases are opened under a lock of their own label and only inserted under the write lock, so
+/// requests on different labels never wait on each other.
 #[derive(Debug)]
 pub struct ConnectionManager {
-    db_root_path: Option<String>,
-    pools: HashMap<Felt, Arc<Pool<SqliteConnectionManager>>>,
-    is_memory_mode: bool,
+    db_root_path: String,
+    backend: StoreBackend,
+    stores: RwLock<HashMap<Felt, OpenStore>>,
+    /// One lock per label being opened, so a database is only opened once.
+    opening: Mutex<HashMap<Felt, Arc<Mutex<()>>>>,
 }
 
 impl ConnectionManager {
-    /// Creates a new ConnectionManager with a connection pool to the specified database file.
-    pub fn new(db_root_path: &str) -> Self {
-        debug!("using root database path: {}", db_root_path);
+    /// Creates a new ConnectionManager storing one database per trie under `db_root_path`.
+    pub fn new(db_root_path: &str, backend: StoreBackend) -> Self {
+        debug!("using root database path: {} with the {:?} backend", db_root_path, backend);
         ConnectionManager {
-            db_root_path: Some(db_root_path.to_string()),
-            pools: HashMap::new(),
-            is_memory_mode: false,
+            db_root_path: db_root_path.to_string(),
+            backend,
+            stores: RwLock::new(HashMap::new()),
+            opening: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Creates a new ConnectionManager for memory mode using in-memory databases.
-    /// Each trie gets its own in-memory database to avoid collisions.
+    /// Creates a new ConnectionManager for memory mode.
+    /// Each trie gets its own in-memory store to avoid collisions.
     pub fn new_memory() -> Self {
-        debug!("using in-memory databases for memory mode");
+        debug!("using in-memory stores for memory mode");
         ConnectionManager {
-            db_root_path: None,
-            pools: HashMap::new(),
-            is_memory_mode: true,
+            db_root_path: String::new(),
+            backend: StoreBackend::Memory,
+            stores: RwLock::new(HashMap::new()),
+            opening: Mutex::new(HashMap::new()),
         }
     }
 
-    /// Gets a connection from the pool.
-    pub fn get_connection(&mut self, trie_label: Felt) -> Result<PooledConnection<SqliteConnectionManager>, Error> {
-        if !self.pools.contains_key(&trie_label) {
-            let manager = if self.is_memory_mode {
-                // In memory mode, use in-memory databases with unique names to avoid collisions
-                // Each trie gets its own in-memory database identified by the trie_label
-                SqliteConnectionManager::memory()
-            } else {
-                // In file mode, use file-based databases
-                let db_path = format!("{}/{}.db", self.db_root_path.as_ref().unwrap(), trie_label);
-                std::fs::create_dir_all(self.db_root_path.as_ref().unwrap()).map_err(Error::Io)?;
-                SqliteConnectionManager::file(db_path)
-            };
-
-            let pool = Pool::new(manager).map_err(Error::Pool)?;
-            self.pools.insert(trie_label, Arc::new(pool));
-        }
-
-        Ok(self.pools.get(&trie_label).unwrap().get()?)
+    pub fn backend(&self) -> StoreBackend {
+        self.backend
     }
 
-    pub fn create_tables_if_not_exists(&mut self, trie_label: Felt) -> Result<(), Error> {
-        self.get_connection(trie_label)?.execute(
-            "CREATE TABLE IF NOT EXISTS trie_nodes (
-                idx INTEGER PRIMARY KEY,
-                hash BLOB NOT NULL,
-                data BLOB,
-                trie_idx INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        self.get_connection(trie_label)?.execute(
-            "CREATE TABLE IF NOT EXISTS leafs (
-                idx INTEGER PRIMARY KEY,
-                key BLOB NOT NULL,
-                value BLOB NOT NULL,
-                root_idx INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        Ok(())
+    fn db_path(&self, trie_label: Felt, extension: &str) -> Result<String, Error> {
+        std::fs::create_dir_all(&self.db_root_path).map_err(Error::Io)?;
+        Ok(format!("{}/{}.{}", self.db_root_path, trie_label, extension))
     }
 
-    pub fn create_table(&mut self, trie_label: Felt) -> Result<(), Error> {
-        // Drop existing tables first to ensure clean schema
-        let _ = self.delete_tables(trie_label);
-
-        self.get_connection(trie_label)?.execute(
-            "CREATE TABLE trie_nodes (
-                idx INTEGER PRIMARY KEY,
-                hash BLOB NOT NULL,
-                data BLOB,
-                trie_idx INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        self.get_connection(trie_label)?.execute(
-            "CREATE TABLE leafs (
-                idx INTEGER PRIMARY KEY,
-                key BLOB NOT NULL,
-                value BLOB NOT NULL,
-                root_idx INTEGER NOT NULL
-            )",
-            [],
-        )?;
-
-        // self.get_connection()?.execute(
-        //     "CREATE TABLE IF NOT EXISTS batches (
-        //         id INTEGER PRIMARY KEY,
-        //         parent_id INTEGER,
-        //         status TEXT NOT NULL,
-        //         root_idx INTEGER NOT NULL,
-        //         FOREIGN KEY (parent_id) REFERENCES batches(id)
-        //     )",
-        //     [],
-        // )?;
-
-        Ok(())
+    /// Gets the store of a trie, opening and creating its database on first use.
+    pub fn get_store(&self, trie_label: Felt) -> Result<Box<dyn TrieStore>, Error> {
+        let open = self.stores.read().unwrap().get(&trie_label).cloned();
+        let open = match open {
+            Some(open) => open,
+            None => self.open_store(trie_label)?,
+        };
+
+        Ok(match open {
+            OpenStore::Sqlite(pool) => Box::new(SqliteStore::new(pool.get()?)),
+            OpenStore::Memory(store) => Box::new(store),
+            OpenStore::Redb(store) => Box::new(store),
+        })
     }
 
-    /// Deletes all tables from the database.
-    pub fn delete_tables(&mut self, trie_label: Felt) -> Result<(), Error> {
-        // Drop the leafs table
-        self.get_connection(trie_label)?.execute("DROP TABLE IF EXISTS leafs", [])?;
+    /// Opens the database of a trie and creates its schema, unless a concurrent request already did.
+    fn open_store(&self, trie_label: Felt) -> Result<OpenStore, Error> {
+        let opening = self.opening.lock().unwrap().entry(trie_label).or_default().clone();
+        let _opening = opening.lock().unwrap();
+        if let Some(open) = self.stores.read().unwrap().get(&trie_label) {
+            return Ok(open.clone());
+        }
 
-        // Drop the trie_nodes table
-        self.get_connection(trie_label)?.execute("DROP TABLE IF EXISTS trie_nodes", [])?;
+        let store = match self.backend {
+            StoreBackend::Sqlite => {
+                let manager = SqliteConnectionManager::file(self.db_path(trie_label, "db")?)
+                    .with_init(|conn| conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;"));
+                let pool = Pool::new(manager).map_err(Error::Pool)?;
+                store::sqlite::create_tables_if_not_exists(&pool.get()?)?;
+                OpenStore::Sqlite(Arc::new(pool))
+            }
+            StoreBackend::Memory => OpenStore::Memory(MemoryStore::new()),
+            StoreBackend::Redb => OpenStore::Redb(RedbStore::open(self.db_path(trie_label, "redb")?)?),
+        };
+        self.stores.write().unwrap().insert(trie_label, store.clone());
+
+        Ok(store)
+    }
 
-        // Uncomment if the batches table is added in the future
-        // self.get_connection()?.execute("DROP TABLE IF EXISTS batches", [])?;
+    /// Lists the labels of the tries stored under the database root folder.
+    /// In memory mode, lists the labels of the tries opened so far.
+    pub fn labels(&self) -> Result<Vec<Felt>, Error> {
+        let extension = match self.backend {
+            StoreBackend::Sqlite => "db",
+            StoreBackend::Redb => "redb",
+            StoreBackend::Memory => return Ok(self.stores.read().unwrap().keys().copied().collect()),
+        };
+
+        let mut labels = vec![];
+        if !std::path::Path::new(&self.db_root_path).exists() {
+            return Ok(labels);
+        }
+        for entry in std::fs::read_dir(&self.db_root_path)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == extension) {
+                if let Some(label) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| Felt::from_hex_str(stem).ok())
+                {
+                    labels.push(label);
+                }
+            }
+        }
 
-        Ok(())
+        Ok(labels)
     }
 }
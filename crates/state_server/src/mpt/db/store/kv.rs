@@ -0,0 +1,452 @@
+use std::{collections::HashSet, fmt, path::Path, sync::Arc};
+
+use pathfinder_crypto::Felt;
+use pathfinder_storage::StoredNode;
+use redb::{Database, MultimapTableDefinition, ReadableMultimapTable, ReadableTable, TableDefinition, WriteTransaction};
+use serde::{Deserialize, Serialize};
+
+use super::{check_head, encode_node, felt_from_bytes, head_after, now, RetainCounts, RetainSelector, StoreWrite, TrieStore};
+use crate::mpt::{db::trie::RootRecord, error::Error};
+
+type LeafKey = ([u8; 32], u64);
+type LeafValue = ([u8; 32], u64);
+
+/// Node index to node hash followed by the encoded node.
+const NODES: TableDefinition<u64, &[u8]> = TableDefinition::new("nodes");
+/// Node hash to the first persisted node index with that hash.
+const NODE_HASHES: TableDefinition<[u8; 32], u64> = TableDefinition::new("node_hashes");
+/// `(key, sequence number)` to `(value, root_idx)`, so the history of a key is contiguous and ordered.
+const LEAVES: TableDefinition<LeafKey, LeafValue> = TableDefinition::new("leaves");
+/// Batch id to JSON encoded batch.
+const BATCHES: TableDefinition<u64, &[u8]> = TableDefinition::new("batches");
+/// Trie root to the latest batch that produced it.
+const BATCH_ROOTS: TableDefinition<[u8; 32], u64> = TableDefinition::new("batch_roots");
+/// Root record id to JSON encoded record.
+const ROOTS: TableDefinition<u64, &[u8]> = TableDefinition::new("roots");
+/// Trie root to the ids of its records.
+const ROOT_IDS: MultimapTableDefinition<[u8; 32], u64> = MultimapTableDefinition::new("root_ids");
+const COUNTERS: TableDefinition<&str, u64> = TableDefinition::new("counters");
+/// Holds the tracked head root under [`HEAD`], if any.
+const HEADS: TableDefinition<&str, [u8; 32]> = TableDefinition::new("heads");
+
+const LEAF_SEQ: &str = "leaf_seq";
+const HEAD: &str = "head";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Batch {
+    parent_id: Option<u64>,
+    root_idx: u64,
+    trie_root: Felt,
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, Error> {
+    Ok(serde_json::to_vec(value).map_err(anyhow::Error::from)?)
+}
+
+fn from_json<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, Error> {
+    serde_json::from_slice(bytes).map_err(|e| Error::MptDecodeError(e.to_string()))
+}
+
+fn decode_node(bytes: &[u8]) -> Result<(Felt, StoredNode), Error> {
+    Ok((felt_from_bytes(&bytes[..32])?, StoredNode::decode(&bytes[32..])?))
+}
+
+/// The latest entry of `key`, or the latest one persisted at or before `max_root_idx` if set.
+fn latest_leaf(
+    leaves: &impl ReadableTable<LeafKey, LeafValue>,
+    key: [u8; 32],
+    max_root_idx: Option<u64>,
+) -> Result<Option<LeafValue>, Error> {
+    for entry in leaves.range((key, 0)..=(key, u64::MAX))?.rev() {
+        let (value, root_idx) = entry?.1.value();
+        if !matches!(max_root_idx, Some(max_root_idx) if root_idx > max_root_idx) {
+            return Ok(Some((value, root_idx)));
+        }
+    }
+
+    Ok(None)
+}
+
+fn read_head(heads: &impl ReadableTable<&'static str, [u8; 32]>) -> Result<Option<Felt>, Error> {
+    heads.get(HEAD)?.map(|head| felt_from_bytes(&head.value())).transpose()
+}
+
+fn root_records(
+    roots: &impl ReadableTable<u64, &'static [u8]>,
+    root_ids: &impl ReadableMultimapTable<[u8; 32], u64>,
+    trie_root: Felt,
+) -> Result<Vec<RootRecord>, Error> {
+    let mut records = vec![];
+    for id in root_ids.get(trie_root.to_be_bytes())? {
+        if let Some(record) = roots.get(id?.value())? {
+            records.push(from_json(record.value())?);
+        }
+    }
+
+    Ok(records)
+}
+
+/// A trie stored in a redb key-value database, one file per trie label. Clones share the same database.
+#[derive(Clone)]
+pub struct RedbStore {
+    db: Arc<Database>,
+}
+
+impl fmt::Debug for RedbStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbStore").finish_non_exhaustive()
+    }
+}
+
+impl RedbStore {
+    /// Opens the database at `path`, creating it and its tables if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let db = Database::create(path)?;
+
+        let tx = db.begin_write()?;
+        tx.open_table(NODES)?;
+        tx.open_table(NODE_HASHES)?;
+        tx.open_table(LEAVES)?;
+        tx.open_table(BATCHES)?;
+        tx.open_table(BATCH_ROOTS)?;
+        tx.open_table(ROOTS)?;
+        tx.open_multimap_table(ROOT_IDS)?;
+        tx.open_table(COUNTERS)?;
+        tx.open_table(HEADS)?;
+        tx.commit()?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Reads every stored node and every root index referenced by leaves, batches and root records.
+    fn retain_input(tx: &WriteTransaction) -> Result<(Vec<(u64, Felt, StoredNode)>, Vec<u64>), Error> {
+        let mut nodes = vec![];
+        for entry in tx.open_table(NODES)?.iter()? {
+            let (idx, data) = entry?;
+            let (hash, node) = decode_node(data.value())?;
+            nodes.push((idx.value(), hash, node));
+        }
+
+        let mut indices = HashSet::new();
+        for entry in tx.open_table(LEAVES)?.iter()? {
+            indices.insert(entry?.1.value().1);
+        }
+        for entry in tx.open_table(BATCHES)?.iter()? {
+            indices.insert(from_json::<Batch>(entry?.1.value())?.root_idx);
+        }
+        for entry in tx.open_table(ROOTS)?.iter()? {
+            indices.insert(from_json::<RootRecord>(entry?.1.value())?.root_idx);
+        }
+
+        Ok((nodes, indices.into_iter().collect()))
+    }
+
+    /// Rebuilds the tables indexing nodes, batches and root records after entries were removed.
+    fn reindex(tx: &WriteTransaction) -> Result<(), Error> {
+        tx.delete_table(NODE_HASHES)?;
+        tx.delete_table(BATCH_ROOTS)?;
+        tx.delete_multimap_table(ROOT_IDS)?;
+
+        let mut node_hashes = tx.open_table(NODE_HASHES)?;
+        for entry in tx.open_table(NODES)?.iter()? {
+            let (idx, data) = entry?;
+            let hash = felt_from_bytes(&data.value()[..32])?.to_be_bytes();
+            if node_hashes.get(hash)?.is_none() {
+                node_hashes.insert(hash, idx.value())?;
+            }
+        }
+
+        let mut batch_roots = tx.open_table(BATCH_ROOTS)?;
+        for entry in tx.open_table(BATCHES)?.iter()? {
+            let (id, batch) = entry?;
+            let batch: Batch = from_json(batch.value())?;
+            batch_roots.insert(batch.trie_root.to_be_bytes(), id.value())?;
+        }
+
+        let mut root_ids = tx.open_multimap_table(ROOT_IDS)?;
+        for entry in tx.open_table(ROOTS)?.iter()? {
+            let (id, record) = entry?;
+            let record: RootRecord = from_json(record.value())?;
+            root_ids.insert(record.trie_root.to_be_bytes(), id.value())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TrieStore for RedbStore {
+    fn node(&self, idx: u64) -> Result<Option<(Felt, StoredNode)>, Error> {
+        let tx = self.db.begin_read()?;
+        let nodes = tx.open_table(NODES)?;
+
+        nodes.get(idx)?.map(|data| decode_node(data.value())).transpose()
+    }
+
+    fn node_idx_by_hash(&self, hash: Felt) -> Result<Option<u64>, Error> {
+        let tx = self.db.begin_read()?;
+        let node_hashes = tx.open_table(NODE_HASHES)?;
+
+        Ok(node_hashes.get(hash.to_be_bytes())?.map(|idx| idx.value()))
+    }
+
+    fn max_node_idx(&self) -> Result<u64, Error> {
+        let tx = self.db.begin_read()?;
+        let nodes = tx.open_table(NODES)?;
+
+        Ok(nodes.last()?.map_or(0, |(idx, _)| idx.value()))
+    }
+
+    fn leaf(&self, key: Felt, max_root_idx: Option<u64>) -> Result<Option<Felt>, Error> {
+        let tx = self.db.begin_read()?;
+        let leaves = tx.open_table(LEAVES)?;
+
+        latest_leaf(&leaves, key.to_be_bytes(), max_root_idx)?
+            .map(|(value, _)| felt_from_bytes(&value))
+            .transpose()
+    }
+
+    fn leaf_values(&self, key: Felt, max_root_idx: u64) -> Result<Vec<Felt>, Error> {
+        let tx = self.db.begin_read()?;
+        let leaves = tx.open_table(LEAVES)?;
+        let key = key.to_be_bytes();
+
+        let mut values = vec![];
+        for entry in leaves.range((key, 0)..=(key, u64::MAX))?.rev() {
+            let (value, root_idx) = entry?.1.value();
+            if root_idx <= max_root_idx {
+                values.push(felt_from_bytes(&value)?);
+            }
+        }
+
+        Ok(values)
+    }
+
+    fn leaf_keys(&self) -> Result<Vec<Felt>, Error> {
+        let tx = self.db.begin_read()?;
+        let mut keys: Vec<Felt> = vec![];
+        for entry in tx.open_table(LEAVES)?.iter()? {
+            let key = felt_from_bytes(&entry?.0.value().0)?;
+            if keys.last() != Some(&key) {
+                keys.push(key);
+            }
+        }
+
+        Ok(keys)
+    }
+
+    fn batch_id_by_root(&self, trie_root: Felt) -> Result<Option<u64>, Error> {
+        let tx = self.db.begin_read()?;
+        let batch_roots = tx.open_table(BATCH_ROOTS)?;
+
+        Ok(batch_roots.get(trie_root.to_be_bytes())?.map(|id| id.value()))
+    }
+
+    fn roots(&self, after_id: u64, limit: usize, tag: Option<&str>) -> Result<Vec<RootRecord>, Error> {
+        let tx = self.db.begin_read()?;
+        let mut roots = vec![];
+        for entry in tx.open_table(ROOTS)?.range(after_id + 1..)? {
+            if roots.len() == limit {
+                break;
+            }
+            let record: RootRecord = from_json(entry?.1.value())?;
+            if tag.is_none() || record.tag.as_deref() == tag {
+                roots.push(record);
+            }
+        }
+
+        Ok(roots)
+    }
+
+    fn root_records(&self, trie_root: Felt) -> Result<Vec<RootRecord>, Error> {
+        let tx = self.db.begin_read()?;
+
+        root_records(&tx.open_table(ROOTS)?, &tx.open_multimap_table(ROOT_IDS)?, trie_root)
+    }
+
+    fn head(&self) -> Result<Option<Felt>, Error> {
+        let tx = self.db.begin_read()?;
+
+        read_head(&tx.open_table(HEADS)?)
+    }
+
+    fn swap_head(&self, expected: Option<Felt>, head: Option<Felt>) -> Result<(), Error> {
+        let tx = self.db.begin_write()?;
+        {
+            let mut heads = tx.open_table(HEADS)?;
+            check_head(read_head(&heads)?, expected)?;
+            match head {
+                Some(head) => heads.insert(HEAD, head.to_be_bytes())?,
+                None => heads.remove(HEAD)?,
+            };
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn write(&self, write: &StoreWrite) -> Result<Option<u64>, Error> {
+        let tx = self.db.begin_write()?;
+        let mut batch_id = None;
+
+        {
+            let mut heads = tx.open_table(HEADS)?;
+            let head = read_head(&heads)?;
+            if let Some(next_head) = head_after(head, write)?.filter(|next_head| Some(*next_head) != head) {
+                heads.insert(HEAD, next_head.to_be_bytes())?;
+            }
+
+            let mut nodes = tx.open_table(NODES)?;
+            let mut node_hashes = tx.open_table(NODE_HASHES)?;
+            for (idx, hash, node) in &write.nodes {
+                if nodes.get(*idx)?.is_some() {
+                    continue;
+                }
+                let mut data = hash.to_be_bytes().to_vec();
+                data.extend(encode_node(node)?);
+                nodes.insert(*idx, data.as_slice())?;
+                if node_hashes.get(hash.to_be_bytes())?.is_none() {
+                    node_hashes.insert(hash.to_be_bytes(), *idx)?;
+                }
+            }
+
+            let mut leaves = tx.open_table(LEAVES)?;
+            let mut counters = tx.open_table(COUNTERS)?;
+            let mut seq = counters.get(LEAF_SEQ)?.map_or(0, |seq| seq.value());
+            for (key, value) in &write.leaves {
+                let (key, value) = (key.to_be_bytes(), value.to_be_bytes());
+                // Compare against the latest value only, so a key can be set again after a delete or an overwrite
+                if latest_leaf(&leaves, key, None)?.map(|(latest, _)| latest) != Some(value) {
+                    seq += 1;
+                    leaves.insert((key, seq), (value, write.root_idx))?;
+                }
+            }
+            counters.insert(LEAF_SEQ, seq)?;
+
+            if let Some(batch) = write.batch {
+                let mut batches = tx.open_table(BATCHES)?;
+                let id = batches.last()?.map_or(1, |(id, _)| id.value() + 1);
+                let batch = Batch {
+                    parent_id: batch.parent_id,
+                    root_idx: write.root_idx,
+                    trie_root: write.trie_root,
+                };
+                batches.insert(id, to_json(&batch)?.as_slice())?;
+                tx.open_table(BATCH_ROOTS)?.insert(write.trie_root.to_be_bytes(), id)?;
+                batch_id = Some(id);
+            }
+
+            if let Some(root) = &write.root {
+                let mut roots = tx.open_table(ROOTS)?;
+                let mut root_ids = tx.open_multimap_table(ROOT_IDS)?;
+                if !write.skips_root(root, &root_records(&roots, &root_ids, write.trie_root)?) {
+                    let id = roots.last()?.map_or(1, |(id, _)| id.value() + 1);
+                    let record = RootRecord {
+                        id,
+                        trie_root: write.trie_root,
+                        root_idx: write.root_idx,
+                        parent_root: root.parent_root,
+                        created_at: now(),
+                        tag: root.tag.clone(),
+                    };
+                    roots.insert(id, to_json(&record)?.as_slice())?;
+                    root_ids.insert(write.trie_root.to_be_bytes(), id)?;
+                }
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(batch_id)
+    }
+
+    /// Freed pages are reused by later writes rather than returned to the file system.
+    fn retain(&self, select: &mut RetainSelector) -> Result<RetainCounts, Error> {
+        let tx = self.db.begin_write()?;
+        let mut counts = RetainCounts::default();
+
+        let (nodes, indices) = Self::retain_input(&tx)?;
+        let (reachable, kept_roots) = select(nodes, indices)?;
+
+        {
+            let mut nodes = tx.open_table(NODES)?;
+            let mut deleted = vec![];
+            for entry in nodes.iter()? {
+                let idx = entry?.0.value();
+                if !reachable.contains(&idx) {
+                    deleted.push(idx);
+                }
+            }
+            for idx in deleted {
+                nodes.remove(idx)?;
+                counts.nodes_deleted += 1;
+            }
+
+            // A retained root reads, per key, the latest leaf entry persisted at or before it
+            let mut leaves = tx.open_table(LEAVES)?;
+            let mut deleted = vec![];
+            let mut history: Vec<(LeafKey, u64)> = vec![];
+            let mut retire = |history: &mut Vec<(LeafKey, u64)>| {
+                let retained: HashSet<u64> = kept_roots
+                    .iter()
+                    .filter_map(|kept| history.iter().rev().find(|(_, root_idx)| root_idx <= kept))
+                    .map(|((_, seq), _)| *seq)
+                    .collect();
+                deleted.extend(history.drain(..).map(|(key, _)| key).filter(|(_, seq)| !retained.contains(seq)));
+            };
+            for entry in leaves.iter()? {
+                let (key, value) = entry?;
+                let key = key.value();
+                if history.last().is_some_and(|((last, _), _)| *last != key.0) {
+                    retire(&mut history);
+                }
+                history.push((key, value.value().1));
+            }
+            retire(&mut history);
+            for key in deleted {
+                leaves.remove(key)?;
+                counts.leafs_deleted += 1;
+            }
+
+            let mut batches = tx.open_table(BATCHES)?;
+            let mut kept = vec![];
+            let mut deleted = vec![];
+            for entry in batches.iter()? {
+                let (id, batch) = entry?;
+                let batch: Batch = from_json(batch.value())?;
+                if kept_roots.contains(&batch.root_idx) {
+                    kept.push((id.value(), batch));
+                } else {
+                    deleted.push(id.value());
+                }
+            }
+            for id in &deleted {
+                batches.remove(*id)?;
+                counts.batches_deleted += 1;
+            }
+            let kept_ids: HashSet<u64> = kept.iter().map(|(id, _)| *id).collect();
+            for (id, mut batch) in kept {
+                if batch.parent_id.is_some_and(|parent_id| !kept_ids.contains(&parent_id)) {
+                    batch.parent_id = None;
+                    batches.insert(id, to_json(&batch)?.as_slice())?;
+                }
+            }
+
+            let mut roots = tx.open_table(ROOTS)?;
+            let mut deleted = vec![];
+            for entry in roots.iter()? {
+                let (id, record) = entry?;
+                if !kept_roots.contains(&from_json::<RootRecord>(record.value())?.root_idx) {
+                    deleted.push(id.value());
+                }
+            }
+            for id in deleted {
+                roots.remove(id)?;
+            }
+        }
+
+        Self::reindex(&tx)?;
+        tx.commit()?;
+
+        Ok(counts)
+    }
+}
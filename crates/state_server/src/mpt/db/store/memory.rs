@@ -0,0 +1,263 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use pathfinder_crypto::Felt;
+use pathfinder_storage::StoredNode;
+
+use super::{check_head, felt_from_bytes, head_after, now, RetainCounts, RetainSelector, StoreWrite, TrieStore};
+use crate::mpt::{db::trie::RootRecord, error::Error};
+
+/// One write of a leaf: `(sequence number, value, root_idx)`.
+type LeafEntry = (u64, Felt, u64);
+
+#[derive(Debug, Clone, Copy)]
+struct Batch {
+    parent_id: Option<u64>,
+    root_idx: u64,
+    trie_root: Felt,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    nodes: BTreeMap<u64, (Felt, StoredNode)>,
+    /// First persisted node index of each hash.
+    node_hashes: HashMap<Felt, u64>,
+    /// Leaf history per key, oldest first. Keys are big endian so they iterate in key order.
+    leaves: BTreeMap<[u8; 32], Vec<LeafEntry>>,
+    leaf_seq: u64,
+    batches: BTreeMap<u64, Batch>,
+    roots: BTreeMap<u64, RootRecord>,
+    head: Option<Felt>,
+}
+
+impl MemoryState {
+    fn latest_leaf(&self, key: &[u8; 32], max_root_idx: Option<u64>) -> Option<&LeafEntry> {
+        self.leaves.get(key)?.iter().rev().find(|(_, _, root_idx)| match max_root_idx {
+            Some(max_root_idx) => *root_idx <= max_root_idx,
+            None => true,
+        })
+    }
+
+    fn root_indices(&self) -> Vec<u64> {
+        let indices: HashSet<u64> = self
+            .leaves
+            .values()
+            .flatten()
+            .map(|(_, _, root_idx)| *root_idx)
+            .chain(self.batches.values().map(|batch| batch.root_idx))
+            .chain(self.roots.values().map(|record| record.root_idx))
+            .collect();
+
+        indices.into_iter().collect()
+    }
+
+    fn index_node_hashes(&mut self) {
+        self.node_hashes.clear();
+        for (idx, (hash, _)) in &self.nodes {
+            self.node_hashes.entry(*hash).or_insert(*idx);
+        }
+    }
+}
+
+/// A trie held in process memory. Clones share the same trie.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    state: Arc<RwLock<MemoryState>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TrieStore for MemoryStore {
+    fn node(&self, idx: u64) -> Result<Option<(Felt, StoredNode)>, Error> {
+        Ok(self.state.read().unwrap().nodes.get(&idx).cloned())
+    }
+
+    fn node_idx_by_hash(&self, hash: Felt) -> Result<Option<u64>, Error> {
+        Ok(self.state.read().unwrap().node_hashes.get(&hash).copied())
+    }
+
+    fn max_node_idx(&self) -> Result<u64, Error> {
+        Ok(self.state.read().unwrap().nodes.keys().next_back().copied().unwrap_or(0))
+    }
+
+    fn leaf(&self, key: Felt, max_root_idx: Option<u64>) -> Result<Option<Felt>, Error> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .latest_leaf(&key.to_be_bytes(), max_root_idx)
+            .map(|(_, value, _)| *value))
+    }
+
+    fn leaf_values(&self, key: Felt, max_root_idx: u64) -> Result<Vec<Felt>, Error> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .leaves
+            .get(&key.to_be_bytes())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .rev()
+                    .filter(|(_, _, root_idx)| *root_idx <= max_root_idx)
+                    .map(|(_, value, _)| *value)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn leaf_keys(&self) -> Result<Vec<Felt>, Error> {
+        self.state.read().unwrap().leaves.keys().map(|key| felt_from_bytes(key)).collect()
+    }
+
+    fn batch_id_by_root(&self, trie_root: Felt) -> Result<Option<u64>, Error> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .batches
+            .iter()
+            .rev()
+            .find(|(_, batch)| batch.trie_root == trie_root)
+            .map(|(id, _)| *id))
+    }
+
+    fn roots(&self, after_id: u64, limit: usize, tag: Option<&str>) -> Result<Vec<RootRecord>, Error> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .roots
+            .range(after_id + 1..)
+            .map(|(_, record)| record)
+            .filter(|record| tag.is_none() || record.tag.as_deref() == tag)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn root_records(&self, trie_root: Felt) -> Result<Vec<RootRecord>, Error> {
+        Ok(self
+            .state
+            .read()
+            .unwrap()
+            .roots
+            .values()
+            .filter(|record| record.trie_root == trie_root)
+            .cloned()
+            .collect())
+    }
+
+    fn head(&self) -> Result<Option<Felt>, Error> {
+        Ok(self.state.read().unwrap().head)
+    }
+
+    fn swap_head(&self, expected: Option<Felt>, head: Option<Felt>) -> Result<(), Error> {
+        let mut state = self.state.write().unwrap();
+        check_head(state.head, expected)?;
+        state.head = head;
+        Ok(())
+    }
+
+    fn write(&self, write: &StoreWrite) -> Result<Option<u64>, Error> {
+        let mut state = self.state.write().unwrap();
+        state.head = head_after(state.head, write)?;
+
+        for (idx, hash, node) in &write.nodes {
+            if !state.nodes.contains_key(idx) {
+                state.nodes.insert(*idx, (*hash, node.clone()));
+                state.node_hashes.entry(*hash).or_insert(*idx);
+            }
+        }
+
+        for (key, value) in &write.leaves {
+            let key = key.to_be_bytes();
+            if state.latest_leaf(&key, None).map(|(_, latest, _)| latest) != Some(value) {
+                state.leaf_seq += 1;
+                let seq = state.leaf_seq;
+                state.leaves.entry(key).or_default().push((seq, *value, write.root_idx));
+            }
+        }
+
+        let batch_id = write.batch.map(|batch| {
+            let id = state.batches.keys().next_back().map_or(1, |id| id + 1);
+            state.batches.insert(
+                id,
+                Batch {
+                    parent_id: batch.parent_id,
+                    root_idx: write.root_idx,
+                    trie_root: write.trie_root,
+                },
+            );
+            id
+        });
+
+        let recorded: Vec<RootRecord> = state
+            .roots
+            .values()
+            .filter(|record| record.trie_root == write.trie_root)
+            .cloned()
+            .collect();
+        if let Some(root) = write.root.as_ref().filter(|root| !write.skips_root(root, &recorded)) {
+            let id = state.roots.keys().next_back().map_or(1, |id| id + 1);
+            state.roots.insert(
+                id,
+                RootRecord {
+                    id,
+                    trie_root: write.trie_root,
+                    root_idx: write.root_idx,
+                    parent_root: root.parent_root,
+                    created_at: now(),
+                    tag: root.tag.clone(),
+                },
+            );
+        }
+
+        Ok(batch_id)
+    }
+
+    fn retain(&self, select: &mut RetainSelector) -> Result<RetainCounts, Error> {
+        let mut state = self.state.write().unwrap();
+        let mut counts = RetainCounts::default();
+
+        let nodes = state.nodes.iter().map(|(idx, (hash, node))| (*idx, *hash, node.clone())).collect();
+        let (reachable, kept_roots) = select(nodes, state.root_indices())?;
+
+        let nodes = state.nodes.len();
+        state.nodes.retain(|idx, _| reachable.contains(idx));
+        counts.nodes_deleted = (nodes - state.nodes.len()) as u64;
+        state.index_node_hashes();
+
+        // A retained root reads, per key, the latest leaf entry persisted at or before it
+        for history in state.leaves.values_mut() {
+            let retained: HashSet<u64> = kept_roots
+                .iter()
+                .filter_map(|kept| history.iter().rev().find(|(_, _, root_idx)| root_idx <= kept))
+                .map(|(seq, ..)| *seq)
+                .collect();
+            let entries = history.len();
+            history.retain(|(seq, ..)| retained.contains(seq));
+            counts.leafs_deleted += (entries - history.len()) as u64;
+        }
+        state.leaves.retain(|_, history| !history.is_empty());
+
+        let batches = state.batches.len();
+        state.batches.retain(|_, batch| kept_roots.contains(&batch.root_idx));
+        counts.batches_deleted = (batches - state.batches.len()) as u64;
+        let batch_ids: HashSet<u64> = state.batches.keys().copied().collect();
+        for batch in state.batches.values_mut() {
+            batch.parent_id = batch.parent_id.filter(|parent_id| batch_ids.contains(parent_id));
+        }
+
+        state.roots.retain(|_, record| kept_roots.contains(&record.root_idx));
+
+        Ok(counts)
+    }
+}
@@ -0,0 +1,198 @@
+use std::{collections::HashSet, fmt::Debug};
+
+use clap::ValueEnum;
+use pathfinder_crypto::Felt;
+use pathfinder_storage::StoredNode;
+
+use crate::mpt::{db::trie::RootRecord, error::Error};
+
+pub mod kv;
+pub mod memory;
+pub mod sqlite;
+
+/// The storage engine holding the nodes and leaves of each trie.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StoreBackend {
+    /// One SQLite database file per trie label.
+    #[default]
+    Sqlite,
+    /// Process memory only, lost on restart.
+    Memory,
+    /// One redb key-value database file per trie label.
+    Redb,
+}
+
+/// A root recorded along with a write.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RootInsert {
+    /// The root the change was applied to, `None` if unknown.
+    pub parent_root: Option<Felt>,
+    pub tag: Option<String>,
+}
+
+/// A batch recorded along with a write.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchInsert {
+    /// The batch that produced the root the writes were applied to, if any.
+    pub parent_id: Option<u64>,
+}
+
+/// Everything a single trie update persists, applied atomically by [`TrieStore::write`].
+#[derive(Debug)]
+pub struct StoreWrite {
+    /// The trie root produced by the update.
+    pub trie_root: Felt,
+    /// The node index of `trie_root`.
+    pub root_idx: u64,
+    /// New nodes as `(index, hash, node)`. Nodes whose index is already stored are skipped.
+    pub nodes: Vec<(u64, Felt, StoredNode)>,
+    /// Leaves as `(key, value)`, associated with `root_idx`. Leaves whose value is already the latest one
+    /// stored for their key are skipped, zero values are deletion tombstones.
+    pub leaves: Vec<(Felt, Felt)>,
+    pub batch: Option<BatchInsert>,
+    pub root: Option<RootInsert>,
+    /// The root the update was applied to. If the trie tracks a head, the write fails with
+    /// [`Error::HeadConflict`] unless the head is this root, and advances the head to `trie_root`.
+    pub head: Option<Felt>,
+}
+
+impl StoreWrite {
+    pub fn new(trie_root: Felt, root_idx: u64) -> Self {
+        Self {
+            trie_root,
+            root_idx,
+            nodes: vec![],
+            leaves: vec![],
+            batch: None,
+            root: None,
+            head: None,
+        }
+    }
+
+    /// Records `trie_root` as derived from `parent_root`, unless that derivation is already recorded or
+    /// the write leaves the root unchanged.
+    pub fn with_root(mut self, parent_root: Option<Felt>, tag: Option<String>) -> Self {
+        self.root = Some(RootInsert { parent_root, tag });
+        self
+    }
+
+    /// Records the write as a batch building on `parent_id`.
+    pub fn with_batch(mut self, parent_id: Option<u64>) -> Self {
+        self.batch = Some(BatchInsert { parent_id });
+        self
+    }
+
+    /// Checks the write against the tracked head, if any, see [`StoreWrite::head`].
+    pub fn with_head(mut self, parent_root: Felt) -> Self {
+        self.head = Some(parent_root);
+        self
+    }
+
+    /// Whether the root record should be skipped because `trie_root` is already recorded with the same parent.
+    pub(crate) fn skips_root(&self, root: &RootInsert, recorded: &[RootRecord]) -> bool {
+        root.parent_root == Some(self.trie_root) || recorded.iter().any(|record| record.parent_root == root.parent_root)
+    }
+}
+
+/// Picks the nodes to keep and the root indices to retain, given every stored node as `(index, hash, node)` and
+/// every root index referenced by leaves, batches and root records.
+pub type RetainSelector<'a> = dyn FnMut(Vec<(u64, Felt, StoredNode)>, Vec<u64>) -> Result<(HashSet<u64>, Vec<u64>), Error> + 'a;
+
+/// Number of rows a [`TrieStore::retain`] removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetainCounts {
+    pub nodes_deleted: u64,
+    pub leafs_deleted: u64,
+    pub batches_deleted: u64,
+}
+
+/// Node and leaf storage of a single trie label.
+///
+/// Nodes are addressed by their trie index. Leaves keep their full history: each write appends a
+/// `(key, value, root_idx)` entry, and a leaf is read as of a root by taking the latest entry persisted
+/// at or before that root's index.
+pub trait TrieStore: Debug + Send {
+    /// Retrieves the hash and node stored at `idx`.
+    fn node(&self, idx: u64) -> Result<Option<(Felt, StoredNode)>, Error>;
+
+    /// Retrieves the index of the first persisted node with the given hash.
+    fn node_idx_by_hash(&self, hash: Felt) -> Result<Option<u64>, Error>;
+
+    /// Retrieves the highest stored node index, `0` if the trie is empty.
+    fn max_node_idx(&self) -> Result<u64, Error>;
+
+    /// Retrieves the latest value written for `key`, or the latest one persisted at or before
+    /// `max_root_idx` if set. Deleted keys return their zero tombstone.
+    fn leaf(&self, key: Felt, max_root_idx: Option<u64>) -> Result<Option<Felt>, Error>;
+
+    /// Retrieves every value written for `key` at or before `max_root_idx`, newest first. Includes zero tombstones.
+    fn leaf_values(&self, key: Felt, max_root_idx: u64) -> Result<Vec<Felt>, Error>;
+
+    /// Retrieves every key that has been written, in ascending order. Includes keys that were later deleted.
+    fn leaf_keys(&self) -> Result<Vec<Felt>, Error>;
+
+    /// Retrieves the id of the latest batch that produced `trie_root`.
+    fn batch_id_by_root(&self, trie_root: Felt) -> Result<Option<u64>, Error>;
+
+    /// Retrieves the recorded roots with an id above `after_id` in insertion order, filtered by `tag` if set.
+    fn roots(&self, after_id: u64, limit: usize, tag: Option<&str>) -> Result<Vec<RootRecord>, Error>;
+
+    /// Retrieves every record of `trie_root` in insertion order.
+    fn root_records(&self, trie_root: Felt) -> Result<Vec<RootRecord>, Error>;
+
+    /// Retrieves the tracked head root, `None` if the trie does not track one.
+    fn head(&self) -> Result<Option<Felt>, Error>;
+
+    /// Sets the tracked head to `head` if it is still `expected`, failing with [`Error::HeadConflict`] otherwise.
+    /// A `None` head stops tracking.
+    fn swap_head(&self, expected: Option<Felt>, head: Option<Felt>) -> Result<(), Error>;
+
+    /// Applies `write` atomically.
+    ///
+    /// # Returns
+    ///
+    /// The id of the recorded batch, if any.
+    fn write(&self, write: &StoreWrite) -> Result<Option<u64>, Error>;
+
+    /// Lets `select` pick the nodes to keep and the roots to retain, then deletes every other node, every leaf
+    /// entry no retained root reads, and every batch and root record of a root that is not retained.
+    /// Runs atomically with respect to writes and compacts the storage afterwards if supported.
+    fn retain(&self, select: &mut RetainSelector) -> Result<RetainCounts, Error>;
+}
+
+/// Fails with [`Error::HeadConflict`] unless the tracked head is `expected`.
+pub(crate) fn check_head(head: Option<Felt>, expected: Option<Felt>) -> Result<(), Error> {
+    if head != expected {
+        return Err(Error::HeadConflict(head));
+    }
+    Ok(())
+}
+
+/// The tracked head once `write` is applied, failing with [`Error::HeadConflict`] if the write does not build on it.
+pub(crate) fn head_after(head: Option<Felt>, write: &StoreWrite) -> Result<Option<Felt>, Error> {
+    match (head, write.head) {
+        (Some(head), Some(parent_root)) => {
+            check_head(Some(head), Some(parent_root))?;
+            Ok(Some(write.trie_root))
+        }
+        (head, _) => Ok(head),
+    }
+}
+
+/// Seconds since the Unix epoch, used as the creation time of root records.
+pub(crate) fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Encodes a node into its stored representation.
+pub(crate) fn encode_node(node: &StoredNode) -> Result<Vec<u8>, Error> {
+    let mut write_buffer = [0u8; 256];
+    let length = node.encode(&mut write_buffer)?;
+    Ok(write_buffer[..length].to_vec())
+}
+
+pub(crate) fn felt_from_bytes(bytes: &[u8]) -> Result<Felt, Error> {
+    Felt::from_be_slice(bytes).map_err(|e| Error::MptDecodeError(e.to_string()))
+}
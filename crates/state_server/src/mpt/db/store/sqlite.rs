@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+
+use pathfinder_crypto::Felt;
+use pathfinder_storage::StoredNode;
+use r2d2::PooledConnection;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction, TransactionBehavior};
+
+use super::{check_head, encode_node, felt_from_bytes, head_after, now, RetainCounts, RetainSelector, StoreWrite, TrieStore};
+use crate::mpt::{db::trie::RootRecord, error::Error};
+
+/// Creates the tables of a trie database and the indices its lookups rely on.
+pub fn create_tables_if_not_exists(conn: &Connection) -> Result<(), Error> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS trie_nodes (
+            idx INTEGER PRIMARY KEY,
+            hash BLOB NOT NULL,
+            data BLOB,
+            trie_idx INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS leafs (
+            idx INTEGER PRIMARY KEY,
+            key BLOB NOT NULL,
+            value BLOB NOT NULL,
+            root_idx INTEGER NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS roots (
+            id INTEGER PRIMARY KEY,
+            trie_root BLOB NOT NULL,
+            root_idx INTEGER NOT NULL,
+            parent_root BLOB,
+            created_at INTEGER NOT NULL,
+            tag TEXT
+        );
+        CREATE TABLE IF NOT EXISTS batches (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            status TEXT NOT NULL,
+            root_idx INTEGER NOT NULL,
+            trie_root BLOB NOT NULL,
+            FOREIGN KEY (parent_id) REFERENCES batches(id)
+        );
+        CREATE TABLE IF NOT EXISTS head (
+            id INTEGER PRIMARY KEY CHECK (id = 0),
+            trie_root BLOB NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS trie_nodes_trie_idx ON trie_nodes (trie_idx);
+        CREATE INDEX IF NOT EXISTS trie_nodes_hash ON trie_nodes (hash);
+        CREATE INDEX IF NOT EXISTS leafs_key ON leafs (key, idx);
+        CREATE INDEX IF NOT EXISTS roots_trie_root ON roots (trie_root);
+        CREATE INDEX IF NOT EXISTS batches_trie_root ON batches (trie_root);",
+    )?;
+
+    Ok(())
+}
+
+fn read_head(conn: &Connection) -> Result<Option<Felt>, Error> {
+    let head: Option<Vec<u8>> = conn
+        .query_row("SELECT trie_root FROM head WHERE id = 0", [], |row| row.get(0))
+        .optional()?;
+
+    head.map(|head| felt_from_bytes(&head)).transpose()
+}
+
+fn write_head(conn: &Connection, head: Option<Felt>) -> Result<(), Error> {
+    match head {
+        Some(head) => conn.execute(
+            "INSERT OR REPLACE INTO head (id, trie_root) VALUES (0, ?)",
+            params![head.to_be_bytes().to_vec()],
+        )?,
+        None => conn.execute("DELETE FROM head", [])?,
+    };
+
+    Ok(())
+}
+
+fn root_record_from_row(row: &Row) -> rusqlite::Result<RootRecord> {
+    let felt = |bytes: Vec<u8>| Felt::from_be_slice(&bytes).unwrap();
+
+    Ok(RootRecord {
+        id: row.get(0)?,
+        trie_root: felt(row.get(1)?),
+        root_idx: row.get(2)?,
+        parent_root: row.get::<_, Option<Vec<u8>>>(3)?.map(felt),
+        created_at: row.get(4)?,
+        tag: row.get(5)?,
+    })
+}
+
+/// A trie stored in a SQLite database, accessed through a pooled connection.
+#[derive(Debug)]
+pub struct SqliteStore {
+    conn: PooledConnection<SqliteConnectionManager>,
+}
+
+impl SqliteStore {
+    pub fn new(conn: PooledConnection<SqliteConnectionManager>) -> Self {
+        Self { conn }
+    }
+
+    fn nodes(&self) -> Result<Vec<(u64, Felt, StoredNode)>, Error> {
+        let mut stmt = self.conn.prepare("SELECT trie_idx, hash, data FROM trie_nodes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, u64>(0)?, row.get::<_, Vec<u8>>(1)?, row.get::<_, Vec<u8>>(2)?))
+        })?;
+
+        let mut nodes = vec![];
+        for row in rows {
+            let (idx, hash, data) = row?;
+            nodes.push((idx, felt_from_bytes(&hash)?, StoredNode::decode(&data)?));
+        }
+
+        Ok(nodes)
+    }
+
+    fn root_indices(&self) -> Result<Vec<u64>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT root_idx FROM leafs UNION SELECT root_idx FROM batches UNION SELECT root_idx FROM roots")?;
+        let indices = stmt.query_map([], |row| row.get::<_, u64>(0))?;
+
+        Ok(indices.collect::<Result<_, _>>()?)
+    }
+}
+
+impl TrieStore for SqliteStore {
+    fn node(&self, idx: u64) -> Result<Option<(Felt, StoredNode)>, Error> {
+        let mut stmt = self.conn.prepare_cached("SELECT hash, data FROM trie_nodes WHERE trie_idx = ?")?;
+
+        let Some((hash, data)) = stmt
+            .query_row(params![idx], |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?)))
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some((felt_from_bytes(&hash)?, StoredNode::decode(&data)?)))
+    }
+
+    fn node_idx_by_hash(&self, hash: Felt) -> Result<Option<u64>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT trie_idx FROM trie_nodes WHERE hash = ? ORDER BY idx LIMIT 1")?;
+
+        Ok(stmt.query_row(params![hash.to_be_bytes().to_vec()], |row| row.get(0)).optional()?)
+    }
+
+    fn max_node_idx(&self) -> Result<u64, Error> {
+        let mut stmt = self.conn.prepare_cached("SELECT MAX(trie_idx) FROM trie_nodes")?;
+        let trie_idx: Option<u64> = stmt.query_row([], |row| row.get(0))?;
+
+        Ok(trie_idx.unwrap_or(0))
+    }
+
+    fn leaf(&self, key: Felt, max_root_idx: Option<u64>) -> Result<Option<Felt>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT value FROM leafs WHERE key = ?1 AND (?2 IS NULL OR root_idx <= ?2) ORDER BY idx DESC LIMIT 1")?;
+
+        let Some(value): Option<Vec<u8>> = stmt
+            .query_row(params![key.to_be_bytes().to_vec(), max_root_idx], |row| row.get(0))
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(felt_from_bytes(&value)?))
+    }
+
+    fn leaf_values(&self, key: Felt, max_root_idx: u64) -> Result<Vec<Felt>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT value FROM leafs WHERE key = ?1 AND root_idx <= ?2 ORDER BY idx DESC")?;
+        let values = stmt.query_map(params![key.to_be_bytes().to_vec(), max_root_idx], |row| row.get::<_, Vec<u8>>(0))?;
+
+        values.map(|value| felt_from_bytes(&value?)).collect()
+    }
+
+    fn leaf_keys(&self) -> Result<Vec<Felt>, Error> {
+        let mut stmt = self.conn.prepare_cached("SELECT DISTINCT key FROM leafs ORDER BY key")?;
+        let keys = stmt.query_map([], |row| row.get::<_, Vec<u8>>(0))?;
+
+        keys.map(|key| felt_from_bytes(&key?)).collect()
+    }
+
+    fn batch_id_by_root(&self, trie_root: Felt) -> Result<Option<u64>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT id FROM batches WHERE trie_root = ? ORDER BY id DESC LIMIT 1")?;
+
+        Ok(stmt
+            .query_row(params![trie_root.to_be_bytes().to_vec()], |row| row.get(0))
+            .optional()?)
+    }
+
+    fn roots(&self, after_id: u64, limit: usize, tag: Option<&str>) -> Result<Vec<RootRecord>, Error> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, trie_root, root_idx, parent_root, created_at, tag FROM roots
+            WHERE id > ?1 AND (?2 IS NULL OR tag = ?2) ORDER BY id LIMIT ?3",
+        )?;
+        let roots = stmt.query_map(params![after_id, tag, limit as u64], root_record_from_row)?;
+
+        Ok(roots.collect::<Result<_, _>>()?)
+    }
+
+    fn root_records(&self, trie_root: Felt) -> Result<Vec<RootRecord>, Error> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT id, trie_root, root_idx, parent_root, created_at, tag FROM roots WHERE trie_root = ? ORDER BY id")?;
+        let roots = stmt.query_map(params![trie_root.to_be_bytes().to_vec()], root_record_from_row)?;
+
+        Ok(roots.collect::<Result<_, _>>()?)
+    }
+
+    fn head(&self) -> Result<Option<Felt>, Error> {
+        read_head(&self.conn)
+    }
+
+    fn swap_head(&self, expected: Option<Felt>, head: Option<Felt>) -> Result<(), Error> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        check_head(read_head(&tx)?, expected)?;
+        write_head(&tx, head)?;
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn write(&self, write: &StoreWrite) -> Result<Option<u64>, Error> {
+        // Existence checks are folded into the inserts and served by the indices, and the whole write
+        // is a single transaction rather than one implicit transaction per row
+        const INSERT_NODE: &str = "INSERT INTO trie_nodes (hash, data, trie_idx) SELECT ?1, ?2, ?3
+            WHERE NOT EXISTS (SELECT 1 FROM trie_nodes WHERE trie_idx = ?3)";
+        // Compare against the latest value only, so a key can be set again after a delete or an overwrite
+        const INSERT_LEAF: &str = "INSERT INTO leafs (key, value, root_idx) SELECT ?1, ?2, ?3
+            WHERE ?2 IS NOT (SELECT value FROM leafs WHERE key = ?1 ORDER BY idx DESC LIMIT 1)";
+        const INSERT_BATCH: &str = "INSERT INTO batches (parent_id, status, root_idx, trie_root) VALUES (?1, 'committed', ?2, ?3)";
+        const INSERT_ROOT: &str = "INSERT INTO roots (trie_root, root_idx, parent_root, created_at, tag)
+            SELECT ?1, ?2, ?3, ?4, ?5
+            WHERE NOT EXISTS (SELECT 1 FROM roots WHERE trie_root = ?1 AND parent_root IS ?3)";
+
+        // Take the write lock upfront so the head cannot move between the check and the commit
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let trie_root = write.trie_root.to_be_bytes().to_vec();
+        let head = read_head(&tx)?;
+        let next_head = head_after(head, write)?;
+
+        {
+            let mut insert_node = tx.prepare_cached(INSERT_NODE)?;
+            for (idx, hash, node) in &write.nodes {
+                insert_node.execute(params![hash.to_be_bytes().to_vec(), encode_node(node)?, idx])?;
+            }
+
+            let mut insert_leaf = tx.prepare_cached(INSERT_LEAF)?;
+            for (key, value) in &write.leaves {
+                insert_leaf.execute(params![key.to_be_bytes().to_vec(), value.to_be_bytes().to_vec(), write.root_idx])?;
+            }
+        }
+
+        let batch_id = match write.batch {
+            Some(batch) => {
+                tx.execute(INSERT_BATCH, params![batch.parent_id, write.root_idx, &trie_root])?;
+                Some(tx.last_insert_rowid() as u64)
+            }
+            None => None,
+        };
+
+        if let Some(root) = write.root.as_ref().filter(|root| root.parent_root != Some(write.trie_root)) {
+            tx.execute(
+                INSERT_ROOT,
+                params![
+                    &trie_root,
+                    write.root_idx,
+                    root.parent_root.map(|root| root.to_be_bytes().to_vec()),
+                    now(),
+                    root.tag
+                ],
+            )?;
+        }
+
+        if next_head != head {
+            write_head(&tx, next_head)?;
+        }
+
+        tx.commit()?;
+
+        Ok(batch_id)
+    }
+
+    fn retain(&self, select: &mut RetainSelector) -> Result<RetainCounts, Error> {
+        let conn = &self.conn;
+        let tx = conn.unchecked_transaction()?;
+        let mut counts = RetainCounts::default();
+
+        let nodes = self.nodes()?;
+        let indices: HashSet<u64> = nodes.iter().map(|(idx, ..)| *idx).collect();
+        let (reachable, kept_roots) = select(nodes, self.root_indices()?)?;
+        {
+            let mut delete_node = conn.prepare_cached("DELETE FROM trie_nodes WHERE trie_idx = ?")?;
+            for idx in indices.iter().filter(|idx| !reachable.contains(idx)) {
+                counts.nodes_deleted += delete_node.execute(params![idx])? as u64;
+            }
+        }
+
+        conn.execute("CREATE TEMP TABLE IF NOT EXISTS retained_roots (idx INTEGER PRIMARY KEY)", [])?;
+        conn.execute("CREATE TEMP TABLE IF NOT EXISTS retained_leafs (idx INTEGER PRIMARY KEY)", [])?;
+        conn.execute("DELETE FROM retained_roots", [])?;
+        conn.execute("DELETE FROM retained_leafs", [])?;
+        {
+            // A retained root reads, per key, the latest leaf row persisted at or before it
+            let mut retain_root = conn.prepare_cached("INSERT OR IGNORE INTO retained_roots (idx) VALUES (?)")?;
+            let mut retain_leafs =
+                conn.prepare_cached("INSERT OR IGNORE INTO retained_leafs SELECT MAX(idx) FROM leafs WHERE root_idx <= ? GROUP BY key")?;
+            for root_idx in &kept_roots {
+                retain_root.execute(params![root_idx])?;
+                retain_leafs.execute(params![root_idx])?;
+            }
+        }
+
+        counts.leafs_deleted = conn.execute("DELETE FROM leafs WHERE idx NOT IN (SELECT idx FROM retained_leafs)", [])? as u64;
+        counts.batches_deleted = conn.execute("DELETE FROM batches WHERE root_idx NOT IN (SELECT idx FROM retained_roots)", [])? as u64;
+        conn.execute(
+            "UPDATE batches SET parent_id = NULL WHERE parent_id IS NOT NULL AND parent_id NOT IN (SELECT id FROM batches)",
+            [],
+        )?;
+        conn.execute("DELETE FROM roots WHERE root_idx NOT IN (SELECT idx FROM retained_roots)", [])?;
+
+        conn.execute("DROP TABLE retained_roots", [])?;
+        conn.execute("DROP TABLE retained_leafs", [])?;
+        tx.commit()?;
+        conn.execute("VACUUM", [])?;
+
+        Ok(counts)
+    }
+}
@@ -2,17 +2,31 @@ use bitvec::{order::Msb0, slice::BitSlice};
 use pathfinder_crypto::Felt;
 use pathfinder_merkle_tree::storage::Storage;
 use pathfinder_storage::{StoredNode, TrieStorageIndex};
-use r2d2::PooledConnection;
-use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
 use types::proofs::injected_state::leaf::TrieLeaf;
 
-use crate::mpt::error::Error;
+use crate::mpt::{
+    db::store::{StoreWrite, TrieStore},
+    error::Error,
+};
+
+/// A root recorded for a trie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RootRecord {
+    pub id: u64,
+    pub trie_root: Felt,
+    pub root_idx: u64,
+    /// The root the change was applied to, `None` for imported roots.
+    pub parent_root: Option<Felt>,
+    /// Unix timestamp in seconds.
+    pub created_at: u64,
+    pub tag: Option<String>,
+}
 
 /// Represents a Trie database.
 #[derive(Debug, Clone, Copy)]
 pub struct TrieDB<'a> {
-    conn: &'a PooledConnection<SqliteConnectionManager>,
+    store: &'a dyn TrieStore,
     pub max_root_idx: u64,
 }
 
@@ -21,84 +35,65 @@ impl<'a> TrieDB<'a> {
     ///
     /// # Arguments
     ///
-    /// * `conn` - A reference to a pooled SQLite connection.
-    pub fn new(conn: &'a PooledConnection<SqliteConnectionManager>) -> Self {
-        Self { conn, max_root_idx: 0 }
+    /// * `store` - The storage of the trie.
+    pub fn new(store: &'a dyn TrieStore) -> Self {
+        Self { store, max_root_idx: 0 }
     }
 
-    /// Persists the leaves in the database, skipping any (key, value) pairs that already exist.
-    ///
-    /// # Arguments
-    ///
-    /// * `leaves` - A vector of `TrieLeaf` representing the leaves to be persisted.
-    /// * `root_idx` - The trie root index to associate with these leaves.
+    /// Persists the nodes, leaves, batch and root record of a trie update in a single atomic write.
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns a `Error` if there was an error persisting the leaves.
-    pub fn persist_leafs(&self, leaves: &Vec<TrieLeaf>, root_idx: u64) -> Result<(), Error> {
-        const SELECT_QUERY: &str = "SELECT 1 FROM leafs WHERE key = ?1 AND value = ?2";
-        const INSERT_QUERY: &str = "INSERT INTO leafs (key, value, root_idx) VALUES (?1, ?2, ?3)";
-
-        for item in leaves {
-            let key_bytes = item.get_key().to_be_bytes().to_vec();
-            let value_bytes = item.data.value.to_be_bytes().to_vec();
-
-            // Check if the (key, value) pair already exists
-            let mut stmt = self.conn.prepare_cached(SELECT_QUERY)?;
-            let exists: Option<u8> = stmt.query_row(params![&key_bytes, &value_bytes], |row| row.get(0)).optional()?;
-
-            if exists.is_none() {
-                self.conn
-                    .execute(INSERT_QUERY, params![&key_bytes, &value_bytes, root_idx])
-                    .map_err(Error::from)?;
-            }
-        }
-
-        Ok(())
+    /// Returns the id of the recorded batch, if any.
+    pub fn write(&self, write: &StoreWrite) -> Result<Option<u64>, Error> {
+        self.store.write(write)
     }
 
-    pub fn delete_leaf(&self, key: Felt) -> Result<(), Error> {
-        const DELETE_QUERY: &str = "DELETE FROM leafs WHERE key = ?";
-        self.conn
-            .execute(DELETE_QUERY, params![key.to_be_bytes().to_vec()])
-            .map_err(Error::from)?;
-        Ok(())
+    /// Retrieves the id of the latest batch that produced the given trie root.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` if the root was not produced by a batch.
+    pub fn get_batch_id_by_root(&self, trie_root: Felt) -> Result<Option<u64>, Error> {
+        self.store.batch_id_by_root(trie_root)
     }
 
-    /// Persists the nodes in the database.
+    /// Retrieves the recorded roots in insertion order.
     ///
     /// # Arguments
     ///
-    /// * `nodes` - A vector of tuples representing the nodes to be persisted. Each tuple contains a `StoredNode`, a `Felt` hash, and a trie
-    ///   index.
+    /// * `after_id` - Only roots recorded after this id are returned.
+    /// * `limit` - The maximum number of roots to return.
+    /// * `tag` - Only roots with this tag are returned, if set.
+    pub fn get_roots(&self, after_id: u64, limit: usize, tag: Option<&str>) -> Result<Vec<RootRecord>, Error> {
+        self.store.roots(after_id, limit, tag)
+    }
+
+    /// Retrieves the tracked head root, `None` if the trie does not track one.
+    pub fn get_head(&self) -> Result<Option<Felt>, Error> {
+        self.store.head()
+    }
+
+    /// Moves the tracked head from `expected` to `head`, a `None` head disabling tracking.
     ///
     /// # Errors
     ///
-    /// Returns a `Error` if there was an error persisting the nodes.
-    pub fn persist_nodes(&self, nodes: Vec<(StoredNode, Felt, u64)>) -> Result<(), Error> {
-        // We'll check for existence before inserting to avoid duplicates.
-        const SELECT_QUERY: &str = "SELECT 1 FROM trie_nodes WHERE trie_idx = ?";
-        const INSERT_QUERY: &str = "INSERT INTO trie_nodes (hash, data, trie_idx) VALUES (?1, ?2, ?3)";
-        let mut write_buffer = [0u8; 256];
-        for (node, hash, trie_idx) in nodes {
-            let length = node.encode(&mut write_buffer)?;
-            let hash_bytes = hash.to_be_bytes().to_vec();
-            let data_bytes = write_buffer[..length].to_vec();
-
-            // Check if a node with the same trie_idx already exists
-            let mut stmt = self.conn.prepare_cached(SELECT_QUERY)?;
-            let exists: Option<u8> = stmt.query_row(params![trie_idx], |row| row.get(0)).optional()?;
-
-            if exists.is_none() {
-                // Only insert if not already present
-                self.conn
-                    .execute(INSERT_QUERY, params![&hash_bytes, &data_bytes, trie_idx])
-                    .map_err(Error::from)?;
-            }
+    /// Returns `Error::HeadConflict` with the current head if it is not `expected`, and
+    /// `Error::MissingNodeIndex` if `head` is neither stored nor the empty root.
+    pub fn swap_head(&self, expected: Option<Felt>, head: Option<Felt>) -> Result<(), Error> {
+        if let Some(head) = head.filter(|head| *head != Felt::ZERO) {
+            self.get_node_idx_by_hash(head)?;
         }
+        self.store.swap_head(expected, head)
+    }
 
-        Ok(())
+    /// Retrieves the first record of the given root, i.e. the change that created it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` if the root was never recorded.
+    pub fn get_root_record(&self, trie_root: Felt) -> Result<Option<RootRecord>, Error> {
+        Ok(self.store.root_records(trie_root)?.into_iter().next())
     }
 
     /// Retrieves the maximum trie index from the database.
@@ -107,14 +102,7 @@ impl<'a> TrieDB<'a> {
     ///
     /// Returns a `Error` if there was an error retrieving the trie index.
     pub fn get_node_idx(&self) -> Result<u64, Error> {
-        let mut stmt = self.conn.prepare_cached("SELECT MAX(trie_idx) FROM trie_nodes")?;
-
-        let trie_idx: Option<u64> = stmt
-            .query_row([], |row| row.get::<_, Option<u64>>(0))
-            .optional()? // Using optional to handle no rows found situation gracefully
-            .flatten(); // Flatten to convert Option<Option<u64>> to Option<u64>
-
-        Ok(trie_idx.map_or(0, |idx| idx))
+        self.store.max_node_idx()
     }
 
     /// Retrieves the trie index for a given node hash.
@@ -125,17 +113,9 @@ impl<'a> TrieDB<'a> {
     ///
     /// # Errors
     ///
-    /// Returns an `Error` if there was a database error.
-    ///
-    /// # Returns
-    ///
-    /// Returns `Ok(Some(index))` if the hash is found, `Ok(None)` otherwise.
+    /// Returns `Error::MissingNodeIndex` if no node has this hash.
     pub fn get_node_idx_by_hash(&self, hash: Felt) -> Result<u64, Error> {
-        let mut stmt = self.conn.prepare_cached("SELECT trie_idx FROM trie_nodes WHERE hash = ?")?;
-
-        let index: u64 = stmt.query_row(params![hash.to_be_bytes().to_vec()], |row| row.get(0))?;
-
-        Ok(index)
+        self.store.node_idx_by_hash(hash)?.ok_or(Error::MissingNodeIndex)
     }
 
     /// Retrieves the node hash for a given trie index.
@@ -152,20 +132,7 @@ impl<'a> TrieDB<'a> {
     ///
     /// Returns `Ok(Some(hash))` if the index is found, `Ok(None)` otherwise.
     pub fn get_node_hash_by_idx(&self, idx: u64) -> Result<Option<Felt>, Error> {
-        let mut stmt = self.conn.prepare_cached("SELECT hash FROM trie_nodes WHERE trie_idx = ?")?;
-
-        let hash_bytes: Option<Vec<u8>> = stmt
-            .query_row(params![idx], |row| row.get(0))
-            .optional() // Handle case where index is not found
-            .map_err(Error::from)?; // Convert rusqlite error to custom Error
-
-        match hash_bytes {
-            Some(bytes) => {
-                let hash = Felt::from_be_slice(&bytes).unwrap();
-                Ok(Some(hash))
-            }
-            None => Ok(None),
-        }
+        Ok(self.store.node(idx)?.map(|(hash, _)| hash))
     }
 
     /// Retrieves the leaf at the given key. This is used to get the latest leaf for a given key.
@@ -178,21 +145,23 @@ impl<'a> TrieDB<'a> {
     ///
     /// Returns `Ok(leaf)` if the leaf is found, `Ok(TrieLeaf::empty(key))` otherwise.
     pub fn get_leaf(&self, key: Felt) -> anyhow::Result<TrieLeaf> {
-        let mut stmt = self.conn.prepare_cached("SELECT value FROM leafs WHERE key = ?")?;
-        let result: Option<TrieLeaf> = stmt
-            .query_row(params![key.to_be_bytes().to_vec()], |row| {
-                let value: Vec<u8> = row.get(0)?;
-                let value = Felt::from_be_slice(&value).unwrap();
-                Ok(TrieLeaf::new(key, value))
-            })
-            .optional()?;
-
-        match result {
-            Some(leaf) => Ok(leaf),
+        match self.store.leaf(key, None)? {
+            Some(value) => Ok(TrieLeaf::new(key, value)),
             None => Ok(TrieLeaf::empty(key)),
         }
     }
 
+    /// Retrieves every key that has been written to the trie, in ascending order.
+    /// Includes keys that were later deleted.
+    pub fn get_leaf_keys(&self) -> Result<Vec<Felt>, Error> {
+        self.store.leaf_keys()
+    }
+
+    /// Retrieves every value written for `key` at or before `max_root_idx`, newest first.
+    pub fn get_leaf_values(&self, key: Felt, max_root_idx: u64) -> Result<Vec<Felt>, Error> {
+        self.store.leaf_values(key, max_root_idx)
+    }
+
     /// Retrieves the leaf at the given key and root index.
     /// This is used to get the leaf at a specific trie checkpoint.
     ///
@@ -203,26 +172,18 @@ impl<'a> TrieDB<'a> {
     ///
     /// # Returns
     ///
-    /// Returns `Ok(leaf)` if the leaf is found, `Ok(TrieLeaf::empty(key))` otherwise.
+    /// Returns `Ok(Some(leaf))` if the leaf is found, `Ok(None)` if it is missing or was deleted.
     pub fn get_leaf_at(&self, key: Felt, max_root_idx: u64) -> anyhow::Result<Option<TrieLeaf>> {
-        let mut stmt = self
-            .conn
-            .prepare_cached("SELECT value FROM leafs WHERE key = ? AND root_idx <= ? ORDER BY idx DESC LIMIT 1")?;
+        let result = self.store.leaf(key, Some(max_root_idx))?.map(|value| {
+            let leaf = TrieLeaf::new(key, value);
 
-        let result: Option<TrieLeaf> = stmt
-            .query_row(params![key.to_be_bytes().to_vec(), max_root_idx], |row| {
-                let value: Vec<u8> = row.get(0)?;
-                let value = Felt::from_be_slice(&value).unwrap();
+            assert!(leaf.commitment() == value, "Value mismatch");
 
-                let leaf = TrieLeaf::new(key, value);
+            leaf
+        });
 
-                assert!(leaf.commitment() == value, "Value mismatch");
-
-                Ok(leaf)
-            })
-            .optional()?;
-
-        Ok(result)
+        // Deleted keys are stored as zero valued tombstones
+        Ok(result.filter(|leaf| leaf.data.value != Felt::ZERO))
     }
 }
 
@@ -238,15 +199,7 @@ impl Storage for TrieDB<'_> {
     /// Returns `Ok(None)` if no node is found at the specified index.
     /// Otherwise, returns `Ok(Some(node))` where `node` is the retrieved stored node.
     fn get(&self, index: TrieStorageIndex) -> anyhow::Result<Option<StoredNode>> {
-        let mut stmt = self.conn.prepare_cached("SELECT data FROM trie_nodes WHERE trie_idx = ?")?;
-
-        let Some(data): Option<Vec<u8>> = stmt.query_row(params![&index.0], |row| row.get(0)).optional()? else {
-            return Ok(None);
-        };
-
-        let node = StoredNode::decode(&data)?;
-
-        Ok(Some(node))
+        Ok(self.store.node(index.0)?.map(|(_, node)| node))
     }
 
     /// Retrieves the hash value of the stored node at the specified index from the trie database.
@@ -260,18 +213,11 @@ impl Storage for TrieDB<'_> {
     /// Returns `Ok(None)` if no node is found at the specified index.
     /// Otherwise, returns `Ok(Some(hash))` where `hash` is the retrieved hash value.
     fn hash(&self, index: TrieStorageIndex) -> anyhow::Result<Option<Felt>> {
-        let mut stmt = self.conn.prepare_cached("SELECT hash FROM trie_nodes WHERE trie_idx = ?")?;
-
-        let Some(data): Option<Vec<u8>> = stmt.query_row(params![&index.0], |row| row.get(0)).optional()? else {
-            return Ok(None);
-        };
-
-        Ok(Some(Felt::from_be_slice(&data)?))
+        Ok(self.store.node(index.0)?.map(|(hash, _)| hash))
     }
 
     /// Retrieves the leaf value associated with the specified path from the trie database.
-    /// This version retrieves the *latest* version of the leaf based on insertion order,
-    /// assuming an auto-incrementing primary key 'id' exists in the 'leafs' table.
+    /// This version retrieves the *latest* version of the leaf based on write order.
     /// Note: This method is used by the merkle tree implementation and should get the latest leaf.
     ///
     /// # Arguments
@@ -289,19 +235,6 @@ impl Storage for TrieDB<'_> {
                 .map(|opt| opt.map(|leaf| leaf.data.value));
         }
 
-        let mut stmt = self
-            .conn
-            .prepare_cached("SELECT value FROM leafs WHERE key = ? ORDER BY idx DESC LIMIT 1")?;
-
-        // Execute the query
-        let Some(data): Option<Vec<u8>> = stmt
-            .query_row(params![Felt::from_bits(path)?.to_be_bytes().to_vec()], |row| row.get(0))
-            .optional()?
-        else {
-            // No leaf found for this key
-            return Ok(None);
-        };
-
-        Ok(Some(Felt::from_be_slice(&data)?))
+        Ok(self.store.leaf(Felt::from_bits(path)?, None)?)
     }
 }
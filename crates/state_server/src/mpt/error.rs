@@ -1,6 +1,12 @@
-use axum::response::{IntoResponse, Response};
+use axum::{
+    http::header::WWW_AUTHENTICATE,
+    response::{IntoResponse, Response},
+    Json,
+};
+use pathfinder_crypto::Felt;
 use pathfinder_merkle_tree::tree::GetProofError;
 use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::error;
 
@@ -34,17 +40,72 @@ pub enum Error {
     HexParsing(#[from] hex::FromHexError),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("Missing or unknown API key")]
+    Unauthorized,
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Stale trie root, the head is {}", .0.map_or("unset".to_string(), |head| head.to_string()))]
+    HeadConflict(Option<Felt>),
     #[error("Pool creation error: {0}")]
     Pool(r2d2::Error),
+    #[error("Key-value store error: {0}")]
+    Kv(#[from] redb::Error),
     #[error(transparent)]
     Any(#[from] anyhow::Error),
 }
 
+/// The redb operations fail with their own error types, all of which convert into `redb::Error`.
+macro_rules! impl_from_kv_error {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(error: $error) -> Self {
+                    Error::Kv(error.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_kv_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError
+);
+
+/// Body of a `409 Conflict`, carrying the head to rebase on.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeadConflictResponse {
+    pub error: String,
+    pub head: Option<Felt>,
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         error!("Mpt error: {}", self);
         match self {
             Error::MissingNodeIndex => StatusCode::NOT_FOUND.into_response(),
+            Error::LeafNotFound => (StatusCode::NOT_FOUND, Error::LeafNotFound.to_string()).into_response(),
+            Error::InvalidRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            Error::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                [(WWW_AUTHENTICATE, "Bearer")],
+                Error::Unauthorized.to_string(),
+            )
+                .into_response(),
+            Error::Forbidden(message) => (StatusCode::FORBIDDEN, message).into_response(),
+            Error::HeadConflict(head) => (
+                StatusCode::CONFLICT,
+                Json(HeadConflictResponse {
+                    error: Error::HeadConflict(head).to_string(),
+                    head,
+                }),
+            )
+                .into_response(),
             error => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
         }
     }
@@ -1,4 +1,6 @@
 pub mod db;
 pub mod error;
 pub mod hash;
+pub mod prune;
+pub mod snapshot;
 pub mod trie;
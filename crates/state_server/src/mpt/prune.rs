@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+
+use pathfinder_crypto::Felt;
+use pathfinder_storage::StoredNode;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::mpt::{db::store::TrieStore, error::Error};
+
+/// Which trie roots survive a prune.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetainPolicy {
+    /// Keep the given roots. The empty root `0x0` is always available and may be listed.
+    Roots(Vec<Felt>),
+    /// Keep the `n` most recently persisted roots.
+    Last(usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneStats {
+    pub roots_kept: Vec<Felt>,
+    pub nodes_deleted: u64,
+    pub leafs_deleted: u64,
+    pub batches_deleted: u64,
+}
+
+/// Deletes every trie node that no retained root can reach, along with the leaf entries, batches and
+/// root records only pruned roots depended on. Runs atomically and compacts the storage afterwards if supported.
+///
+/// A root is either a node that no other node references or a node some write recorded as its
+/// resulting root. Roots are ordered by their node index, which follows the order in which they
+/// were persisted. The tracked head, if any, is always retained.
+pub fn prune(store: &dyn TrieStore, policy: &RetainPolicy) -> Result<PruneStats, Error> {
+    let head = store.head()?.filter(|head| *head != Felt::ZERO);
+    let mut roots_kept = vec![];
+    let counts = store.retain(&mut |nodes, root_indices| {
+        let nodes: HashMap<u64, (Felt, StoredNode)> = nodes.into_iter().map(|(idx, hash, node)| (idx, (hash, node))).collect();
+        let referenced: HashSet<u64> = nodes.values().flat_map(|(_, node)| children(node)).collect();
+        let mut roots: Vec<u64> = nodes.keys().filter(|idx| !referenced.contains(idx)).copied().collect();
+        roots.extend(root_indices);
+        roots.retain(|idx| nodes.contains_key(idx));
+        roots.sort_unstable();
+        roots.dedup();
+
+        let mut kept_roots: Vec<u64> = match policy {
+            RetainPolicy::Roots(hashes) => {
+                let mut kept = vec![];
+                for hash in hashes.iter().filter(|hash| **hash != Felt::ZERO) {
+                    let matching: Vec<u64> = roots.iter().filter(|idx| nodes[*idx].0 == *hash).copied().collect();
+                    if matching.is_empty() {
+                        return Err(Error::MissingNodeIndex);
+                    }
+                    kept.extend(matching);
+                }
+                kept
+            }
+            RetainPolicy::Last(n) => roots.iter().rev().take(*n).copied().collect(),
+        };
+        if let Some(head) = head.filter(|head| !kept_roots.iter().any(|idx| nodes[idx].0 == *head)) {
+            kept_roots.extend(roots.iter().filter(|idx| nodes[**idx].0 == head));
+        }
+
+        // Mark every node reachable from a retained root
+        let mut reachable: HashSet<u64> = HashSet::new();
+        let mut stack = kept_roots.clone();
+        while let Some(idx) = stack.pop() {
+            if !reachable.insert(idx) {
+                continue;
+            }
+            if let Some((_, node)) = nodes.get(&idx) {
+                stack.extend(children(node));
+            }
+        }
+
+        roots_kept = kept_roots.iter().map(|idx| nodes[idx].0).collect();
+        Ok((reachable, kept_roots))
+    })?;
+
+    let stats = PruneStats {
+        roots_kept,
+        nodes_deleted: counts.nodes_deleted,
+        leafs_deleted: counts.leafs_deleted,
+        batches_deleted: counts.batches_deleted,
+    };
+
+    debug!(
+        "pruned {} nodes, {} leafs and {} batches, kept {} roots",
+        stats.nodes_deleted,
+        stats.leafs_deleted,
+        stats.batches_deleted,
+        stats.roots_kept.len()
+    );
+
+    Ok(stats)
+}
+
+fn children(node: &StoredNode) -> Vec<u64> {
+    match node {
+        StoredNode::Binary { left, right } => vec![left.0, right.0],
+        StoredNode::Edge { child, .. } => vec![child.0],
+        StoredNode::LeafBinary | StoredNode::LeafEdge { .. } => vec![],
+    }
+}
@@ -0,0 +1,282 @@
+use std::{
+    io::{BufRead, Write},
+    ops::Bound,
+};
+
+use pathfinder_crypto::Felt;
+use pathfinder_storage::TrieStorageIndex;
+use serde::{Deserialize, Serialize};
+use types::proofs::injected_state::leaf::TrieLeaf;
+
+use crate::mpt::{
+    db::{
+        store::{StoreWrite, TrieStore},
+        trie::TrieDB,
+    },
+    error::Error,
+    trie::Trie,
+};
+
+pub const SNAPSHOT_VERSION: u64 = 1;
+
+/// Number of leaves walked per export page and committed per import chunk, matching the largest `/leaves` page.
+pub const SNAPSHOT_CHUNK_SIZE: usize = 1000;
+
+/// First line of a snapshot file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    pub version: u64,
+    pub trie_label: Felt,
+    pub trie_root: Felt,
+}
+
+impl SnapshotHeader {
+    pub fn new(trie_label: Felt, trie_root: Felt) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            trie_label,
+            trie_root,
+        }
+    }
+
+    /// Parses the header from the first line of `lines`.
+    pub fn read(lines: &mut impl Iterator<Item = Result<String, Error>>) -> Result<Self, Error> {
+        let line = lines.next().ok_or(Error::InvalidRequest("empty snapshot".to_string()))??;
+        let header: Self = serde_json::from_str(&line).map_err(|e| Error::InvalidRequest(format!("invalid snapshot header: {}", e)))?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(Error::InvalidRequest(format!("unsupported snapshot version {}", header.version)));
+        }
+        Ok(header)
+    }
+}
+
+/// One line per leaf following the header, ordered by key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotLeaf {
+    pub key: Felt,
+    pub value: Felt,
+}
+
+impl SnapshotLeaf {
+    /// Parses the `index`-th leaf line, which must follow the leaf with key `previous`.
+    fn parse(line: &str, index: usize, previous: Option<Felt>) -> Result<Self, Error> {
+        let leaf: Self =
+            serde_json::from_str(line).map_err(|e| Error::InvalidRequest(format!("invalid snapshot leaf {}: {}", index, e)))?;
+        if leaf.value == Felt::ZERO {
+            return Err(Error::InvalidRequest(format!("snapshot leaf {} has a zero value", leaf.key)));
+        }
+        if previous.is_some_and(|previous| leaf.key <= previous) {
+            return Err(Error::InvalidRequest(format!(
+                "snapshot key {} is duplicated or out of order",
+                leaf.key
+            )));
+        }
+        Ok(leaf)
+    }
+}
+
+impl From<TrieLeaf> for SnapshotLeaf {
+    fn from(leaf: TrieLeaf) -> Self {
+        Self {
+            key: leaf.key,
+            value: leaf.data.value,
+        }
+    }
+}
+
+/// The set of leaves of a trie under a given root, serialized as JSON lines:
+///
+/// ```text
+/// {"version":1,"trie_label":"0x123","trie_root":"0xabc..."}
+/// {"key":"0x1","value":"0xa"}
+/// {"key":"0x2","value":"0x14"}
+/// ```
+///
+/// Snapshots are exported and imported a page of leaves at a time, this type holds a whole one in memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub header: SnapshotHeader,
+    pub leaves: Vec<SnapshotLeaf>,
+}
+
+impl Snapshot {
+    pub fn to_jsonl(&self) -> Result<String, Error> {
+        let mut out = vec![];
+        write_line(&mut out, &self.header)?;
+        for leaf in &self.leaves {
+            write_line(&mut out, leaf)?;
+        }
+        String::from_utf8(out).map_err(|e| Error::Any(e.into()))
+    }
+
+    pub fn from_jsonl(input: &str) -> Result<Self, Error> {
+        let mut lines = read_lines(input.as_bytes());
+        let header = SnapshotHeader::read(&mut lines)?;
+
+        let mut leaves: Vec<SnapshotLeaf> = vec![];
+        for (index, line) in lines.enumerate() {
+            leaves.push(SnapshotLeaf::parse(&line?, index + 1, leaves.last().map(|leaf| leaf.key))?);
+        }
+
+        Ok(Self { header, leaves })
+    }
+}
+
+/// Returns the non-empty lines of `reader`.
+pub fn read_lines(reader: impl BufRead) -> impl Iterator<Item = Result<String, Error>> {
+    reader
+        .lines()
+        .map(|line| line.map_err(Error::from))
+        .filter(|line| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+}
+
+/// Writes `value` to `writer` as one JSON line.
+pub fn write_line(mut writer: impl Write, value: &impl Serialize) -> Result<(), Error> {
+    serde_json::to_writer(&mut writer, value).map_err(anyhow::Error::from)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Walks up to `limit` leaves committed to by `trie_root` with a key above `cursor`.
+///
+/// # Returns
+///
+/// The leaves in ascending key order, and the cursor of the next page if more leaves follow.
+pub fn export_page(
+    store: &dyn TrieStore,
+    trie_root: Felt,
+    cursor: Option<Felt>,
+    limit: usize,
+) -> Result<(Vec<SnapshotLeaf>, Option<Felt>), Error> {
+    let storage = TrieDB::new(store);
+    let start = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+
+    // Walk one extra leaf to tell whether another page follows
+    let mut leaves = Trie::get_leaves(&storage, trie_root, (start, Bound::Unbounded), limit + 1)?;
+    let next_cursor = if leaves.len() > limit {
+        leaves.truncate(limit);
+        leaves.last().map(|leaf| leaf.key)
+    } else {
+        None
+    };
+
+    Ok((leaves.into_iter().map(SnapshotLeaf::from).collect(), next_cursor))
+}
+
+/// Writes a snapshot of every leaf committed to by `trie_root` to `writer`, one page at a time.
+///
+/// # Returns
+///
+/// The number of exported leaves.
+pub fn export(store: &dyn TrieStore, trie_label: Felt, trie_root: Felt, mut writer: impl Write) -> Result<u64, Error> {
+    write_line(&mut writer, &SnapshotHeader::new(trie_label, trie_root))?;
+
+    let (mut exported, mut cursor) = (0, None);
+    loop {
+        let (leaves, next_cursor) = export_page(store, trie_root, cursor, SNAPSHOT_CHUNK_SIZE)?;
+        for leaf in &leaves {
+            write_line(&mut writer, leaf)?;
+        }
+        exported += leaves.len() as u64;
+
+        match next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+    writer.flush()?;
+
+    Ok(exported)
+}
+
+/// A snapshot imported by [`import`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedSnapshot {
+    /// The node index of the imported root.
+    pub trie_id: TrieStorageIndex,
+    pub leaves: u64,
+}
+
+/// Rebuilds the trie of `header` from the leaf lines following it, committing and persisting the leaves a chunk at a
+/// time. The root is only recorded, without a parent, once the rebuilt root matches the one of the header: the nodes
+/// of a rejected snapshot are persisted but no recorded root reaches them.
+pub fn import(
+    store: &dyn TrieStore,
+    header: &SnapshotHeader,
+    lines: impl Iterator<Item = Result<String, Error>>,
+    tag: Option<String>,
+) -> Result<ImportedSnapshot, Error> {
+    let mut import = Import {
+        trie_root: Felt::ZERO,
+        root_idx: None,
+        last_key: None,
+        leaves: 0,
+    };
+    let mut chunk = Vec::with_capacity(SNAPSHOT_CHUNK_SIZE);
+    for (index, line) in lines.enumerate() {
+        let leaf = SnapshotLeaf::parse(&line?, index + 1, import.last_key)?;
+        import.last_key = Some(leaf.key);
+        chunk.push(TrieLeaf::new(leaf.key, leaf.value));
+
+        if chunk.len() == SNAPSHOT_CHUNK_SIZE {
+            import.apply(store, &chunk)?;
+            chunk.clear();
+        }
+    }
+    if !chunk.is_empty() {
+        import.apply(store, &chunk)?;
+    }
+
+    if import.trie_root != header.trie_root {
+        return Err(Error::InvalidRequest(format!(
+            "snapshot root mismatch: expected {}, rebuilt {}",
+            header.trie_root, import.trie_root
+        )));
+    }
+
+    let Some(root_idx) = import.root_idx else {
+        return Ok(ImportedSnapshot {
+            trie_id: TrieStorageIndex::from(0),
+            leaves: 0,
+        });
+    };
+    TrieDB::new(store).write(&StoreWrite::new(import.trie_root, u64::from(root_idx)).with_root(None, tag))?;
+
+    Ok(ImportedSnapshot {
+        trie_id: root_idx,
+        leaves: import.leaves,
+    })
+}
+
+/// The trie rebuilt so far by [`import`].
+struct Import {
+    trie_root: Felt,
+    root_idx: Option<TrieStorageIndex>,
+    last_key: Option<Felt>,
+    leaves: u64,
+}
+
+impl Import {
+    /// Sets `leaves` on top of the trie rebuilt so far and persists the resulting nodes and leaves.
+    fn apply(&mut self, store: &dyn TrieStore, leaves: &[TrieLeaf]) -> Result<(), Error> {
+        let (storage, mut trie) = match self.root_idx {
+            Some(root_idx) => Trie::load(root_idx, store),
+            None => {
+                let (storage, trie, _) = Trie::create_empty(store)?;
+                (storage, trie)
+            }
+        };
+        for leaf in leaves {
+            trie.set(&storage, leaf.get_path(), leaf.data.value)?;
+        }
+
+        let update = trie.commit(&storage)?;
+        let write = Trie::prepare_updates(&storage, &update, leaves, None)?;
+        storage.write(&write)?;
+
+        self.trie_root = update.root_commitment;
+        self.root_idx = Some(TrieStorageIndex::from(write.root_idx));
+        self.leaves += leaves.len() as u64;
+        Ok(())
+    }
+}
@@ -1,17 +1,24 @@
-use bitvec::{order::Msb0, slice::BitSlice};
+use std::ops::{Bound, RangeBounds};
+
+use bitvec::{order::Msb0, slice::BitSlice, vec::BitVec};
 use pathfinder_common::{hash::TruncatedKeccakHash, trie::TrieNode};
 use pathfinder_crypto::Felt;
 use pathfinder_merkle_tree::{
     merkle_node::Direction,
+    storage::Storage,
     tree::{MerkleTree, TrieNodeWithHash},
 };
 use pathfinder_storage::{Node, NodeRef, StoredNode, TrieStorageIndex, TrieUpdate};
-use r2d2::PooledConnection;
-use r2d2_sqlite::SqliteConnectionManager;
 use tracing::debug;
 use types::proofs::injected_state::leaf::TrieLeaf;
 
-use crate::mpt::{db::trie::TrieDB, error::Error};
+use crate::mpt::{
+    db::{
+        store::{StoreWrite, TrieStore},
+        trie::TrieDB,
+    },
+    error::Error,
+};
 
 pub struct Trie {}
 
@@ -23,32 +30,30 @@ pub enum Membership {
 
 /// The Trie struct represents a Merkle Trie data structure.
 impl Trie {
-    /// Loads a Trie from the given root index and database connection.
+    /// Loads a Trie from the given root index and store.
     ///
     /// # Arguments
     ///
     /// * `root_idx` - The root index of the Trie.
-    /// * `conn` - The database connection.
+    /// * `store` - The storage of the trie.
     ///
     /// # Returns
     ///
     /// A new Trie instance.
     #[allow(dead_code)]
-    pub fn load(
-        root_idx: TrieStorageIndex,
-        conn: &PooledConnection<SqliteConnectionManager>,
-    ) -> (TrieDB, MerkleTree<TruncatedKeccakHash, 251>) {
-        let storage = TrieDB::new(conn);
+    pub fn load(root_idx: TrieStorageIndex, store: &dyn TrieStore) -> (TrieDB, MerkleTree<TruncatedKeccakHash, 251>) {
+        let storage = TrieDB::new(store);
         let trie = MerkleTree::<TruncatedKeccakHash, 251>::new(root_idx);
 
         (storage, trie)
     }
 
-    pub fn load_from_root<'a>(
+    /// Loads the Trie whose root node has the given hash, failing with `Error::MissingNodeIndex` if it is not stored.
+    pub fn load_from_root(
         root: Felt,
-        conn: &'a PooledConnection<SqliteConnectionManager>,
-    ) -> Result<(TrieDB<'a>, MerkleTree<TruncatedKeccakHash, 251>, TrieStorageIndex), Error> {
-        let storage = TrieDB::new(conn);
+        store: &dyn TrieStore,
+    ) -> Result<(TrieDB, MerkleTree<TruncatedKeccakHash, 251>, TrieStorageIndex), Error> {
+        let storage = TrieDB::new(store);
         let root_idx_u64 = storage.get_node_idx_by_hash(root)?;
         let root_idx = TrieStorageIndex::from(root_idx_u64);
         let trie = MerkleTree::<TruncatedKeccakHash, 251>::new(root_idx);
@@ -60,15 +65,13 @@ impl Trie {
     ///
     /// # Arguments
     ///
-    /// * `conn` - The database connection.
+    /// * `store` - The storage of the trie.
     ///
     /// # Returns
     ///
     /// A new empty Trie instance with storage, trie, and root index.
-    pub fn create_empty(
-        conn: &PooledConnection<SqliteConnectionManager>,
-    ) -> Result<(TrieDB, MerkleTree<TruncatedKeccakHash, 251>, TrieStorageIndex), Error> {
-        let storage = TrieDB::new(conn);
+    pub fn create_empty(store: &dyn TrieStore) -> Result<(TrieDB, MerkleTree<TruncatedKeccakHash, 251>, TrieStorageIndex), Error> {
+        let storage = TrieDB::new(store);
         let trie = MerkleTree::<TruncatedKeccakHash, 251>::empty();
         let root_idx = TrieStorageIndex::from(0);
 
@@ -89,6 +92,160 @@ impl Trie {
         MerkleTree::<TruncatedKeccakHash, 251>::get_proof(root_idx.into(), storage, &key_bits).map_err(Error::GetProof)
     }
 
+    /// Walks the trie nodes under `root` in ascending key order and returns up to `limit` leaves whose key lies
+    /// within `range`. Subtrees that cannot hold a key in the range are not visited.
+    ///
+    /// Leaf values are taken from the leaf nodes reached: of the values ever written for a key, the one the node
+    /// hash commits to is returned, so roots of forked histories read their own values. Every visited node is
+    /// checked to hash to the hash its parent commits to, so the leaves returned are the ones `root` commits to.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The TrieDB.
+    /// * `root` - The root to walk from.
+    /// * `range` - The key bounds.
+    /// * `limit` - The maximum number of leaves to return.
+    pub fn get_leaves(storage: &TrieDB, root: Felt, range: (Bound<Felt>, Bound<Felt>), limit: usize) -> Result<Vec<TrieLeaf>, Error> {
+        const HEIGHT: usize = 251;
+
+        let mut leaves = vec![];
+        if root == Felt::ZERO || limit == 0 {
+            return Ok(leaves);
+        }
+
+        let root_idx = storage.get_node_idx_by_hash(root)?;
+        let node_hash = |index: TrieStorageIndex| storage.get_node_hash_by_idx(index.0)?.ok_or(Error::MissingNodeIndex);
+        let check_hash = |node: TrieNode, hash: Felt| {
+            let node_hash = node.hash::<TruncatedKeccakHash>();
+            if node_hash == hash {
+                Ok(())
+            } else {
+                Err(Error::MptDecodeError(format!("node hashes to {} instead of {}", node_hash, hash)))
+            }
+        };
+        let to_key = |path: &BitSlice<u8, Msb0>| Felt::from_bits(path).map_err(|e| Error::MptDecodeError(e.to_string()));
+
+        let in_range = |prefix: &BitVec<u8, Msb0>| -> Result<bool, Error> {
+            let bound = |fill: bool| {
+                let mut path = prefix.clone();
+                path.resize(HEIGHT, fill);
+                Felt::from_bits(&path).map_err(|e| Error::MptDecodeError(e.to_string()))
+            };
+            let (min, max) = (bound(false)?, bound(true)?);
+            let above_start = match range.0 {
+                Bound::Included(start) => max >= start,
+                Bound::Excluded(start) => max > start,
+                Bound::Unbounded => true,
+            };
+            let below_end = match range.1 {
+                Bound::Included(end) => min <= end,
+                Bound::Excluded(end) => min < end,
+                Bound::Unbounded => true,
+            };
+            Ok(above_start && below_end)
+        };
+
+        // Depth first, pushing the right child first so the left one is visited first
+        let mut stack: Vec<(TrieStorageIndex, BitVec<u8, Msb0>, Felt)> = vec![(TrieStorageIndex::from(root_idx), BitVec::new(), root)];
+        while let Some((index, prefix, hash)) = stack.pop() {
+            if leaves.len() == limit {
+                break;
+            }
+            if !in_range(&prefix)? {
+                continue;
+            }
+
+            let node = storage.get(index)?.ok_or(Error::MissingNodeIndex)?;
+            let mut found: Vec<(Felt, Felt)> = vec![];
+            match node {
+                StoredNode::Binary { left, right } => {
+                    let (left_hash, right_hash) = (node_hash(left)?, node_hash(right)?);
+                    check_hash(
+                        TrieNode::Binary {
+                            left: left_hash,
+                            right: right_hash,
+                        },
+                        hash,
+                    )?;
+                    for (child, bit, child_hash) in [(right, true, right_hash), (left, false, left_hash)] {
+                        let mut path = prefix.clone();
+                        path.push(bit);
+                        stack.push((child, path, child_hash));
+                    }
+                }
+                StoredNode::Edge { child, path } => {
+                    let child_hash = node_hash(child)?;
+                    check_hash(
+                        TrieNode::Edge {
+                            child: child_hash,
+                            path: path.clone(),
+                        },
+                        hash,
+                    )?;
+                    let mut child_prefix = prefix.clone();
+                    child_prefix.extend_from_bitslice(&path);
+                    stack.push((child, child_prefix, child_hash));
+                }
+                StoredNode::LeafBinary if prefix.len() == HEIGHT - 1 => {
+                    let (mut left_path, mut right_path) = (prefix.clone(), prefix.clone());
+                    left_path.push(false);
+                    right_path.push(true);
+                    let (left_key, right_key) = (to_key(&left_path)?, to_key(&right_path)?);
+
+                    let rights = storage.get_leaf_values(right_key, root_idx)?;
+                    let (left, right) = storage
+                        .get_leaf_values(left_key, root_idx)?
+                        .into_iter()
+                        .flat_map(|left| rights.iter().map(move |right| (left, *right)))
+                        .find(|(left, right)| {
+                            TrieNode::Binary {
+                                left: *left,
+                                right: *right,
+                            }
+                            .hash::<TruncatedKeccakHash>()
+                                == hash
+                        })
+                        .ok_or_else(|| {
+                            Error::MptDecodeError(format!(
+                                "no stored values of leaves {} and {} match node {}",
+                                left_key, right_key, hash
+                            ))
+                        })?;
+                    found.extend([(left_key, left), (right_key, right)]);
+                }
+                StoredNode::LeafEdge { path } => {
+                    let mut leaf_path = prefix.clone();
+                    leaf_path.extend_from_bitslice(&path);
+                    let key = to_key(&leaf_path)?;
+
+                    let value = storage
+                        .get_leaf_values(key, root_idx)?
+                        .into_iter()
+                        .find(|value| {
+                            TrieNode::Edge {
+                                child: *value,
+                                path: path.clone(),
+                            }
+                            .hash::<TruncatedKeccakHash>()
+                                == hash
+                        })
+                        .ok_or_else(|| Error::MptDecodeError(format!("no stored value of leaf {} matches node {}", key, hash)))?;
+                    found.push((key, value));
+                }
+                // A placeholder root for a root commitment without nodes
+                StoredNode::LeafBinary => {}
+            }
+
+            for (key, value) in found {
+                if leaves.len() < limit && range.contains(&key) {
+                    leaves.push(TrieLeaf::new(key, value));
+                }
+            }
+        }
+
+        Ok(leaves)
+    }
+
     // TODO this should return result not option
     pub fn verify_proof(proof: &[TrieNodeWithHash], root: Felt, leaf: TrieLeaf) -> Option<Membership> {
         let key = leaf.get_path();
@@ -149,7 +306,7 @@ impl Trie {
         }
     }
 
-    /// Persists batch items and corresponding nodes to the TrieDB.
+    /// Collects the nodes and leaves of a trie update into a write, without persisting anything.
     ///
     /// # Arguments
     ///
@@ -160,16 +317,19 @@ impl Trie {
     ///
     /// # Returns
     ///
-    /// A Result containing the root node index or an error.
-    pub fn persist_updates(
+    /// A Result containing the write, whose `root_idx` is the root node index, or an error.
+    pub fn prepare_updates(
         storage: &TrieDB,
         update: &TrieUpdate,
-        items: &Vec<TrieLeaf>,
+        items: &[TrieLeaf],
         starting_index: Option<u64>,
-    ) -> Result<TrieStorageIndex, Error> {
-        let next_index = starting_index.unwrap_or_else(|| storage.get_node_idx().unwrap()) + 1;
-        let mut nodes_to_persist: Vec<(StoredNode, Felt, u64)> = vec![];
-        let mut root_index: Option<TrieStorageIndex> = None;
+    ) -> Result<StoreWrite, Error> {
+        let next_index = match starting_index {
+            Some(index) => index,
+            None => storage.get_node_idx()?,
+        } + 1;
+        let mut nodes_to_persist: Vec<(u64, Felt, StoredNode)> = vec![];
+        let mut root_index: Option<u64> = None;
 
         // Insert new nodes into storage
         for (rel_index, (hash, node)) in update.nodes_added.iter().enumerate() {
@@ -200,33 +360,55 @@ impl Trie {
             };
 
             let index = next_index + (rel_index as u64);
-            nodes_to_persist.push((node, *hash, index));
+            nodes_to_persist.push((index, *hash, node));
 
             // Track the root node index by matching the hash
             if *hash == update.root_commitment {
-                root_index = Some(TrieStorageIndex::from(index));
+                root_index = Some(index);
             }
         }
 
-        storage.persist_nodes(nodes_to_persist)?;
-
-        // Determine the final root index before persisting leaves
+        // Determine the final root index the leaves are associated with
         let final_root_idx = if let Some(root_idx) = root_index {
             root_idx
-        } else if let Ok(idx) = storage.get_node_idx_by_hash(update.root_commitment).map(TrieStorageIndex::from) {
+        } else if let Ok(idx) = storage.get_node_idx_by_hash(update.root_commitment) {
             idx
         } else {
             // Fallback: create a placeholder root node so that subsequent look-ups succeed.
-            let empty_root_node = StoredNode::LeafBinary;
-            let root_idx = storage.get_node_idx()? + 1; // next available index after persistence
-            let nodes_for_empty_root = vec![(empty_root_node, update.root_commitment, root_idx)];
-            storage.persist_nodes(nodes_for_empty_root)?;
-            TrieStorageIndex::from(root_idx)
+            let max_idx = nodes_to_persist.iter().map(|(idx, ..)| *idx).max().unwrap_or(0);
+            let root_idx = storage.get_node_idx()?.max(max_idx) + 1; // next available index after persistence
+            nodes_to_persist.push((root_idx, update.root_commitment, StoredNode::LeafBinary));
+            root_idx
         };
 
-        // Now persist the leaves with the final root index
-        storage.persist_leafs(items, u64::from(final_root_idx))?;
+        let mut write = StoreWrite::new(update.root_commitment, final_root_idx);
+        write.nodes = nodes_to_persist;
+        write.leaves = items.iter().map(|item| (item.get_key(), item.data.value)).collect();
+
+        Ok(write)
+    }
+
+    /// Persists batch items and corresponding nodes to the TrieDB.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The TrieDB.
+    /// * `update` - The TrieUpdate.
+    /// * `items` - The items to be persisted.
+    /// * `starting_index` - Optional starting index to replay from. If None, uses the maximum index + 1.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the root node index or an error.
+    pub fn persist_updates(
+        storage: &TrieDB,
+        update: &TrieUpdate,
+        items: &[TrieLeaf],
+        starting_index: Option<u64>,
+    ) -> Result<TrieStorageIndex, Error> {
+        let write = Self::prepare_updates(storage, update, items, starting_index)?;
+        storage.write(&write)?;
 
-        Ok(final_root_idx)
+        Ok(TrieStorageIndex::from(write.root_idx))
     }
 }
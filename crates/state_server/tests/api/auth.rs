@@ -0,0 +1,279 @@
+use axum::{
+    body::Body,
+    http::{header::AUTHORIZATION, Request, StatusCode},
+    Router,
+};
+use pathfinder_crypto::Felt;
+use state_server::{
+    api::{proof::GetStateProofsRequest, prune::PruneRequest, write::WriteRequest},
+    auth::{ApiKey, AuthConfig, LabelPattern, API_KEY_HEADER},
+    create_router, AppState,
+};
+use tower::ServiceExt;
+
+use crate::helpers::{read_actions, write_actions};
+
+const READER: &str = "reader-token";
+const WRITER: &str = "writer-token";
+const ADMIN: &str = "admin-token";
+
+fn setup_with_auth() -> Router {
+    let label = Felt::from(0x123_u64);
+    let config = AuthConfig {
+        keys: vec![
+            ApiKey {
+                name: "reader".to_string(),
+                token: READER.to_string(),
+                read: vec![LabelPattern::Any],
+                write: vec![],
+                admin: vec![],
+            },
+            ApiKey {
+                name: "writer".to_string(),
+                token: WRITER.to_string(),
+                read: vec![],
+                write: vec![LabelPattern::Label(label)],
+                admin: vec![],
+            },
+            ApiKey {
+                name: "admin".to_string(),
+                token: ADMIN.to_string(),
+                read: vec![],
+                write: vec![],
+                admin: vec![LabelPattern::Label(label)],
+            },
+        ],
+    };
+    create_router(AppState::new_memory().unwrap().with_auth(config))
+}
+
+async fn send(router: &Router, request: Request<Body>) -> StatusCode {
+    router.clone().oneshot(request).await.unwrap().status()
+}
+
+fn read_request(trie_label: Felt) -> axum::http::request::Builder {
+    Request::builder()
+        .method("GET")
+        .uri(format!("/read?trie_label={}&trie_root=0x0&key=0x1", trie_label))
+}
+
+fn write_request(trie_label: Felt) -> (axum::http::request::Builder, Body) {
+    let body = serde_json::to_vec(&WriteRequest {
+        trie_label,
+        trie_root: Felt::ZERO,
+        key: Felt::from(1_u64),
+        value: Felt::from(10_u64),
+        tag: None,
+    })
+    .unwrap();
+    (
+        Request::builder()
+            .method("POST")
+            .uri("/write")
+            .header("content-type", "application/json"),
+        Body::from(body),
+    )
+}
+
+fn proofs_request(token: &str, request: &GetStateProofsRequest) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/get_state_proofs")
+        .header("content-type", "application/json")
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::from(serde_json::to_vec(request).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn missing_or_unknown_tokens_are_rejected() {
+    let router = setup_with_auth();
+    let label = Felt::from(0x123_u64);
+
+    assert_eq!(
+        send(&router, read_request(label).body(Body::empty()).unwrap()).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        send(
+            &router,
+            read_request(label)
+                .header(AUTHORIZATION, "Bearer unknown")
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        send(
+            &router,
+            read_request(label).header(AUTHORIZATION, READER).body(Body::empty()).unwrap()
+        )
+        .await,
+        StatusCode::UNAUTHORIZED,
+        "the bearer scheme is required in the authorization header"
+    );
+    assert_eq!(
+        send(
+            &router,
+            read_request(label)
+                .header(AUTHORIZATION, format!("Bearer {}", READER))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        send(
+            &router,
+            read_request(label).header(API_KEY_HEADER, READER).body(Body::empty()).unwrap()
+        )
+        .await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn scopes_are_enforced_per_label() {
+    let router = setup_with_auth();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+
+    let (request, body) = write_request(label);
+    assert_eq!(
+        send(&router, request.header(API_KEY_HEADER, READER).body(body).unwrap()).await,
+        StatusCode::FORBIDDEN,
+        "read scope does not allow writes"
+    );
+
+    let (request, body) = write_request(label);
+    assert_eq!(
+        send(&router, request.header(API_KEY_HEADER, WRITER).body(body).unwrap()).await,
+        StatusCode::OK
+    );
+
+    let (request, body) = write_request(other_label);
+    assert_eq!(
+        send(&router, request.header(API_KEY_HEADER, WRITER).body(body).unwrap()).await,
+        StatusCode::FORBIDDEN,
+        "write scope is limited to the listed labels"
+    );
+
+    assert_eq!(
+        send(
+            &router,
+            read_request(label).header(API_KEY_HEADER, WRITER).body(Body::empty()).unwrap()
+        )
+        .await,
+        StatusCode::OK,
+        "write scope implies read scope"
+    );
+    assert_eq!(
+        send(
+            &router,
+            read_request(other_label)
+                .header(API_KEY_HEADER, WRITER)
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn state_proofs_require_write_scope_for_write_actions() {
+    let router = setup_with_auth();
+    let label = Felt::from(0x123_u64);
+
+    let reads = GetStateProofsRequest {
+        actions: read_actions(label, Felt::ZERO, vec![Felt::from(1_u64)]),
+    };
+    let writes = GetStateProofsRequest {
+        actions: write_actions(label, Felt::ZERO, vec![(Felt::from(1_u64), Felt::from(10_u64))]),
+    };
+
+    assert_eq!(send(&router, proofs_request(READER, &reads)).await, StatusCode::OK);
+    assert_eq!(send(&router, proofs_request(READER, &writes)).await, StatusCode::FORBIDDEN);
+    assert_eq!(send(&router, proofs_request(WRITER, &writes)).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn prune_requires_admin_scope() {
+    let router = setup_with_auth();
+    let label = Felt::from(0x123_u64);
+
+    let (request, body) = write_request(label);
+    assert_eq!(
+        send(&router, request.header(API_KEY_HEADER, WRITER).body(body).unwrap()).await,
+        StatusCode::OK
+    );
+
+    let prune_request = |token: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/prune")
+            .header("content-type", "application/json")
+            .header(API_KEY_HEADER, token)
+            .body(Body::from(
+                serde_json::to_vec(&PruneRequest {
+                    trie_label: label,
+                    keep_roots: vec![],
+                    keep_last: Some(1),
+                })
+                .unwrap(),
+            ))
+            .unwrap()
+    };
+    assert_eq!(
+        send(&router, prune_request(WRITER)).await,
+        StatusCode::FORBIDDEN,
+        "write scope does not allow pruning"
+    );
+    assert_eq!(send(&router, prune_request(ADMIN)).await, StatusCode::OK);
+
+    let (request, body) = write_request(label);
+    assert_eq!(
+        send(&router, request.header(API_KEY_HEADER, ADMIN).body(body).unwrap()).await,
+        StatusCode::OK,
+        "admin scope implies write scope"
+    );
+}
+
+#[tokio::test]
+async fn auth_config_rejects_duplicate_tokens() {
+    let path = std::env::temp_dir().join(format!("state_server_auth_{}.toml", uuid::Uuid::new_v4()));
+
+    std::fs::write(
+        &path,
+        r#"
+            [[keys]]
+            name = "runner"
+            token = "secret"
+            read = ["*"]
+            write = ["0x123"]
+        "#,
+    )
+    .unwrap();
+    let config = AuthConfig::from_file(&path).unwrap();
+    assert_eq!(config.keys[0].read, vec![LabelPattern::Any]);
+    assert_eq!(config.keys[0].write, vec![LabelPattern::Label(Felt::from(0x123_u64))]);
+
+    std::fs::write(
+        &path,
+        r#"
+            [[keys]]
+            name = "a"
+            token = "secret"
+
+            [[keys]]
+            name = "b"
+            token = "secret"
+        "#,
+    )
+    .unwrap();
+    assert!(AuthConfig::from_file(&path).is_err());
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,129 @@
+use axum::Router;
+use pathfinder_crypto::Felt;
+use state_server::{
+    api::{leaves::GetLeavesRequest, roots::GetRootsRequest},
+    create_router,
+    mpt::{db::store::StoreBackend, snapshot::Snapshot, trie::Membership},
+    AppState,
+};
+
+use crate::helpers::{
+    build_trie, delete_from_trie, export_trie, get_leaves, get_roots, get_state_proofs, prune_trie, read_actions, read_from_trie,
+    temp_db_root, verify_read_proof_crypto, write_batch, write_to_trie,
+};
+
+/// Runs the same sequence of writes, reads, proofs, pruning and export against a backend.
+async fn exercise_backend(router: &Router, backend: StoreBackend) -> Felt {
+    let ctx = format!("{:?}", backend);
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let root1 = build_trie(router, label, vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]).await;
+    let batch = write_batch(router, label, root1, vec![(k2, Felt::from(21_u64)), (k3, Felt::from(30_u64))]).await;
+    let root3 = delete_from_trie(router, label, batch.trie_root, k1).await.trie_root;
+
+    assert_eq!(
+        read_from_trie(router, label, root1, k2).await.value,
+        Some(Felt::from(20_u64)),
+        "{ctx}: old root"
+    );
+    assert_eq!(
+        read_from_trie(router, label, batch.trie_root, k2).await.value,
+        Some(Felt::from(21_u64)),
+        "{ctx}: batch root"
+    );
+    assert_eq!(read_from_trie(router, label, root3, k1).await.value, None, "{ctx}: deleted key");
+
+    let proofs = get_state_proofs(router, read_actions(label, root3, vec![k1, k2, k3]))
+        .await
+        .state_proofs;
+    verify_read_proof_crypto(&proofs[0], k1, Felt::ZERO, &ctx, Some(Membership::NonMember));
+    verify_read_proof_crypto(&proofs[1], k2, Felt::from(21_u64), &ctx, Some(Membership::Member));
+    verify_read_proof_crypto(&proofs[2], k3, Felt::from(30_u64), &ctx, Some(Membership::Member));
+
+    let roots = get_roots(
+        router,
+        &GetRootsRequest {
+            trie_label: label,
+            cursor: None,
+            limit: None,
+            tag: None,
+        },
+    )
+    .await
+    .roots;
+    assert_eq!(roots.last().unwrap().trie_root, root3, "{ctx}: latest root recorded last");
+    assert_eq!(roots.last().unwrap().parent_root, Some(batch.trie_root), "{ctx}: lineage");
+
+    let stats = prune_trie(router, label, vec![root3], None).await;
+    assert_eq!(stats.roots_kept, vec![root3], "{ctx}: pruned roots");
+    assert!(stats.nodes_deleted > 0, "{ctx}: unreachable nodes must be deleted");
+
+    let leaves = get_leaves(
+        router,
+        &GetLeavesRequest {
+            trie_label: label,
+            trie_root: root3,
+            cursor: None,
+            limit: None,
+            start_key: None,
+            end_key: None,
+        },
+    )
+    .await
+    .leaves;
+    let leaves: Vec<(Felt, Felt)> = leaves.iter().map(|leaf| (leaf.key, leaf.value)).collect();
+    assert_eq!(
+        leaves,
+        vec![(k2, Felt::from(21_u64)), (k3, Felt::from(30_u64))],
+        "{ctx}: leaves after prune"
+    );
+
+    let snapshot = Snapshot::from_jsonl(&export_trie(router, label, root3).await).unwrap();
+    assert_eq!(snapshot.header.trie_root, root3, "{ctx}: exported root");
+    assert_eq!(snapshot.leaves.len(), 2, "{ctx}: exported leaves");
+
+    root3
+}
+
+#[tokio::test]
+async fn backends_produce_the_same_roots() {
+    let mut roots = vec![];
+    for backend in [StoreBackend::Sqlite, StoreBackend::Memory, StoreBackend::Redb] {
+        let db_root = temp_db_root();
+        let state = AppState::new(&db_root, backend).unwrap();
+        roots.push(exercise_backend(&create_router(state), backend).await);
+        std::fs::remove_dir_all(&db_root).ok();
+    }
+
+    assert!(roots.windows(2).all(|pair| pair[0] == pair[1]), "backends diverged: {:?}", roots);
+}
+
+#[tokio::test]
+async fn file_backends_persist_across_restarts() {
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    for backend in [StoreBackend::Sqlite, StoreBackend::Redb] {
+        let db_root = temp_db_root();
+        let router = create_router(AppState::new(&db_root, backend).unwrap());
+        let root = build_trie(&router, label, vec![(k1, Felt::from(10_u64))]).await;
+        let root = write_to_trie(&router, label, root, k2, Felt::from(20_u64)).await.trie_root;
+        drop(router);
+
+        let router = create_router(AppState::new(&db_root, backend).unwrap());
+        assert_eq!(
+            read_from_trie(&router, label, root, k1).await.value,
+            Some(Felt::from(10_u64)),
+            "{:?}: k1",
+            backend
+        );
+        assert_eq!(
+            read_from_trie(&router, label, root, k2).await.value,
+            Some(Felt::from(20_u64)),
+            "{:?}: k2",
+            backend
+        );
+        std::fs::remove_dir_all(&db_root).ok();
+    }
+}
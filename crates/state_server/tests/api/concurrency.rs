@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::Request as AxumRequest,
+    http::{Request, StatusCode},
+    middleware::{self, Next},
+};
+use pathfinder_crypto::Felt;
+use state_server::{api::write::WriteRequest, create_router, mpt::db::store::StoreBackend, AppState};
+use tokio::{sync::Notify, task::JoinSet};
+use tower::ServiceExt;
+
+use crate::helpers::{build_trie, read_from_trie, temp_db_root, write_to_trie};
+
+const LABELS: u64 = 8;
+const WRITES_PER_LABEL: u64 = 25;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_writers_on_different_labels_keep_every_write() {
+    let db_root = temp_db_root();
+    let router = create_router(AppState::new(&db_root, StoreBackend::Sqlite).unwrap());
+
+    let mut writers = JoinSet::new();
+    for label in 1..=LABELS {
+        let router = router.clone();
+        writers.spawn(async move {
+            let trie_label = Felt::from(label);
+            let mut root = Felt::ZERO;
+            for i in 0..WRITES_PER_LABEL {
+                root = write_to_trie(&router, trie_label, root, Felt::from(i), Felt::from(label * 1000 + i))
+                    .await
+                    .trie_root;
+            }
+            (label, root)
+        });
+    }
+
+    let mut roots = vec![];
+    while let Some(result) = writers.join_next().await {
+        roots.push(result.unwrap());
+    }
+    assert_eq!(roots.len(), LABELS as usize);
+
+    for (label, root) in roots {
+        for i in 0..WRITES_PER_LABEL {
+            assert_eq!(
+                read_from_trie(&router, Felt::from(label), root, Felt::from(i)).await.value,
+                Some(Felt::from(label * 1000 + i)),
+                "label {} key {}",
+                label,
+                i
+            );
+        }
+    }
+
+    std::fs::remove_dir_all(&db_root).ok();
+}
+
+// A single worker thread, so a handler blocking it on the database would stall every other request
+#[tokio::test]
+async fn a_stalled_writer_does_not_block_other_labels() {
+    let db_root = temp_db_root();
+    let state = AppState::new(&db_root, StoreBackend::Sqlite).unwrap();
+    let router = create_router(state.clone());
+    // Serves the stalled write only, signalling once it reached the server
+    let received = Arc::new(Notify::new());
+    let stalled_router = create_router(state).layer(middleware::from_fn({
+        let received = received.clone();
+        move |request: AxumRequest, next: Next| {
+            received.notify_one();
+            next.run(request)
+        }
+    }));
+    let (stalled_label, other_labels) = (Felt::from(1_u64), [Felt::from(2_u64), Felt::from(3_u64)]);
+
+    let stalled_root = build_trie(&router, stalled_label, vec![(Felt::from(1_u64), Felt::from(10_u64))]).await;
+
+    // Hold the write lock of the first trie's database from outside the server
+    let blocker = rusqlite::Connection::open(format!("{}/{}.db", db_root, stalled_label)).unwrap();
+    blocker.execute_batch("BEGIN IMMEDIATE").unwrap();
+
+    let stalled = {
+        let body = serde_json::to_vec(&WriteRequest {
+            trie_label: stalled_label,
+            trie_root: stalled_root,
+            key: Felt::from(2_u64),
+            value: Felt::from(20_u64),
+            tag: None,
+        })
+        .unwrap();
+        tokio::spawn(async move {
+            stalled_router
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/write")
+                        .header("content-type", "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+        })
+    };
+    received.notified().await;
+
+    // The stalled write can not complete before the lock is released, so these only finish if they do not wait for it
+    for label in other_labels {
+        let root = build_trie(&router, label, vec![(Felt::from(1_u64), Felt::from(10_u64))]).await;
+        write_to_trie(&router, label, root, Felt::from(2_u64), Felt::from(20_u64)).await;
+    }
+    assert!(!stalled.is_finished(), "the stalled write is still waiting");
+
+    blocker.execute_batch("COMMIT").unwrap();
+    assert_eq!(stalled.await.unwrap(), StatusCode::OK);
+
+    std::fs::remove_dir_all(&db_root).ok();
+}
@@ -0,0 +1,115 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::mpt::trie::{Membership, Trie};
+use types::proofs::injected_state::{leaf::TrieLeaf, StateProof, TrieNodeSerde};
+
+use crate::helpers::{
+    build_trie, delete_actions, delete_from_trie, delete_from_trie_raw, get_state_proofs, get_state_proofs_raw, read_from_trie, setup,
+    write_to_trie,
+};
+
+#[tokio::test]
+async fn delete_removes_key_from_trie() {
+    let (router, _) = setup().await.unwrap();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+    let (k1, k2, v1, v2) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(10_u64), Felt::from(20_u64));
+
+    let root = build_trie(&router, label, vec![(k1, v1), (k2, v2)]).await;
+    let deleted_root = delete_from_trie(&router, label, root, k1).await.trie_root;
+
+    // The resulting trie must be the one that never contained the key
+    let expected_root = build_trie(&router, other_label, vec![(k2, v2)]).await;
+    assert_eq!(deleted_root, expected_root);
+
+    assert_eq!(read_from_trie(&router, label, deleted_root, k1).await.value, None);
+    assert_eq!(read_from_trie(&router, label, deleted_root, k2).await.value, Some(v2));
+    assert_eq!(
+        read_from_trie(&router, label, root, k1).await.value,
+        Some(v1),
+        "previous root stays readable"
+    );
+}
+
+#[tokio::test]
+async fn delete_last_key_empties_trie() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k, v) = (Felt::from(1_u64), Felt::from(10_u64));
+
+    let root = write_to_trie(&router, label, Felt::ZERO, k, v).await.trie_root;
+
+    assert_eq!(delete_from_trie(&router, label, root, k).await.trie_root, Felt::ZERO);
+}
+
+#[tokio::test]
+async fn delete_missing_key_is_not_found() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    let root = write_to_trie(&router, label, Felt::ZERO, Felt::from(1_u64), Felt::from(10_u64))
+        .await
+        .trie_root;
+
+    let resp = delete_from_trie_raw(&router, label, root, Felt::from(2_u64)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = delete_from_trie_raw(&router, label, Felt::ZERO, Felt::from(1_u64)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn key_can_be_set_again_after_delete() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, v) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(10_u64));
+
+    let r1 = build_trie(&router, label, vec![(k1, v), (k2, v)]).await;
+    let r2 = delete_from_trie(&router, label, r1, k1).await.trie_root;
+    let r3 = write_to_trie(&router, label, r2, k1, v).await.trie_root;
+
+    assert_eq!(r3, r1);
+    assert_eq!(read_from_trie(&router, label, r2, k1).await.value, None);
+    assert_eq!(read_from_trie(&router, label, r3, k1).await.value, Some(v));
+}
+
+#[tokio::test]
+async fn delete_proof_proves_inclusion_then_non_inclusion() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let kv: Vec<(Felt, Felt)> = (1..=8_u64).map(|i| (Felt::from(i), Felt::from(i * 100))).collect();
+    let (key, value) = kv[3];
+
+    let root = build_trie(&router, label, kv).await;
+    let deleted_root = delete_from_trie(&router, label, root, key).await.trie_root;
+
+    let resp = get_state_proofs(&router, delete_actions(label, root, vec![key])).await;
+    let StateProof::Delete(proof) = &resp.state_proofs[0] else {
+        panic!("Expected delete proof, got {:?}", resp.state_proofs[0]);
+    };
+
+    assert_eq!((proof.trie_root_prev, proof.trie_root_post), (root, deleted_root));
+    assert_eq!(proof.leaf_prev, TrieLeaf::new(key, value));
+
+    let nodes = |proof: &[TrieNodeSerde]| proof.iter().map(|n| (n.clone().into(), Felt::ZERO)).collect::<Vec<_>>();
+    assert_eq!(
+        Trie::verify_proof(&nodes(&proof.state_proof_prev), root, proof.leaf_prev),
+        Some(Membership::Member)
+    );
+    assert_eq!(
+        Trie::verify_proof(&nodes(&proof.state_proof_post), deleted_root, TrieLeaf::empty(key)),
+        Some(Membership::NonMember)
+    );
+}
+
+#[tokio::test]
+async fn delete_proof_of_missing_key_is_not_found() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    let root = write_to_trie(&router, label, Felt::ZERO, Felt::from(1_u64), Felt::from(10_u64))
+        .await
+        .trie_root;
+
+    let resp = get_state_proofs_raw(&router, delete_actions(label, root, vec![Felt::from(2_u64)])).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,170 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::mpt::{
+    snapshot::{Snapshot, SnapshotLeaf, SNAPSHOT_CHUNK_SIZE},
+    trie::Membership,
+};
+
+use crate::helpers::{
+    build_trie, delete_from_trie, export_trie, export_trie_raw, get_state_proofs, get_trie_root_node_idx, import_trie, import_trie_raw,
+    read_actions, read_from_trie, setup, verify_read_proof_crypto, write_batch, write_to_trie,
+};
+
+#[tokio::test]
+async fn export_then_import_rebuilds_the_same_root() {
+    let (router, _) = setup().await.unwrap();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let root = build_trie(
+        &router,
+        label,
+        vec![
+            (k1, Felt::from(10_u64)),
+            (k2, Felt::from(20_u64)),
+            (k3, Felt::from(30_u64)),
+            (k2, Felt::from(21_u64)),
+        ],
+    )
+    .await;
+    let root = delete_from_trie(&router, label, root, k3).await.trie_root;
+
+    let snapshot = Snapshot::from_jsonl(&export_trie(&router, label, root).await).unwrap();
+    assert_eq!(snapshot.header.trie_label, label);
+    assert_eq!(snapshot.header.trie_root, root);
+    assert_eq!(
+        snapshot.leaves,
+        vec![
+            SnapshotLeaf {
+                key: k1,
+                value: Felt::from(10_u64)
+            },
+            SnapshotLeaf {
+                key: k2,
+                value: Felt::from(21_u64)
+            },
+        ],
+        "only the leaves set under the root are exported"
+    );
+
+    let imported = import_trie(&router, Some(other_label), snapshot.to_jsonl().unwrap()).await;
+    assert_eq!(imported.trie_label, other_label);
+    assert_eq!(imported.trie_root, root);
+    assert_eq!(imported.leaves, 2);
+
+    let proofs = get_state_proofs(&router, read_actions(other_label, root, vec![k1, k2, k3]))
+        .await
+        .state_proofs;
+    verify_read_proof_crypto(&proofs[0], k1, Felt::from(10_u64), "imported k1", Some(Membership::Member));
+    verify_read_proof_crypto(&proofs[1], k2, Felt::from(21_u64), "imported k2", Some(Membership::Member));
+    verify_read_proof_crypto(&proofs[2], k3, Felt::ZERO, "imported k3", Some(Membership::NonMember));
+}
+
+#[tokio::test]
+async fn import_into_another_instance_uses_snapshot_label() {
+    let (router, _) = setup().await.unwrap();
+    let (other_router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, v1) = (Felt::from(1_u64), Felt::from(10_u64));
+
+    let old_root = write_to_trie(&router, label, Felt::ZERO, k1, v1).await.trie_root;
+    let root = write_to_trie(&router, label, old_root, Felt::from(2_u64), Felt::from(20_u64))
+        .await
+        .trie_root;
+
+    let imported = import_trie(&other_router, None, export_trie(&router, label, old_root).await).await;
+    assert_eq!(imported.trie_label, label);
+    assert_eq!(imported.trie_root, old_root);
+
+    assert_eq!(read_from_trie(&other_router, label, old_root, k1).await.value, Some(v1));
+    assert_eq!(
+        get_trie_root_node_idx(&other_router, label, root).await.status(),
+        StatusCode::NOT_FOUND,
+        "later roots are not part of the snapshot"
+    );
+
+    // Writes continue from the imported root as they would on the source instance
+    let next_root = write_to_trie(&other_router, label, old_root, Felt::from(2_u64), Felt::from(20_u64))
+        .await
+        .trie_root;
+    assert_eq!(next_root, root);
+}
+
+#[tokio::test]
+async fn import_rejects_tampered_snapshots() {
+    let (router, _) = setup().await.unwrap();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+
+    let root = build_trie(
+        &router,
+        label,
+        vec![(Felt::from(1_u64), Felt::from(10_u64)), (Felt::from(2_u64), Felt::from(20_u64))],
+    )
+    .await;
+    let snapshot = Snapshot::from_jsonl(&export_trie(&router, label, root).await).unwrap();
+
+    let mut tampered = snapshot.clone();
+    tampered.leaves[0].value = Felt::from(11_u64);
+    let resp = import_trie_raw(&router, Some(other_label), tampered.to_jsonl().unwrap()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "root mismatch");
+    assert_eq!(
+        get_trie_root_node_idx(&router, other_label, root).await.status(),
+        StatusCode::NOT_FOUND
+    );
+
+    let mut truncated = snapshot.clone();
+    truncated.leaves.pop();
+    let resp = import_trie_raw(&router, Some(other_label), truncated.to_jsonl().unwrap()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "root mismatch after truncation");
+
+    let mut reordered = snapshot.clone();
+    reordered.leaves.swap(0, 1);
+    let resp = import_trie_raw(&router, Some(other_label), reordered.to_jsonl().unwrap()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "out of order keys");
+
+    let resp = import_trie_raw(&router, Some(other_label), "not a snapshot".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "malformed header");
+}
+
+#[tokio::test]
+async fn export_then_import_spans_several_pages() {
+    let (router, _) = setup().await.unwrap();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+    let leaves = (1..=2 * SNAPSHOT_CHUNK_SIZE as u64 + 1)
+        .map(|i| (Felt::from(i), Felt::from(i * 10)))
+        .collect::<Vec<_>>();
+
+    let root = write_batch(&router, label, Felt::ZERO, leaves.clone()).await.trie_root;
+
+    let exported = export_trie(&router, label, root).await;
+    let snapshot = Snapshot::from_jsonl(&exported).unwrap();
+    assert_eq!(
+        snapshot.leaves,
+        leaves
+            .iter()
+            .map(|(key, value)| SnapshotLeaf { key: *key, value: *value })
+            .collect::<Vec<_>>(),
+        "every page is exported in key order"
+    );
+
+    let imported = import_trie(&router, Some(other_label), exported).await;
+    assert_eq!(imported.trie_root, root);
+    assert_eq!(imported.leaves, leaves.len() as u64);
+
+    let last = leaves.last().unwrap();
+    assert_eq!(read_from_trie(&router, other_label, root, last.0).await.value, Some(last.1));
+}
+
+#[tokio::test]
+async fn export_unknown_root_is_not_found() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    write_to_trie(&router, label, Felt::ZERO, Felt::from(1_u64), Felt::from(10_u64)).await;
+
+    let resp = export_trie_raw(&router, label, Felt::from(0xdead_u64)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let snapshot = Snapshot::from_jsonl(&export_trie(&router, label, Felt::ZERO).await).unwrap();
+    assert!(snapshot.leaves.is_empty(), "the empty root has no leaves");
+}
@@ -0,0 +1,110 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+
+use crate::helpers::{
+    build_trie, conflict_head, create_trie_raw, delete_from_trie_raw, get_head, get_state_proofs_raw, prune_trie, read_from_trie, setup,
+    swap_head, swap_head_raw, write_actions, write_batch_to_trie, write_to_trie, write_to_trie_raw,
+};
+
+#[tokio::test]
+async fn untracked_tries_accept_writes_from_any_root() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    let root1 = build_trie(&router, label, vec![(k1, Felt::from(10_u64))]).await;
+    write_to_trie(&router, label, root1, k2, Felt::from(20_u64)).await;
+    write_to_trie(&router, label, root1, k2, Felt::from(21_u64)).await;
+
+    assert_eq!(get_head(&router, label).await, None);
+}
+
+#[tokio::test]
+async fn writes_must_build_on_the_tracked_head() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let root1 = build_trie(&router, label, vec![(k1, Felt::from(10_u64))]).await;
+    assert_eq!(swap_head(&router, label, None, Some(root1)).await.head, Some(root1));
+
+    let root2 = write_to_trie(&router, label, root1, k2, Felt::from(20_u64)).await.trie_root;
+    assert_eq!(get_head(&router, label).await, Some(root2), "writes advance the head");
+
+    let stale = write_to_trie_raw(&router, label, root1, k2, Felt::from(21_u64), None).await;
+    assert_eq!(
+        conflict_head(stale).await,
+        Some(root2),
+        "a second writer from the same parent is rejected"
+    );
+
+    let stale = write_batch_to_trie(&router, label, root1, vec![(k3, Felt::from(30_u64))], None).await;
+    assert_eq!(conflict_head(stale).await, Some(root2));
+    let stale = delete_from_trie_raw(&router, label, root1, k1).await;
+    assert_eq!(conflict_head(stale).await, Some(root2));
+    assert_eq!(
+        get_head(&router, label).await,
+        Some(root2),
+        "rejected writes leave the head in place"
+    );
+    assert_eq!(read_from_trie(&router, label, root2, k2).await.value, Some(Felt::from(20_u64)));
+
+    let root3 = write_to_trie(&router, label, root2, k3, Felt::from(30_u64)).await.trie_root;
+    assert_eq!(get_head(&router, label).await, Some(root3), "rebasing on the head succeeds");
+
+    // Replaying writes for proofs reproduces existing roots and is not checked against the head
+    let resp = get_state_proofs_raw(&router, write_actions(label, root1, vec![(k2, Felt::from(20_u64))])).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(get_head(&router, label).await, Some(root3));
+}
+
+#[tokio::test]
+async fn swap_head_is_a_compare_and_swap() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    let root1 = build_trie(&router, label, vec![(k1, Felt::from(10_u64))]).await;
+    let root2 = write_to_trie(&router, label, root1, k2, Felt::from(20_u64)).await.trie_root;
+
+    swap_head(&router, label, None, Some(root2)).await;
+    assert_eq!(
+        conflict_head(swap_head_raw(&router, label, None, Some(root1)).await).await,
+        Some(root2)
+    );
+    assert_eq!(
+        conflict_head(swap_head_raw(&router, label, Some(root1), Some(root1)).await).await,
+        Some(root2)
+    );
+
+    let unknown = swap_head_raw(&router, label, Some(root2), Some(Felt::from(0xdead_u64))).await;
+    assert_eq!(unknown.status(), StatusCode::NOT_FOUND, "the new head must be a stored root");
+
+    swap_head(&router, label, Some(root2), Some(root1)).await;
+    write_to_trie(&router, label, root1, k2, Felt::from(21_u64)).await;
+
+    let conflict = create_trie_raw(&router, label, vec![Felt::from(9_u64)], vec![Felt::from(90_u64)]).await;
+    assert_eq!(conflict.status(), StatusCode::CONFLICT, "creating a trie builds on the empty root");
+
+    let head = get_head(&router, label).await;
+    assert_eq!(swap_head(&router, label, head, None).await.head, None);
+    write_to_trie(&router, label, root2, k1, Felt::from(11_u64)).await;
+    assert_eq!(get_head(&router, label).await, None, "untracked tries have no head to advance");
+}
+
+#[tokio::test]
+async fn prune_keeps_the_tracked_head() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    let root1 = build_trie(&router, label, vec![(k1, Felt::from(10_u64))]).await;
+    let root2 = write_to_trie(&router, label, root1, k2, Felt::from(20_u64)).await.trie_root;
+    let root3 = write_to_trie(&router, label, root2, k2, Felt::from(21_u64)).await.trie_root;
+    swap_head(&router, label, None, Some(root1)).await;
+
+    let stats = prune_trie(&router, label, vec![], Some(1)).await;
+    assert!(stats.roots_kept.contains(&root3));
+    assert!(stats.roots_kept.contains(&root1), "the head survives pruning");
+    assert_eq!(read_from_trie(&router, label, root1, k1).await.value, Some(Felt::from(10_u64)));
+}
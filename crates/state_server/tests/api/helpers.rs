@@ -1,109 +1,380 @@
-use axum::{body::Body, http::Request, Router};
+use axum::{
+    body::{Body, Bytes},
+    http::Request,
+    Router,
+};
 use http_body_util::BodyExt;
 use pathfinder_crypto::Felt;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::from_slice;
 use state_server::{
     api::{
         create_trie::{CreateTrieRequest, CreateTrieResponse},
+        delete::{DeleteRequest, DeleteResponse},
+        head::{HeadResponse, SwapHeadRequest},
+        import::ImportResponse,
+        leaves::{GetLeavesRequest, GetLeavesResponse},
         proof::{GetStateProofsRequest, GetStateProofsResponse},
+        prune::PruneRequest,
         read::ReadResponse,
+        roots::{GetRootLineageResponse, GetRootsRequest, GetRootsResponse},
         write::{WriteRequest, WriteResponse},
+        write_batch::{WriteBatchItem, WriteBatchRequest, WriteBatchResponse},
     },
     create_router,
-    mpt::trie::{Membership, Trie},
+    mpt::{
+        error::HeadConflictResponse,
+        prune::PruneStats,
+        trie::{Membership, Trie},
+    },
     AppState,
 };
 use tower::ServiceExt;
-use types::proofs::injected_state::{Action, ActionRead, ActionWrite, StateProof, StateProofRead, StateProofWrite};
+use types::proofs::injected_state::{Action, ActionDelete, ActionRead, ActionWrite, StateProof, StateProofRead, StateProofWrite};
 
 pub async fn setup() -> anyhow::Result<(Router, AppState)> {
     let state = AppState::new_memory()?;
     Ok((create_router(state.clone()), state))
 }
 
+/// A database root folder under the system temp dir, unique to the caller.
+pub fn temp_db_root() -> String {
+    std::env::temp_dir()
+        .join(format!("state_server_{}", uuid::Uuid::new_v4()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub async fn send(router: &Router, request: Request<Body>) -> axum::http::Response<axum::body::Body> {
+    router.clone().oneshot(request).await.unwrap()
+}
+
+/// Posts `request` as JSON to `uri` and returns the raw response.
+pub async fn post_json_raw<Req: Serialize>(router: &Router, uri: &str, request: &Req) -> axum::http::Response<axum::body::Body> {
+    send(
+        router,
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(request).unwrap()))
+            .unwrap(),
+    )
+    .await
+}
+
+/// Sends a `GET` request to `uri` and returns the raw response.
+pub async fn get_raw(router: &Router, uri: &str) -> axum::http::Response<axum::body::Body> {
+    send(router, Request::builder().method("GET").uri(uri).body(Body::empty()).unwrap()).await
+}
+
+/// Asserts that the response succeeded and returns its body.
+pub async fn success_body(resp: axum::http::Response<axum::body::Body>, ctx: &str) -> Bytes {
+    assert!(resp.status().is_success(), "{} failed with {}", ctx, resp.status());
+    resp.into_body().collect().await.unwrap().to_bytes()
+}
+
+/// Posts `request` as JSON to `uri` and decodes the JSON body of the successful response.
+pub async fn post_json<Req: Serialize, Resp: DeserializeOwned>(router: &Router, uri: &str, request: &Req) -> Resp {
+    from_slice(&success_body(post_json_raw(router, uri, request).await, &format!("POST {}", uri)).await).unwrap()
+}
+
+/// Sends a `GET` request to `uri` and decodes the JSON body of the successful response.
+pub async fn get_json<Resp: DeserializeOwned>(router: &Router, uri: &str) -> Resp {
+    from_slice(&success_body(get_raw(router, uri).await, &format!("GET {}", uri)).await).unwrap()
+}
+
+/// Appends the `name=value` query parameters whose value is set.
+fn with_query(mut uri: String, params: &[(&str, Option<String>)]) -> String {
+    for (name, value) in params {
+        if let Some(value) = value {
+            uri.push_str(&format!("&{}={}", name, value));
+        }
+    }
+    uri
+}
+
+fn create_trie_request(trie_label: Felt, keys: Vec<Felt>, values: Vec<Felt>) -> CreateTrieRequest {
+    CreateTrieRequest {
+        trie_label,
+        keys,
+        values,
+        tag: None,
+    }
+}
+
+pub async fn create_trie_raw(
+    router: &Router,
+    trie_label: Felt,
+    keys: Vec<Felt>,
+    values: Vec<Felt>,
+) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(router, "/create_trie", &create_trie_request(trie_label, keys, values)).await
+}
+
 pub async fn create_trie(router: &Router, trie_label: Felt, keys: Vec<Felt>, values: Vec<Felt>) -> CreateTrieResponse {
-    let body = router
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/create_trie")
-                .header("content-type", "application/json")
-                .body(Body::from(
-                    serde_json::to_vec(&CreateTrieRequest { trie_label, keys, values }).unwrap(),
-                ))
-                .unwrap(),
-        )
-        .await
-        .unwrap()
-        .into_body()
-        .collect()
-        .await
-        .unwrap()
-        .to_bytes();
-    from_slice(&body).unwrap()
+    post_json(router, "/create_trie", &create_trie_request(trie_label, keys, values)).await
 }
 
 pub async fn read_from_trie(router: &Router, trie_label: Felt, trie_root: Felt, key: Felt) -> ReadResponse {
-    let resp = router
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("GET")
-                .uri(format!("/read?trie_label={}&trie_root={}&key={}", trie_label, trie_root, key))
-                .header("content-type", "application/json")
-                .body(Body::empty())
-                .unwrap(),
-        )
-        .await
-        .unwrap();
-    assert!(resp.status().is_success(), "GET /read failed with {}", resp.status());
-    let body = resp.into_body().collect().await.unwrap().to_bytes();
-    from_slice(&body).unwrap()
+    get_json(
+        router,
+        &format!("/read?trie_label={}&trie_root={}&key={}", trie_label, trie_root, key),
+    )
+    .await
 }
 
 pub async fn write_to_trie(router: &Router, trie_label: Felt, trie_root: Felt, key: Felt, value: Felt) -> WriteResponse {
-    let resp = router
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/write")
-                .header("content-type", "application/json")
-                .body(Body::from(
-                    serde_json::to_vec(&WriteRequest {
-                        trie_label,
-                        trie_root,
-                        key,
-                        value,
-                    })
-                    .unwrap(),
-                ))
-                .unwrap(),
-        )
+    write_to_trie_with_tag(router, trie_label, trie_root, key, value, None).await
+}
+
+fn write_request(trie_label: Felt, trie_root: Felt, key: Felt, value: Felt, tag: Option<&str>) -> WriteRequest {
+    WriteRequest {
+        trie_label,
+        trie_root,
+        key,
+        value,
+        tag: tag.map(str::to_string),
+    }
+}
+
+pub async fn write_to_trie_raw(
+    router: &Router,
+    trie_label: Felt,
+    trie_root: Felt,
+    key: Felt,
+    value: Felt,
+    tag: Option<&str>,
+) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(router, "/write", &write_request(trie_label, trie_root, key, value, tag)).await
+}
+
+pub async fn write_to_trie_with_tag(
+    router: &Router,
+    trie_label: Felt,
+    trie_root: Felt,
+    key: Felt,
+    value: Felt,
+    tag: Option<&str>,
+) -> WriteResponse {
+    post_json(router, "/write", &write_request(trie_label, trie_root, key, value, tag)).await
+}
+
+fn write_batch_request(trie_label: Felt, trie_root: Felt, kv: Vec<(Felt, Felt)>, parent_batch_id: Option<u64>) -> WriteBatchRequest {
+    WriteBatchRequest {
+        trie_label,
+        trie_root,
+        writes: kv.into_iter().map(|(key, value)| WriteBatchItem { key, value }).collect(),
+        parent_batch_id,
+        tag: None,
+    }
+}
+
+pub async fn write_batch_to_trie(
+    router: &Router,
+    trie_label: Felt,
+    trie_root: Felt,
+    kv: Vec<(Felt, Felt)>,
+    parent_batch_id: Option<u64>,
+) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(
+        router,
+        "/write_batch",
+        &write_batch_request(trie_label, trie_root, kv, parent_batch_id),
+    )
+    .await
+}
+
+pub async fn write_batch(router: &Router, trie_label: Felt, trie_root: Felt, kv: Vec<(Felt, Felt)>) -> WriteBatchResponse {
+    post_json(router, "/write_batch", &write_batch_request(trie_label, trie_root, kv, None)).await
+}
+
+fn delete_request(trie_label: Felt, trie_root: Felt, key: Felt) -> DeleteRequest {
+    DeleteRequest {
+        trie_label,
+        trie_root,
+        key,
+        tag: None,
+    }
+}
+
+pub async fn delete_from_trie_raw(router: &Router, trie_label: Felt, trie_root: Felt, key: Felt) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(router, "/delete", &delete_request(trie_label, trie_root, key)).await
+}
+
+pub async fn delete_from_trie(router: &Router, trie_label: Felt, trie_root: Felt, key: Felt) -> DeleteResponse {
+    post_json(router, "/delete", &delete_request(trie_label, trie_root, key)).await
+}
+
+fn leaves_uri(request: &GetLeavesRequest) -> String {
+    with_query(
+        format!("/leaves?trie_label={}&trie_root={}", request.trie_label, request.trie_root),
+        &[
+            ("cursor", request.cursor.map(|cursor| cursor.to_string())),
+            ("start_key", request.start_key.map(|key| key.to_string())),
+            ("end_key", request.end_key.map(|key| key.to_string())),
+            ("limit", request.limit.map(|limit| limit.to_string())),
+        ],
+    )
+}
+
+pub async fn get_leaves_raw(router: &Router, request: &GetLeavesRequest) -> axum::http::Response<axum::body::Body> {
+    get_raw(router, &leaves_uri(request)).await
+}
+
+pub async fn get_leaves(router: &Router, request: &GetLeavesRequest) -> GetLeavesResponse {
+    get_json(router, &leaves_uri(request)).await
+}
+
+fn roots_uri(request: &GetRootsRequest) -> String {
+    with_query(
+        format!("/roots?trie_label={}", request.trie_label),
+        &[
+            ("cursor", request.cursor.map(|cursor| cursor.to_string())),
+            ("limit", request.limit.map(|limit| limit.to_string())),
+            ("tag", request.tag.clone()),
+        ],
+    )
+}
+
+pub async fn get_roots_raw(router: &Router, request: &GetRootsRequest) -> axum::http::Response<axum::body::Body> {
+    get_raw(router, &roots_uri(request)).await
+}
+
+pub async fn get_roots(router: &Router, request: &GetRootsRequest) -> GetRootsResponse {
+    get_json(router, &roots_uri(request)).await
+}
+
+fn root_lineage_uri(trie_label: Felt, trie_root: Felt, limit: Option<usize>) -> String {
+    with_query(
+        format!("/root_lineage?trie_label={}&trie_root={}", trie_label, trie_root),
+        &[("limit", limit.map(|limit| limit.to_string()))],
+    )
+}
+
+pub async fn get_root_lineage_raw(
+    router: &Router,
+    trie_label: Felt,
+    trie_root: Felt,
+    limit: Option<usize>,
+) -> axum::http::Response<axum::body::Body> {
+    get_raw(router, &root_lineage_uri(trie_label, trie_root, limit)).await
+}
+
+pub async fn get_root_lineage(router: &Router, trie_label: Felt, trie_root: Felt, limit: Option<usize>) -> GetRootLineageResponse {
+    get_json(router, &root_lineage_uri(trie_label, trie_root, limit)).await
+}
+
+pub async fn export_trie_raw(router: &Router, trie_label: Felt, trie_root: Felt) -> axum::http::Response<axum::body::Body> {
+    get_raw(router, &format!("/export?trie_label={}&trie_root={}", trie_label, trie_root)).await
+}
+
+pub async fn export_trie(router: &Router, trie_label: Felt, trie_root: Felt) -> String {
+    String::from_utf8(
+        success_body(export_trie_raw(router, trie_label, trie_root).await, "GET /export")
+            .await
+            .to_vec(),
+    )
+    .unwrap()
+}
+
+pub async fn import_trie_raw(router: &Router, trie_label: Option<Felt>, snapshot: String) -> axum::http::Response<axum::body::Body> {
+    let uri = match trie_label {
+        Some(trie_label) => format!("/import?trie_label={}", trie_label),
+        None => "/import".to_string(),
+    };
+    send(
+        router,
+        Request::builder().method("POST").uri(uri).body(Body::from(snapshot)).unwrap(),
+    )
+    .await
+}
+
+pub async fn import_trie(router: &Router, trie_label: Option<Felt>, snapshot: String) -> ImportResponse {
+    from_slice(&success_body(import_trie_raw(router, trie_label, snapshot).await, "POST /import").await).unwrap()
+}
+
+pub async fn get_head(router: &Router, trie_label: Felt) -> Option<Felt> {
+    get_json::<HeadResponse>(router, &format!("/head?trie_label={}", trie_label))
         .await
-        .unwrap();
-    assert!(resp.status().is_success(), "POST /write failed with {}", resp.status());
+        .head
+}
+
+pub async fn swap_head_raw(
+    router: &Router,
+    trie_label: Felt,
+    expected: Option<Felt>,
+    head: Option<Felt>,
+) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(
+        router,
+        "/head",
+        &SwapHeadRequest {
+            trie_label,
+            expected,
+            head,
+        },
+    )
+    .await
+}
+
+pub async fn swap_head(router: &Router, trie_label: Felt, expected: Option<Felt>, head: Option<Felt>) -> HeadResponse {
+    post_json(
+        router,
+        "/head",
+        &SwapHeadRequest {
+            trie_label,
+            expected,
+            head,
+        },
+    )
+    .await
+}
+
+/// Reads the head carried by a `409 Conflict` response.
+pub async fn conflict_head(resp: axum::http::Response<axum::body::Body>) -> Option<Felt> {
+    assert_eq!(resp.status(), axum::http::StatusCode::CONFLICT);
     let body = resp.into_body().collect().await.unwrap().to_bytes();
-    from_slice(&body).unwrap()
+    from_slice::<HeadConflictResponse>(&body).unwrap().head
+}
+
+pub async fn prune_trie_raw(
+    router: &Router,
+    trie_label: Felt,
+    keep_roots: Vec<Felt>,
+    keep_last: Option<usize>,
+) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(
+        router,
+        "/prune",
+        &PruneRequest {
+            trie_label,
+            keep_roots,
+            keep_last,
+        },
+    )
+    .await
+}
+
+pub async fn prune_trie(router: &Router, trie_label: Felt, keep_roots: Vec<Felt>, keep_last: Option<usize>) -> PruneStats {
+    post_json(
+        router,
+        "/prune",
+        &PruneRequest {
+            trie_label,
+            keep_roots,
+            keep_last,
+        },
+    )
+    .await
+}
+
+pub async fn get_state_proofs_raw(router: &Router, actions: Vec<Action>) -> axum::http::Response<axum::body::Body> {
+    post_json_raw(router, "/get_state_proofs", &GetStateProofsRequest { actions }).await
 }
 
 pub async fn get_state_proofs(router: &Router, actions: Vec<Action>) -> GetStateProofsResponse {
-    let resp = router
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/get_state_proofs")
-                .header("content-type", "application/json")
-                .body(Body::from(serde_json::to_vec(&GetStateProofsRequest { actions }).unwrap()))
-                .unwrap(),
-        )
-        .await
-        .unwrap();
-    assert!(resp.status().is_success(), "POST /get_state_proofs failed with {}", resp.status());
-    let body = resp.into_body().collect().await.unwrap().to_bytes();
-    from_slice(&body).unwrap()
+    post_json(router, "/get_state_proofs", &GetStateProofsRequest { actions }).await
 }
 
 pub fn read_actions(trie_label: Felt, trie_root: Felt, keys: Vec<Felt>) -> Vec<Action> {
@@ -131,6 +402,18 @@ pub fn write_actions(trie_label: Felt, trie_root: Felt, kv: Vec<(Felt, Felt)>) -
         .collect()
 }
 
+pub fn delete_actions(trie_label: Felt, trie_root: Felt, keys: Vec<Felt>) -> Vec<Action> {
+    keys.into_iter()
+        .map(|k| {
+            Action::Delete(ActionDelete {
+                trie_label,
+                trie_root,
+                key: k,
+            })
+        })
+        .collect()
+}
+
 pub async fn build_trie(router: &Router, trie_label: Felt, kv: Vec<(Felt, Felt)>) -> Felt {
     let mut root = Felt::ZERO;
     for (k, v) in kv {
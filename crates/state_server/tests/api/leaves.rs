@@ -0,0 +1,179 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::api::leaves::{GetLeavesRequest, GetLeavesResponse};
+
+use crate::helpers::{build_trie, delete_from_trie, get_leaves, get_leaves_raw, setup, write_to_trie};
+
+fn request(trie_label: Felt, trie_root: Felt) -> GetLeavesRequest {
+    GetLeavesRequest {
+        trie_label,
+        trie_root,
+        cursor: None,
+        limit: None,
+        start_key: None,
+        end_key: None,
+    }
+}
+
+fn felts(values: &[u64]) -> Vec<Felt> {
+    values.iter().map(|v| Felt::from(*v)).collect()
+}
+
+#[tokio::test]
+async fn leaves_are_listed_in_key_order_across_pages() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let keys = vec![
+        Felt::from(5_u64),
+        Felt::from_hex_str("0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap(),
+        Felt::from(1_u64),
+        Felt::from_hex_str("0x400000000000000000000000000000000000000000000000000000000000000").unwrap(),
+        Felt::from(3_u64),
+        Felt::from(2_u64),
+    ];
+    let root = build_trie(&router, label, keys.iter().map(|k| (*k, Felt::from(10_u64))).collect()).await;
+
+    let mut listed = vec![];
+    let mut cursor = None;
+    loop {
+        let page = get_leaves(
+            &router,
+            &GetLeavesRequest {
+                cursor,
+                limit: Some(4),
+                ..request(label, root)
+            },
+        )
+        .await;
+        assert!(page.leaves.len() <= 4);
+        listed.extend(page.leaves.iter().map(|leaf| leaf.key));
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let mut expected = keys.clone();
+    expected.sort_by_key(|key| key.to_be_bytes());
+    assert_eq!(listed, expected);
+}
+
+#[tokio::test]
+async fn leaves_of_historical_roots_are_exact() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let old_root = build_trie(&router, label, vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]).await;
+    let root = write_to_trie(&router, label, old_root, k2, Felt::from(21_u64)).await.trie_root;
+    let root = write_to_trie(&router, label, root, k3, Felt::from(30_u64)).await.trie_root;
+    let root = delete_from_trie(&router, label, root, k1).await.trie_root;
+
+    let old = get_leaves(&router, &request(label, old_root)).await;
+    assert_eq!(
+        old.leaves.iter().map(|leaf| (leaf.key, leaf.value)).collect::<Vec<_>>(),
+        vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]
+    );
+    assert_eq!(old.next_cursor, None);
+
+    let latest = get_leaves(&router, &request(label, root)).await;
+    assert_eq!(
+        latest.leaves.iter().map(|leaf| (leaf.key, leaf.value)).collect::<Vec<_>>(),
+        vec![(k2, Felt::from(21_u64)), (k3, Felt::from(30_u64))]
+    );
+}
+
+#[tokio::test]
+async fn leaves_of_forked_roots_are_exact() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    let base = build_trie(&router, label, vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]).await;
+    let left = write_to_trie(&router, label, base, k2, Felt::from(21_u64)).await.trie_root;
+    // Written after `left`, so the update of k2 on the other branch precedes this root
+    let right = write_to_trie(&router, label, base, k1, Felt::from(11_u64)).await.trie_root;
+
+    let leaves = |page: GetLeavesResponse| page.leaves.into_iter().map(|leaf| (leaf.key, leaf.value)).collect::<Vec<_>>();
+    assert_eq!(
+        leaves(get_leaves(&router, &request(label, left)).await),
+        vec![(k1, Felt::from(10_u64)), (k2, Felt::from(21_u64))]
+    );
+    assert_eq!(
+        leaves(get_leaves(&router, &request(label, right)).await),
+        vec![(k1, Felt::from(11_u64)), (k2, Felt::from(20_u64))],
+        "the branch reads its own value of k2"
+    );
+    assert_eq!(
+        leaves(get_leaves(&router, &request(label, base)).await),
+        vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]
+    );
+}
+
+#[tokio::test]
+async fn leaves_respect_key_range() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let root = build_trie(
+        &router,
+        label,
+        felts(&[1, 3, 5, 7, 9]).into_iter().map(|k| (k, Felt::from(1_u64))).collect(),
+    )
+    .await;
+
+    let range = |start_key: Option<u64>, end_key: Option<u64>| GetLeavesRequest {
+        start_key: start_key.map(Felt::from),
+        end_key: end_key.map(Felt::from),
+        ..request(label, root)
+    };
+    let keys = |page: GetLeavesResponse| page.leaves.into_iter().map(|leaf| leaf.key).collect::<Vec<_>>();
+
+    assert_eq!(keys(get_leaves(&router, &range(Some(3), Some(9))).await), felts(&[3, 5, 7]));
+    assert_eq!(keys(get_leaves(&router, &range(Some(4), None)).await), felts(&[5, 7, 9]));
+    assert_eq!(keys(get_leaves(&router, &range(None, Some(2))).await), felts(&[1]));
+    assert!(keys(get_leaves(&router, &range(Some(10), None)).await).is_empty());
+
+    let page = get_leaves(
+        &router,
+        &GetLeavesRequest {
+            limit: Some(2),
+            ..range(Some(3), Some(9))
+        },
+    )
+    .await;
+    assert_eq!(page.next_cursor, Some(Felt::from(5_u64)));
+    let page = get_leaves(
+        &router,
+        &GetLeavesRequest {
+            limit: Some(2),
+            cursor: page.next_cursor,
+            ..range(Some(3), Some(9))
+        },
+    )
+    .await;
+    assert_eq!(keys(page), felts(&[7]));
+}
+
+#[tokio::test]
+async fn leaves_rejects_invalid_requests() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let root = write_to_trie(&router, label, Felt::ZERO, Felt::from(1_u64), Felt::from(10_u64))
+        .await
+        .trie_root;
+
+    let resp = get_leaves_raw(
+        &router,
+        &GetLeavesRequest {
+            limit: Some(0),
+            ..request(label, root)
+        },
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = get_leaves_raw(&router, &request(label, Felt::from(0xdead_u64))).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    assert!(get_leaves(&router, &request(label, Felt::ZERO)).await.leaves.is_empty());
+}
@@ -1,6 +1,16 @@
+pub mod auth;
+pub mod backends;
+pub mod concurrency;
+pub mod delete;
+pub mod export_import;
+pub mod head;
 pub mod helpers;
 pub mod invariants;
+pub mod leaves;
 pub mod proof;
+pub mod prune;
 pub mod read;
 pub mod root_to_node_idx;
+pub mod roots;
 pub mod write;
+pub mod write_batch;
@@ -0,0 +1,141 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::mpt::trie::Membership;
+
+use crate::helpers::{
+    build_trie, get_state_proofs, get_trie_root_node_idx, prune_trie, prune_trie_raw, read_actions, read_from_trie, setup,
+    verify_read_proof_crypto, write_to_trie,
+};
+
+async fn assert_proofs_verify(router: &axum::Router, label: Felt, root: Felt, expected: &[(Felt, Felt)], ctx: &str) {
+    let keys = expected.iter().map(|(key, _)| *key).collect();
+    let proofs = get_state_proofs(router, read_actions(label, root, keys)).await.state_proofs;
+
+    for ((key, value), proof) in expected.iter().zip(proofs.iter()) {
+        let membership = if *value == Felt::ZERO {
+            Membership::NonMember
+        } else {
+            Membership::Member
+        };
+        verify_read_proof_crypto(proof, *key, *value, ctx, Some(membership));
+    }
+}
+
+#[tokio::test]
+async fn prune_keeps_proofs_of_retained_roots() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let root1 = build_trie(&router, label, vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64))]).await;
+    let root2 = write_to_trie(&router, label, root1, k2, Felt::from(21_u64)).await.trie_root;
+    let root3 = write_to_trie(&router, label, root2, k3, Felt::from(30_u64)).await.trie_root;
+    let root4 = write_to_trie(&router, label, root3, k1, Felt::from(11_u64)).await.trie_root;
+
+    let stats = prune_trie(&router, label, vec![root2, root4], None).await;
+    assert_eq!(stats.roots_kept, vec![root2, root4]);
+    assert!(stats.nodes_deleted > 0, "unreachable nodes must be deleted");
+    assert!(stats.leafs_deleted > 0, "overwritten leaves must be deleted");
+
+    assert_proofs_verify(
+        &router,
+        label,
+        root2,
+        &[(k1, Felt::from(10_u64)), (k2, Felt::from(21_u64)), (k3, Felt::ZERO)],
+        "retained root2",
+    )
+    .await;
+    assert_proofs_verify(
+        &router,
+        label,
+        root4,
+        &[(k1, Felt::from(11_u64)), (k2, Felt::from(21_u64)), (k3, Felt::from(30_u64))],
+        "retained root4",
+    )
+    .await;
+
+    for pruned in [root1, root3] {
+        assert_eq!(get_trie_root_node_idx(&router, label, pruned).await.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+#[tokio::test]
+async fn prune_keep_last_allows_further_writes() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2, k3) = (Felt::from(1_u64), Felt::from(2_u64), Felt::from(3_u64));
+
+    let root = build_trie(
+        &router,
+        label,
+        vec![(k1, Felt::from(10_u64)), (k2, Felt::from(20_u64)), (k1, Felt::from(11_u64))],
+    )
+    .await;
+
+    let stats = prune_trie(&router, label, vec![], Some(1)).await;
+    assert_eq!(stats.roots_kept, vec![root]);
+    assert!(stats.nodes_deleted > 0, "unreachable nodes must be deleted");
+
+    let new_root = write_to_trie(&router, label, root, k3, Felt::from(30_u64)).await.trie_root;
+    assert_eq!(read_from_trie(&router, label, new_root, k1).await.value, Some(Felt::from(11_u64)));
+
+    assert_proofs_verify(
+        &router,
+        label,
+        new_root,
+        &[(k1, Felt::from(11_u64)), (k2, Felt::from(20_u64)), (k3, Felt::from(30_u64))],
+        "root written after prune",
+    )
+    .await;
+    assert_proofs_verify(
+        &router,
+        label,
+        root,
+        &[(k1, Felt::from(11_u64)), (k2, Felt::from(20_u64)), (k3, Felt::ZERO)],
+        "retained root",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn prune_only_touches_its_own_trie() {
+    let (router, _) = setup().await.unwrap();
+    let (label, other_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64));
+    let (k1, v1, v2) = (Felt::from(1_u64), Felt::from(10_u64), Felt::from(11_u64));
+
+    let old_root = write_to_trie(&router, label, Felt::ZERO, k1, v1).await.trie_root;
+    write_to_trie(&router, label, old_root, k1, v2).await;
+    let other_root = write_to_trie(&router, other_label, Felt::ZERO, k1, v1).await.trie_root;
+
+    prune_trie(&router, label, vec![], Some(1)).await;
+
+    assert_eq!(
+        get_trie_root_node_idx(&router, label, old_root).await.status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_proofs_verify(&router, other_label, other_root, &[(k1, v1)], "untouched trie").await;
+}
+
+#[tokio::test]
+async fn prune_rejects_invalid_requests() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    let root = write_to_trie(&router, label, Felt::ZERO, Felt::from(1_u64), Felt::from(10_u64))
+        .await
+        .trie_root;
+
+    let resp = prune_trie_raw(&router, label, vec![], None).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "a retain policy is required");
+
+    let resp = prune_trie_raw(&router, label, vec![root], Some(1)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "retain policies are exclusive");
+
+    let resp = prune_trie_raw(&router, label, vec![Felt::from(0xdead_u64)], None).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND, "unknown roots cannot be retained");
+
+    assert_eq!(
+        read_from_trie(&router, label, root, Felt::from(1_u64)).await.value,
+        Some(Felt::from(10_u64))
+    );
+}
@@ -0,0 +1,165 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::api::roots::GetRootsRequest;
+
+use crate::helpers::{
+    create_trie, delete_from_trie, export_trie, get_root_lineage, get_root_lineage_raw, get_roots, get_roots_raw, get_state_proofs,
+    import_trie, setup, write_actions, write_to_trie, write_to_trie_with_tag,
+};
+
+fn request(trie_label: Felt) -> GetRootsRequest {
+    GetRootsRequest {
+        trie_label,
+        cursor: None,
+        limit: None,
+        tag: None,
+    }
+}
+
+#[tokio::test]
+async fn roots_record_parent_index_and_tag() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+    let (k1, k2) = (Felt::from(1_u64), Felt::from(2_u64));
+
+    let first = write_to_trie_with_tag(&router, label, Felt::ZERO, k1, Felt::from(10_u64), Some("block-1")).await;
+    let second = write_to_trie(&router, label, first.trie_root, k2, Felt::from(20_u64)).await;
+    let third = delete_from_trie(&router, label, second.trie_root, k1).await;
+
+    // Writes that leave the root unchanged do not derive a new root
+    write_to_trie(&router, label, third.trie_root, k2, Felt::from(20_u64)).await;
+
+    let roots = get_roots(&router, &request(label)).await;
+    assert_eq!(roots.next_cursor, None);
+    assert_eq!(
+        roots
+            .roots
+            .iter()
+            .map(|root| (root.trie_root, root.parent_root))
+            .collect::<Vec<_>>(),
+        vec![
+            (first.trie_root, Some(Felt::ZERO)),
+            (second.trie_root, Some(first.trie_root)),
+            (third.trie_root, Some(second.trie_root)),
+        ]
+    );
+    assert_eq!(
+        roots.roots.iter().map(|root| root.root_idx).collect::<Vec<_>>(),
+        vec![first.trie_id, second.trie_id, third.trie_id]
+    );
+    assert_eq!(roots.roots[0].tag.as_deref(), Some("block-1"));
+    assert_eq!(roots.roots[1].tag, None);
+    assert!(roots.roots.iter().all(|root| root.created_at > 0));
+}
+
+#[tokio::test]
+async fn roots_are_recorded_by_create_trie_proofs_and_import() {
+    let (router, _) = setup().await.unwrap();
+    let (label, proof_label, import_label) = (Felt::from(0x123_u64), Felt::from(0x456_u64), Felt::from(0x789_u64));
+    let (k1, v1) = (Felt::from(1_u64), Felt::from(10_u64));
+
+    let created = create_trie(&router, label, vec![k1], vec![v1]).await.trie_root;
+    let roots = get_roots(&router, &request(label)).await.roots;
+    assert_eq!(roots.len(), 1);
+    assert_eq!((roots[0].trie_root, roots[0].parent_root), (created, Some(Felt::ZERO)));
+
+    // A write proof records the root it proves, once
+    let written = write_to_trie(&router, proof_label, Felt::ZERO, k1, v1).await.trie_root;
+    get_state_proofs(&router, write_actions(proof_label, Felt::ZERO, vec![(k1, v1)])).await;
+    let roots = get_roots(&router, &request(proof_label)).await.roots;
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].trie_root, written);
+
+    // Imported roots have no known parent
+    import_trie(&router, Some(import_label), export_trie(&router, label, created).await).await;
+    let roots = get_roots(&router, &request(import_label)).await.roots;
+    assert_eq!(roots.len(), 1);
+    assert_eq!((roots[0].trie_root, roots[0].parent_root), (created, None));
+}
+
+#[tokio::test]
+async fn roots_are_paginated_and_filtered_by_tag() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    let mut root = Felt::ZERO;
+    for (key, tag) in [(1_u64, Some("committed")), (2, None), (3, Some("committed"))] {
+        root = write_to_trie_with_tag(&router, label, root, Felt::from(key), Felt::from(key), tag)
+            .await
+            .trie_root;
+    }
+
+    let committed = get_roots(
+        &router,
+        &GetRootsRequest {
+            tag: Some("committed".to_string()),
+            ..request(label)
+        },
+    )
+    .await
+    .roots;
+    assert_eq!(committed.len(), 2);
+    assert_eq!(committed[1].trie_root, root);
+
+    let mut listed = vec![];
+    let mut cursor = None;
+    loop {
+        let page = get_roots(
+            &router,
+            &GetRootsRequest {
+                cursor,
+                limit: Some(2),
+                ..request(label)
+            },
+        )
+        .await;
+        listed.extend(page.roots);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(listed, get_roots(&router, &request(label)).await.roots);
+    assert_eq!(listed.len(), 3);
+
+    let resp = get_roots_raw(
+        &router,
+        &GetRootsRequest {
+            limit: Some(0),
+            ..request(label)
+        },
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn root_lineage_follows_parents() {
+    let (router, _) = setup().await.unwrap();
+    let label = Felt::from(0x123_u64);
+
+    let mut chain = vec![];
+    let mut root = Felt::ZERO;
+    for key in 1_u64..=3 {
+        root = write_to_trie(&router, label, root, Felt::from(key), Felt::from(key))
+            .await
+            .trie_root;
+        chain.push(root);
+    }
+    chain.reverse();
+
+    let lineage = get_root_lineage(&router, label, root, None).await.lineage;
+    assert_eq!(lineage.iter().map(|record| record.trie_root).collect::<Vec<_>>(), chain);
+    assert_eq!(lineage.last().unwrap().parent_root, Some(Felt::ZERO));
+
+    let lineage = get_root_lineage(&router, label, root, Some(2)).await.lineage;
+    assert_eq!(
+        lineage.iter().map(|record| record.trie_root).collect::<Vec<_>>(),
+        chain[..2].to_vec()
+    );
+
+    assert!(get_root_lineage(&router, label, Felt::ZERO, None).await.lineage.is_empty());
+
+    let resp = get_root_lineage_raw(&router, label, Felt::from(0xdead_u64), None).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
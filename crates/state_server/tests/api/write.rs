@@ -13,8 +13,8 @@ async fn write_to_new_trie() {
         Felt::from_hex_str("0x1").unwrap(),
         Felt::from_hex_str("0x1").unwrap(),
     );
-    let conn = state.get_connection(label).unwrap();
-    let db = TrieDB::new(&conn);
+    let store = state.get_store(label).unwrap();
+    let db = TrieDB::new(&*store);
 
     assert!(db.get_leaf_at(key, 0).unwrap().is_none());
     let root = write_to_trie(&router, label, Felt::ZERO, key, val).await.trie_root;
@@ -42,8 +42,8 @@ async fn write_to_existing_trie() {
         Felt::from_hex_str("0x1").unwrap(),
         Felt::from_hex_str("0x2").unwrap(),
     );
-    let conn = state.get_connection(label).unwrap();
-    let db = TrieDB::new(&conn);
+    let store = state.get_store(label).unwrap();
+    let db = TrieDB::new(&*store);
 
     let root1 = write_to_trie(&router, label, Felt::ZERO, k1, v1).await.trie_root;
     assert_ne!(root1, Felt::ZERO, "Root must change after first write");
@@ -75,8 +75,8 @@ async fn override_existing_key() {
         Felt::from_hex_str("0x1").unwrap(),
         Felt::from_hex_str("0x2").unwrap(),
     );
-    let conn = state.get_connection(label).unwrap();
-    let db = TrieDB::new(&conn);
+    let store = state.get_store(label).unwrap();
+    let db = TrieDB::new(&*store);
 
     let root1 = write_to_trie(&router, label, Felt::ZERO, key, v1).await.trie_root;
     let proof1 = write_proof(&router, label, Felt::ZERO, key, v1).await;
@@ -0,0 +1,105 @@
+use axum::http::StatusCode;
+use pathfinder_crypto::Felt;
+use state_server::mpt::db::trie::TrieDB;
+
+use crate::helpers::{build_trie, read_from_trie, setup, write_batch, write_batch_to_trie};
+
+fn kv(pairs: &[(u64, u64)]) -> Vec<(Felt, Felt)> {
+    pairs.iter().map(|(k, v)| (Felt::from(*k), Felt::from(*v))).collect()
+}
+
+#[tokio::test]
+async fn batch_matches_sequential_writes() {
+    let (router, _state) = setup().await.unwrap();
+    let (batch_label, seq_label) = (Felt::from_hex_str("0x123").unwrap(), Felt::from_hex_str("0x456").unwrap());
+    let writes = kv(&[(1, 10), (2, 20), (3, 30), (4, 40)]);
+
+    let batch = write_batch(&router, batch_label, Felt::ZERO, writes.clone()).await;
+    let sequential_root = build_trie(&router, seq_label, writes.clone()).await;
+
+    assert_eq!(batch.trie_root, sequential_root, "batch root must match sequential writes");
+    for (key, value) in writes {
+        assert_eq!(read_from_trie(&router, batch_label, batch.trie_root, key).await.value, Some(value));
+    }
+}
+
+#[tokio::test]
+async fn batch_on_existing_root_keeps_previous_root_readable() {
+    let (router, _state) = setup().await.unwrap();
+    let label = Felt::from_hex_str("0x123").unwrap();
+
+    let first = write_batch(&router, label, Felt::ZERO, kv(&[(1, 10), (2, 20)])).await;
+    let second = write_batch(&router, label, first.trie_root, kv(&[(2, 21), (3, 30)])).await;
+
+    assert_ne!(first.trie_root, second.trie_root);
+    assert_eq!(
+        read_from_trie(&router, label, second.trie_root, Felt::from(1_u64)).await.value,
+        Some(Felt::from(10_u64))
+    );
+    assert_eq!(
+        read_from_trie(&router, label, second.trie_root, Felt::from(2_u64)).await.value,
+        Some(Felt::from(21_u64))
+    );
+    assert_eq!(
+        read_from_trie(&router, label, first.trie_root, Felt::from(2_u64)).await.value,
+        Some(Felt::from(20_u64))
+    );
+    assert_eq!(read_from_trie(&router, label, first.trie_root, Felt::from(3_u64)).await.value, None);
+}
+
+#[tokio::test]
+async fn batches_are_chained_to_their_parent() {
+    let (router, state) = setup().await.unwrap();
+    let label = Felt::from_hex_str("0x123").unwrap();
+
+    let first = write_batch(&router, label, Felt::ZERO, kv(&[(1, 10)])).await;
+    let second = write_batch(&router, label, first.trie_root, kv(&[(2, 20)])).await;
+
+    assert_eq!(first.parent_batch_id, None);
+    assert_eq!(second.parent_batch_id, Some(first.batch_id));
+
+    let store = state.get_store(label).unwrap();
+    let db = TrieDB::new(&*store);
+    assert_eq!(db.get_batch_id_by_root(second.trie_root).unwrap(), Some(second.batch_id));
+    assert_eq!(db.get_node_idx_by_hash(second.trie_root).unwrap(), second.trie_id);
+}
+
+#[tokio::test]
+async fn last_write_wins_within_a_batch() {
+    let (router, _state) = setup().await.unwrap();
+    let label = Felt::from_hex_str("0x123").unwrap();
+
+    let batch = write_batch(&router, label, Felt::ZERO, kv(&[(1, 10), (1, 11)])).await;
+
+    assert_eq!(
+        read_from_trie(&router, label, batch.trie_root, Felt::from(1_u64)).await.value,
+        Some(Felt::from(11_u64))
+    );
+}
+
+#[tokio::test]
+async fn empty_batch_is_rejected() {
+    let (router, _state) = setup().await.unwrap();
+    let label = Felt::from_hex_str("0x123").unwrap();
+
+    let resp = write_batch_to_trie(&router, label, Felt::ZERO, vec![], None).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn batch_on_unknown_root_persists_nothing() {
+    let (router, state) = setup().await.unwrap();
+    let label = Felt::from_hex_str("0x123").unwrap();
+    let root = write_batch(&router, label, Felt::ZERO, kv(&[(1, 10)])).await.trie_root;
+
+    let store = state.get_store(label).unwrap();
+    let db = TrieDB::new(&*store);
+    let max_idx = db.get_node_idx().unwrap();
+
+    let resp = write_batch_to_trie(&router, label, Felt::from(0xdead_u64), kv(&[(2, 20)]), None).await;
+    assert!(!resp.status().is_success());
+
+    assert_eq!(db.get_node_idx().unwrap(), max_idx);
+    assert!(db.get_batch_id_by_root(root).unwrap().is_some());
+}
@@ -36,7 +36,7 @@ impl traits::SyscallHandler for DebugCallContractHandler {
                     .collect::<Vec<Felt252>>();
 
                 let str = decode_byte_array_felts(fields);
-                println!("{}", str);
+                eprintln!("{}", str);
                 Ok(Self::Response {
                     retdata_start: request.calldata_end,
                     retdata_end: request.calldata_end,
@@ -50,7 +50,7 @@ impl traits::SyscallHandler for DebugCallContractHandler {
                     .map(|f| (*f.as_ref()))
                     .collect::<Vec<Felt252>>();
 
-                println!("{:?}", fields);
+                eprintln!("{:?}", fields);
 
                 Ok(Self::Response {
                     retdata_start: request.calldata_end,
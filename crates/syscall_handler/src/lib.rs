@@ -33,8 +33,7 @@ use types::{
         new_syscalls::{CallContractRequest, CallContractResponse},
         traits::CairoType,
     },
-    ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID, OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID, STARKNET_MAINNET_CHAIN_ID,
-    STARKNET_TESTNET_CHAIN_ID,
+    chain_registry::{ChainLayout, ChainRegistry},
 };
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -236,12 +235,12 @@ impl<
                 let chain_id = <Felt252 as TryInto<u128>>::try_into(*vm.get_integer((request.calldata_start + 2)?)?)
                     .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?;
 
-                match chain_id {
-                    ETHEREUM_MAINNET_CHAIN_ID | ETHEREUM_TESTNET_CHAIN_ID | OPTIMISM_MAINNET_CHAIN_ID | OPTIMISM_TESTNET_CHAIN_ID => {
-                        self.evm_call_contract_handler.execute(request, vm).await
-                    }
-                    STARKNET_MAINNET_CHAIN_ID | STARKNET_TESTNET_CHAIN_ID => self.starknet_call_contract_handler.execute(request, vm).await,
-                    _ => Err(SyscallExecutionError::InternalError(Box::from("Unknown chain id"))),
+                match ChainRegistry::global()
+                    .layout(chain_id)
+                    .map_err(|e| SyscallExecutionError::InternalError(e.to_string().into()))?
+                {
+                    ChainLayout::Evm => self.evm_call_contract_handler.execute(request, vm).await,
+                    ChainLayout::Starknet => self.starknet_call_contract_handler.execute(request, vm).await,
                 }
             }
         }
@@ -0,0 +1,177 @@
+use std::{fmt, str::FromStr};
+
+use cairo_vm::{
+    types::builtin_name::BuiltinName,
+    vm::{
+        errors::{hint_errors::HintError, memory_errors::MemoryError},
+        runners::cairo_runner::ExecutionResources,
+        vm_core::VirtualMachine,
+    },
+};
+use clap::Args;
+
+use crate::error::Error;
+
+/// A resource a run can be capped on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resource {
+    Steps,
+    Syscalls,
+    Builtin(BuiltinName),
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Resource::Steps => write!(f, "steps"),
+            Resource::Syscalls => write!(f, "syscalls"),
+            Resource::Builtin(builtin) => write!(f, "{} builtin", builtin.to_str()),
+        }
+    }
+}
+
+/// Cap on the instances of a builtin, parsed from `<builtin>=<count>`, e.g. `poseidon=100000`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuiltinLimit {
+    pub builtin: BuiltinName,
+    pub limit: usize,
+}
+
+impl FromStr for BuiltinLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, limit) = s
+            .split_once('=')
+            .ok_or_else(|| format!("invalid builtin limit `{}`, expected `<builtin>=<count>`", s))?;
+        let builtin = BuiltinName::from_str(name).ok_or_else(|| format!("unknown builtin `{}`", name))?;
+        let limit = limit.parse().map_err(|e| format!("invalid limit for builtin `{}`: {}", name, e))?;
+        Ok(Self { builtin, limit })
+    }
+}
+
+/// Hard ceilings for running untrusted modules. Every limit is optional, runs are unbounded by default.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ResourceBudget {
+    #[arg(long = "max-steps", help = "Abort the run once it executed this many Cairo steps")]
+    pub max_steps: Option<usize>,
+    #[arg(long = "max-syscalls", help = "Abort the run once the module issued this many syscalls")]
+    pub max_syscalls: Option<usize>,
+    #[arg(
+        long = "max-builtin",
+        value_name = "BUILTIN=COUNT",
+        help = "Abort the run once it used more instances of a builtin, checked at every hint and when the run finishes (repeatable)"
+    )]
+    pub max_builtins: Vec<BuiltinLimit>,
+}
+
+/// Counts the resources used by a run against its [`ResourceBudget`], backing the `ResourceTracker` of the hint processors.
+#[derive(Clone, Debug, Default)]
+pub struct BudgetTracker {
+    budget: ResourceBudget,
+    steps: usize,
+    syscalls: usize,
+    exceeded_builtin: Option<BuiltinLimit>,
+}
+
+impl BudgetTracker {
+    pub fn new(budget: ResourceBudget) -> Self {
+        Self {
+            budget,
+            steps: 0,
+            syscalls: 0,
+            exceeded_builtin: None,
+        }
+    }
+
+    pub fn consume_step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Whether the step budget is used up, which stops the VM before its next step.
+    pub fn consumed(&self) -> bool {
+        self.budget.max_steps.is_some_and(|max_steps| self.steps >= max_steps)
+    }
+
+    /// Counts a syscall, failing the hint once the syscall budget is exceeded.
+    pub fn consume_syscall(&mut self) -> Result<(), HintError> {
+        self.syscalls += 1;
+        match self.budget.max_syscalls {
+            Some(max_syscalls) if self.syscalls > max_syscalls => Err(HintError::CustomHint(
+                format!("syscall limit of {} exceeded", max_syscalls).into_boxed_str(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts the builtin instances used so far, failing the hint once a builtin cap is exceeded. Runs of Cairo code
+    /// without hints are only caught by [`Self::check_builtins`] once the run finishes.
+    pub fn consume_builtins(&mut self, vm: &mut VirtualMachine) -> Result<(), HintError> {
+        if self.budget.max_builtins.is_empty() {
+            return Ok(());
+        }
+
+        // Segment sizes are only computed once the run ends, so compute them for the count and reset them after
+        let computed = vm.segments.segment_used_sizes.is_some();
+        vm.segments.compute_effective_sizes();
+        let used = vm
+            .get_builtin_runners()
+            .iter()
+            .map(|runner| Ok((runner.name(), runner.get_used_instances(&vm.segments)?)))
+            .collect::<Result<Vec<_>, MemoryError>>();
+        if !computed {
+            vm.segments.segment_used_sizes = None;
+        }
+
+        for (builtin, used) in used? {
+            if let Some(limit) = self
+                .budget
+                .max_builtins
+                .iter()
+                .find(|limit| limit.builtin == builtin && used > limit.limit)
+            {
+                self.exceeded_builtin = Some(*limit);
+                return Err(HintError::CustomHint(
+                    format!("{} builtin limit of {} exceeded", builtin.to_str(), limit.limit).into_boxed_str(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The budget breach that aborted the run, if any. Call it when the run failed to tell a breach from other errors.
+    pub fn exceeded(&self) -> Option<Error> {
+        if let Some(BuiltinLimit { builtin, limit }) = self.exceeded_builtin {
+            return Some(Error::ResourceLimitExceeded {
+                resource: Resource::Builtin(builtin),
+                limit,
+            });
+        }
+        if let Some(max_syscalls) = self.budget.max_syscalls.filter(|max_syscalls| self.syscalls > *max_syscalls) {
+            return Some(Error::ResourceLimitExceeded {
+                resource: Resource::Syscalls,
+                limit: max_syscalls,
+            });
+        }
+        self.budget
+            .max_steps
+            .filter(|_| self.consumed())
+            .map(|max_steps| Error::ResourceLimitExceeded {
+                resource: Resource::Steps,
+                limit: max_steps,
+            })
+    }
+
+    /// Checks the builtin usage of a finished run against the builtin caps.
+    pub fn check_builtins(&self, resources: &ExecutionResources) -> Result<(), Error> {
+        for BuiltinLimit { builtin, limit } in &self.budget.max_builtins {
+            if resources.builtin_instance_counter.get(builtin).is_some_and(|used| used > limit) {
+                return Err(Error::ResourceLimitExceeded {
+                    resource: Resource::Builtin(*builtin),
+                    limit: *limit,
+                });
+            }
+        }
+        Ok(())
+    }
+}
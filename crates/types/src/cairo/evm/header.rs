@@ -8,7 +8,7 @@ use strum_macros::FromRepr;
 
 use crate::cairo::structs::Uint256;
 
-#[derive(FromRepr, Debug)]
+#[derive(FromRepr, Debug, Clone, Copy)]
 pub enum FunctionId {
     Parent = 0,
     Uncle = 1,
@@ -1,6 +1,6 @@
 use alloy::{
     consensus::{Receipt, ReceiptWithBloom, TxReceipt},
-    primitives::keccak256,
+    primitives::{keccak256, Address, B256},
     rpc::types::Log,
 };
 use alloy_rlp::{Decodable, Encodable};
@@ -42,6 +42,16 @@ impl CairoReceiptWithBloom {
         Self(<ReceiptWithBloom>::decode(&mut rlp).unwrap())
     }
 
+    /// Returns the indexes of the logs emitted by `address` whose first topic is `topic0`.
+    pub fn matching_logs(&self, address: Address, topic0: B256) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .logs()
+            .iter()
+            .enumerate()
+            .filter(move |(_, log)| log.address == address && log.data.topics().first() == Some(&topic0))
+            .map(|(log_index, _)| log_index)
+    }
+
     pub fn handle(&self, function_id: FunctionId, log_index: usize) -> Vec<Felt252> {
         match function_id {
             FunctionId::Address => <Uint256 as Into<[Felt252; 2]>>::into(self.0.logs().get(log_index).unwrap().address.into()).to_vec(),
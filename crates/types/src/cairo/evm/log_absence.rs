@@ -0,0 +1,37 @@
+use alloy::primitives::{Address, Bloom, BloomInput, B256};
+use cairo_vm::Felt252;
+use strum_macros::FromRepr;
+
+#[derive(FromRepr, Debug)]
+pub enum FunctionId {
+    NoMatchingLog = 0,
+}
+
+/// Chains on which the number of receipts of a block can be proven: every transaction uses gas and the gas used of
+/// the header is the cumulative gas used of the last receipt, so no receipt can follow the one reaching it.
+///
+/// Other chains, like the OP stack ones whose deposit transactions follow their own gas accounting, are rejected by
+/// the sound run as well, see `EvmLogAbsence.verify_transactions_count`.
+pub const RECEIPT_COUNT_CHAIN_IDS: [u128; 2] = [1, 11155111];
+
+/// Returns whether the number of receipts of a block of `chain_id` can be proven, see [`RECEIPT_COUNT_CHAIN_IDS`].
+pub fn supports_receipt_count(chain_id: u128) -> bool {
+    RECEIPT_COUNT_CHAIN_IDS.contains(&chain_id)
+}
+
+/// Returns whether a log emitted by `address` with `topic0` may be part of `bloom`.
+///
+/// A `false` result proves that no such log exists, a `true` result may be a false positive.
+pub fn bloom_may_contain(bloom: &Bloom, address: Address, topic0: B256) -> bool {
+    bloom.contains_input(BloomInput::Raw(address.as_slice())) && bloom.contains_input(BloomInput::Raw(topic0.as_slice()))
+}
+
+/// Rebuilds a bloom from the 16 big endian chunks of 128 bits returned for the bloom fields.
+pub fn bloom_from_chunks(chunks: &[Felt252]) -> Bloom {
+    Bloom::from_slice(
+        &chunks
+            .iter()
+            .flat_map(|chunk| chunk.to_bytes_be()[16..].to_vec())
+            .collect::<Vec<u8>>(),
+    )
+}
@@ -0,0 +1,43 @@
+use alloy::primitives::{BlockNumber, TxNumber};
+use cairo_vm::Felt252;
+use strum_macros::FromRepr;
+
+#[derive(FromRepr, Debug)]
+pub enum FunctionId {
+    Logs = 0,
+}
+
+/// Position of a log matched by a filter, returned to Cairo as `[block_number, transaction_index, log_index]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LogPosition {
+    pub block_number: BlockNumber,
+    pub transaction_index: TxNumber,
+    pub log_index: usize,
+}
+
+/// Result of a `LogsByFilter` call.
+///
+/// `transactions_counts` holds the number of receipts of every block of the range, zero when the header bloom rules
+/// the log out, so that Cairo can rescan each block and prove that no matching log was left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogFilterResult {
+    pub positions: Vec<LogPosition>,
+    pub transactions_counts: Vec<u64>,
+}
+
+impl LogFilterResult {
+    /// Flattens the result into the felts returned by the `LogsByFilter` call:
+    /// `[n_logs, n_logs * [block_number, transaction_index, log_index], transactions_counts]`.
+    pub fn to_felts(&self) -> Vec<Felt252> {
+        std::iter::once(Felt252::from(self.positions.len()))
+            .chain(self.positions.iter().flat_map(|position| {
+                [
+                    Felt252::from(position.block_number),
+                    Felt252::from(position.transaction_index),
+                    Felt252::from(position.log_index),
+                ]
+            }))
+            .chain(self.transactions_counts.iter().map(|count| Felt252::from(*count)))
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 pub mod account;
 pub mod header;
 pub mod log;
+pub mod log_absence;
+pub mod log_filter;
 pub mod receipt;
 pub mod storage;
 pub mod transaction;
@@ -2,8 +2,10 @@ use alloy::primitives::Bytes;
 use serde::{Deserialize, Serialize};
 
 pub mod bytecode;
+pub mod oracle;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum UnconstrainedStateValue {
     Bytecode(Bytes),
+    Oracle(oracle::OracleValue),
 }
@@ -0,0 +1,62 @@
+use cairo_vm::{
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+    Felt252,
+};
+use serde::{Deserialize, Serialize};
+use starknet_crypto::poseidon_hash_many;
+
+use crate::cairo::traits::CairoType;
+
+/// Prefixed to oracle memorizer keys so they never collide with bytecode keys in the unconstrained memorizer.
+pub const ORACLE_LABEL: Felt252 = Felt252::from_hex_unchecked("0x6f7261636c65"); // hex val of 'oracle'
+
+/// Identifies a single oracle query: the oracle selected by `oracle_id` applied to `input`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct OracleKey {
+    pub oracle_id: Felt252,
+    pub input: Vec<Felt252>,
+}
+
+impl OracleKey {
+    pub fn new(oracle_id: Felt252, input: Vec<Felt252>) -> Self {
+        Self { oracle_id, input }
+    }
+
+    pub fn hash(&self) -> Felt252 {
+        let mut params = Vec::with_capacity(self.input.len() + 2);
+        params.push(ORACLE_LABEL);
+        params.push(self.oracle_id);
+        params.extend_from_slice(&self.input);
+        poseidon_hash_many(&params)
+    }
+}
+
+/// Witness returned by an oracle, laid out in memory as `[len, values...]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct OracleValue(pub Vec<Felt252>);
+
+impl CairoType for OracleValue {
+    fn from_memory(vm: &VirtualMachine, address: Relocatable) -> Result<Self, MemoryError> {
+        let len = Self::n_fields(vm, address)? - 1;
+        Ok(Self(vm.get_integer_range((address + 1)?, len)?.into_iter().map(|e| *e).collect()))
+    }
+    fn to_memory(&self, vm: &mut VirtualMachine, address: Relocatable) -> Result<Relocatable, MemoryError> {
+        vm.insert_value((address + 0)?, self.0.len())?;
+        vm.load_data((address + 1)?, &self.0.iter().map(MaybeRelocatable::from).collect::<Vec<_>>())?;
+        Ok((address + (self.0.len() + 1))?)
+    }
+    fn n_fields(vm: &VirtualMachine, address: Relocatable) -> Result<usize, MemoryError> {
+        let len = *vm.get_integer((address + 0)?)?;
+        usize::try_from(len)
+            .ok()
+            .and_then(|len| len.checked_add(1))
+            .ok_or_else(|| MemoryError::ErrorRetrievingMessage(format!("invalid oracle value length {}", len).into()))
+    }
+}
+
+impl From<Vec<Felt252>> for OracleValue {
+    fn from(value: Vec<Felt252>) -> Self {
+        Self(value)
+    }
+}
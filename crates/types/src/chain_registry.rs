@@ -0,0 +1,285 @@
+use std::{env, fmt, future::Future, path::Path, sync::OnceLock};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::{
+    HashingFunction, ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID, OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID,
+    RPC_URL_ETHEREUM_MAINNET, RPC_URL_ETHEREUM_TESTNET, RPC_URL_OPTIMISM_MAINNET, RPC_URL_OPTIMISM_TESTNET, RPC_URL_STARKNET_MAINNET,
+    RPC_URL_STARKNET_TESTNET, STARKNET_MAINNET_CHAIN_ID, STARKNET_TESTNET_CHAIN_ID,
+};
+
+/// Environment variable holding the path of the chain registry config file.
+pub const CHAIN_REGISTRY_PATH: &str = "HDP_CHAIN_REGISTRY";
+
+static CHAIN_REGISTRY: OnceLock<ChainRegistry> = OnceLock::new();
+
+#[derive(Error, Debug)]
+pub enum ChainRegistryError {
+    #[error("Chain id {0:#x} is not present in the chain registry")]
+    UnknownChain(u128),
+    #[error("No rpc url configured for chain id {chain_id:#x}, set `rpc_url` or the `{env}` environment variable")]
+    MissingRpcUrl { chain_id: u128, env: String },
+    #[error("All rpc urls failed for chain id {chain_id:#x}: {message}")]
+    RpcUnavailable { chain_id: u128, message: String },
+    #[error("Chain registry has already been initialized")]
+    AlreadyInitialized,
+    #[error("Failed to read chain registry: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse chain registry: {0}")]
+    Parse(String),
+}
+
+/// Layout of a chain, selecting the call handler and verifier that serve it.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainLayout {
+    Evm,
+    Starknet,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainConfig {
+    #[serde(with = "chain_id")]
+    pub chain_id: u128,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub layout: ChainLayout,
+    /// Primary rpc url, takes precedence over `rpc_url_env`.
+    #[serde(default)]
+    pub rpc_url: Option<String>,
+    /// Environment variable the primary rpc url is read from when `rpc_url` is not set.
+    #[serde(default)]
+    pub rpc_url_env: Option<String>,
+    /// Rpc urls tried in order when the primary one fails.
+    #[serde(default)]
+    pub fallback_rpc_urls: Vec<String>,
+    /// Chain the MMR accumulating this chain is deployed on, defaults to the chain itself.
+    #[serde(default, with = "option_chain_id")]
+    pub mmr_deployment_chain_id: Option<u128>,
+    #[serde(default)]
+    pub mmr_hasher: HashingFunction,
+}
+
+impl ChainConfig {
+    fn builtin(chain_id: u128, name: &str, layout: ChainLayout, rpc_url_env: &str, mmr_deployment_chain_id: u128) -> Self {
+        Self {
+            chain_id,
+            name: Some(name.to_string()),
+            layout,
+            rpc_url: None,
+            rpc_url_env: Some(rpc_url_env.to_string()),
+            fallback_rpc_urls: Vec::new(),
+            mmr_deployment_chain_id: Some(mmr_deployment_chain_id),
+            mmr_hasher: HashingFunction::Poseidon,
+        }
+    }
+
+    /// Returns the primary rpc url followed by the fallback urls.
+    pub fn rpc_urls(&self) -> Result<Vec<String>, ChainRegistryError> {
+        let primary = match (&self.rpc_url, &self.rpc_url_env) {
+            (Some(url), _) => Some(url.clone()),
+            (None, Some(var)) => env::var(var).ok().filter(|url| !url.is_empty()),
+            (None, None) => None,
+        };
+
+        let urls: Vec<String> = primary.into_iter().chain(self.fallback_rpc_urls.iter().cloned()).collect();
+        if urls.is_empty() {
+            return Err(ChainRegistryError::MissingRpcUrl {
+                chain_id: self.chain_id,
+                env: self.rpc_url_env.clone().unwrap_or_default(),
+            });
+        }
+
+        Ok(urls)
+    }
+
+    pub fn mmr_deployment_chain_id(&self) -> u128 {
+        self.mmr_deployment_chain_id.unwrap_or(self.chain_id)
+    }
+}
+
+/// Maps chain ids to their rpc endpoints and MMR settings.
+///
+/// Loaded from a TOML or JSON file with a list of `chains`, falling back to the
+/// built-in Ethereum, Optimism and Starknet entries reading the `RPC_URL_*` env vars.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainRegistry {
+    pub chains: Vec<ChainConfig>,
+}
+
+impl Default for ChainRegistry {
+    fn default() -> Self {
+        Self {
+            chains: vec![
+                ChainConfig::builtin(
+                    ETHEREUM_MAINNET_CHAIN_ID,
+                    "ethereum-mainnet",
+                    ChainLayout::Evm,
+                    RPC_URL_ETHEREUM_MAINNET,
+                    ETHEREUM_MAINNET_CHAIN_ID,
+                ),
+                ChainConfig::builtin(
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                    "ethereum-sepolia",
+                    ChainLayout::Evm,
+                    RPC_URL_ETHEREUM_TESTNET,
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                ),
+                ChainConfig::builtin(
+                    OPTIMISM_MAINNET_CHAIN_ID,
+                    "optimism-mainnet",
+                    ChainLayout::Evm,
+                    RPC_URL_OPTIMISM_MAINNET,
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                ),
+                ChainConfig::builtin(
+                    OPTIMISM_TESTNET_CHAIN_ID,
+                    "optimism-sepolia",
+                    ChainLayout::Evm,
+                    RPC_URL_OPTIMISM_TESTNET,
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                ),
+                ChainConfig::builtin(
+                    STARKNET_MAINNET_CHAIN_ID,
+                    "starknet-mainnet",
+                    ChainLayout::Starknet,
+                    RPC_URL_STARKNET_MAINNET,
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                ),
+                ChainConfig::builtin(
+                    STARKNET_TESTNET_CHAIN_ID,
+                    "starknet-sepolia",
+                    ChainLayout::Starknet,
+                    RPC_URL_STARKNET_TESTNET,
+                    ETHEREUM_TESTNET_CHAIN_ID,
+                ),
+            ],
+        }
+    }
+}
+
+impl ChainRegistry {
+    /// Reads a registry from `path`, parsed as TOML when the extension is `.toml` and as JSON otherwise.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ChainRegistryError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(|e| ChainRegistryError::Parse(e.to_string())),
+            _ => serde_json::from_str(&content).map_err(|e| ChainRegistryError::Parse(e.to_string())),
+        }
+    }
+
+    /// Returns the process wide registry, initializing it with the built-in chains if
+    /// [`ChainRegistry::init_global`] was not called.
+    pub fn global() -> &'static Self {
+        CHAIN_REGISTRY.get_or_init(Self::default)
+    }
+
+    pub fn init_global(registry: Self) -> Result<(), ChainRegistryError> {
+        CHAIN_REGISTRY.set(registry).map_err(|_| ChainRegistryError::AlreadyInitialized)
+    }
+
+    /// Initializes the process wide registry from `path`, or from the file named by the
+    /// `HDP_CHAIN_REGISTRY` env var. Keeps the built-in chains when neither is given.
+    pub fn init_global_from(path: Option<&Path>) -> Result<(), ChainRegistryError> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| env::var(CHAIN_REGISTRY_PATH).ok().map(Into::into));
+        match path {
+            Some(path) => Self::init_global(Self::from_file(path)?),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get(&self, chain_id: u128) -> Result<&ChainConfig, ChainRegistryError> {
+        self.chains
+            .iter()
+            .find(|chain| chain.chain_id == chain_id)
+            .ok_or(ChainRegistryError::UnknownChain(chain_id))
+    }
+
+    pub fn layout(&self, chain_id: u128) -> Result<ChainLayout, ChainRegistryError> {
+        Ok(self.get(chain_id)?.layout)
+    }
+
+    pub fn rpc_urls(&self, chain_id: u128) -> Result<Vec<String>, ChainRegistryError> {
+        self.get(chain_id)?.rpc_urls()
+    }
+
+    pub fn chain_ids(&self, layout: ChainLayout) -> impl Iterator<Item = u128> + '_ {
+        self.chains
+            .iter()
+            .filter(move |chain| chain.layout == layout)
+            .map(|chain| chain.chain_id)
+    }
+
+    /// Runs `f` against the rpc urls of `chain_id` in order, returning the first success.
+    pub async fn with_rpc_fallback<T, E, F, Fut>(&self, chain_id: u128, mut f: F) -> Result<T, ChainRegistryError>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: fmt::Display,
+    {
+        let mut errors = Vec::new();
+        for rpc_url in self.rpc_urls(chain_id)? {
+            match f(rpc_url).await {
+                Ok(value) => return Ok(value),
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        Err(ChainRegistryError::RpcUnavailable {
+            chain_id,
+            message: errors.join("; "),
+        })
+    }
+}
+
+/// Chain ids are serialized as hex strings, as some exceed the integer range of TOML and JSON.
+mod chain_id {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub(super) enum RawChainId {
+        Number(u64),
+        String(String),
+    }
+
+    impl RawChainId {
+        pub(super) fn parse<E: serde::de::Error>(self) -> Result<u128, E> {
+            match self {
+                RawChainId::Number(n) => Ok(n as u128),
+                RawChainId::String(s) => match s.strip_prefix("0x") {
+                    Some(hex) => u128::from_str_radix(hex, 16),
+                    None => s.parse::<u128>(),
+                }
+                .map_err(|e| E::custom(format!("invalid chain id {}: {}", s, e))),
+            }
+        }
+    }
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:#x}", value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        RawChainId::deserialize(deserializer)?.parse()
+    }
+}
+
+mod option_chain_id {
+    use super::{chain_id::RawChainId, *};
+
+    pub fn serialize<S: Serializer>(value: &Option<u128>, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&format!("{:#x}", value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u128>, D::Error> {
+        Option::<RawChainId>::deserialize(deserializer)?.map(RawChainId::parse).transpose()
+    }
+}
@@ -1,3 +1,4 @@
+use alloy::primitives::B256;
 use cairo_vm::{
     air_public_input::PublicInputError,
     cairo_run::EncodeTraceError,
@@ -7,6 +8,8 @@ use cairo_vm::{
 };
 use thiserror::Error;
 
+use crate::{budget::Resource, chain_registry::ChainRegistryError};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Invalid arguments")]
@@ -45,4 +48,22 @@ pub enum Error {
     IllegalInputValue,
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    ChainRegistry(#[from] ChainRegistryError),
+    #[error("Rpc fixture error: {0}")]
+    RpcFixture(String),
+    #[error("Results tree root {computed} does not match the program output root {expected}")]
+    ResultsRootMismatch { expected: B256, computed: B256 },
+    #[error("Run exceeded the {resource} limit of {limit}")]
+    ResourceLimitExceeded { resource: Resource, limit: usize },
+    #[error("Failed to prove the run: {0}")]
+    Prove(String),
+    #[error("Proof verification failed: {0}")]
+    ProofVerification(String),
+    #[error("The public output of the proof does not match the expected HDP output")]
+    ProofOutputMismatch,
+    #[error("The proven program hash {proven} does not match the HDP program hash {expected}")]
+    ProgramHashMismatch { expected: Felt252, proven: Felt252 },
+    #[error("Malformed HDP output: {0}")]
+    MalformedOutput(&'static str),
 }
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use alloy::primitives::BlockNumber;
 use cairo_vm::{
     types::relocatable::Relocatable,
@@ -45,6 +47,13 @@ pub struct Key {
     pub block_number: BlockNumber,
 }
 
+impl Key {
+    /// Returns the header key of every block in `blocks` of the chain, in ascending order.
+    pub fn range(chain_id: u128, blocks: RangeInclusive<BlockNumber>) -> impl Iterator<Item = Self> {
+        blocks.map(move |block_number| Self { chain_id, block_number })
+    }
+}
+
 impl ChainIdentifiable for Key {
     fn chain_id(&self) -> u128 {
         self.chain_id
@@ -99,3 +108,12 @@ impl TryFrom<CairoKey> for Key {
         })
     }
 }
+
+impl From<Key> for CairoKey {
+    fn from(value: Key) -> Self {
+        Self {
+            chain_id: Felt252::from(value.chain_id),
+            block_number: Felt252::from(value.block_number),
+        }
+    }
+}
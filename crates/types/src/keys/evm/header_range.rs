@@ -0,0 +1,78 @@
+use alloy::primitives::BlockNumber;
+use cairo_vm::{
+    types::relocatable::Relocatable,
+    vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+    Felt252,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{header, ChainIdentifiable, KeyError};
+use crate::cairo::traits::CairoType;
+
+#[derive(Debug, Clone)]
+pub struct CairoKey {
+    chain_id: Felt252,
+    from_block: Felt252,
+    to_block: Felt252,
+}
+
+impl CairoType for CairoKey {
+    fn from_memory(vm: &VirtualMachine, address: Relocatable) -> Result<Self, MemoryError> {
+        Ok(Self {
+            chain_id: *vm.get_integer((address + 0)?)?,
+            from_block: *vm.get_integer((address + 1)?)?,
+            to_block: *vm.get_integer((address + 2)?)?,
+        })
+    }
+    fn to_memory(&self, vm: &mut VirtualMachine, address: Relocatable) -> Result<Relocatable, MemoryError> {
+        vm.insert_value((address + 0)?, self.chain_id)?;
+        vm.insert_value((address + 1)?, self.from_block)?;
+        vm.insert_value((address + 2)?, self.to_block)?;
+        Ok((address + 3)?)
+    }
+    fn n_fields(_vm: &VirtualMachine, _address: Relocatable) -> Result<usize, MemoryError> {
+        Ok(3)
+    }
+}
+
+/// Inclusive range of blocks `[from_block, to_block]` of a chain.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub chain_id: u128,
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+}
+
+impl Key {
+    /// Returns the header key of every block in the range, in ascending order.
+    pub fn header_keys(&self) -> impl Iterator<Item = header::Key> {
+        header::Key::range(self.chain_id, self.from_block..=self.to_block)
+    }
+}
+
+impl ChainIdentifiable for Key {
+    fn chain_id(&self) -> u128 {
+        self.chain_id
+    }
+}
+
+impl TryFrom<CairoKey> for Key {
+    type Error = KeyError;
+    fn try_from(value: CairoKey) -> Result<Self, Self::Error> {
+        let key = Self {
+            chain_id: value.chain_id.try_into().map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            from_block: value
+                .from_block
+                .try_into()
+                .map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            to_block: value.to_block.try_into().map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+        };
+        if key.from_block > key.to_block {
+            return Err(KeyError::ConversionError(format!(
+                "empty block range {}..={}",
+                key.from_block, key.to_block
+            )));
+        }
+        Ok(key)
+    }
+}
@@ -0,0 +1,105 @@
+use alloy::primitives::{Address, BlockNumber, B256, U256};
+use cairo_vm::{
+    types::relocatable::Relocatable,
+    vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+    Felt252,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{header, receipt, ChainIdentifiable, KeyError};
+use crate::cairo::traits::CairoType;
+
+#[derive(Debug, Clone)]
+pub struct CairoKey {
+    chain_id: Felt252,
+    block_number: Felt252,
+    address: Felt252,
+    topic0_low: Felt252,
+    topic0_high: Felt252,
+}
+
+impl CairoType for CairoKey {
+    fn from_memory(vm: &VirtualMachine, ptr: Relocatable) -> Result<Self, MemoryError> {
+        Ok(Self {
+            chain_id: *vm.get_integer((ptr + 0)?)?,
+            block_number: *vm.get_integer((ptr + 1)?)?,
+            address: *vm.get_integer((ptr + 2)?)?,
+            topic0_low: *vm.get_integer((ptr + 3)?)?,
+            topic0_high: *vm.get_integer((ptr + 4)?)?,
+        })
+    }
+
+    fn to_memory(&self, vm: &mut VirtualMachine, address: Relocatable) -> Result<Relocatable, MemoryError> {
+        vm.insert_value((address + 0)?, self.chain_id)?;
+        vm.insert_value((address + 1)?, self.block_number)?;
+        vm.insert_value((address + 2)?, self.address)?;
+        vm.insert_value((address + 3)?, self.topic0_low)?;
+        vm.insert_value((address + 4)?, self.topic0_high)?;
+        Ok((address + 5)?)
+    }
+
+    fn n_fields(_vm: &VirtualMachine, _address: Relocatable) -> Result<usize, MemoryError> {
+        Ok(5)
+    }
+}
+
+/// Selects the logs of `block_number` emitted by `address` with `topic0`, which must not exist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub chain_id: u128,
+    pub block_number: BlockNumber,
+    pub address: Address,
+    pub topic0: B256,
+}
+
+impl Key {
+    pub fn header_key(&self) -> header::Key {
+        header::Key {
+            chain_id: self.chain_id,
+            block_number: self.block_number,
+        }
+    }
+
+    /// Returns the receipt key of the first `transactions_count` transactions of the block.
+    pub fn receipt_keys(&self, transactions_count: u64) -> impl Iterator<Item = receipt::Key> + '_ {
+        (0..transactions_count).map(move |transaction_index| receipt::Key {
+            chain_id: self.chain_id,
+            block_number: self.block_number,
+            transaction_index,
+        })
+    }
+}
+
+impl ChainIdentifiable for Key {
+    fn chain_id(&self) -> u128 {
+        self.chain_id
+    }
+}
+
+impl TryFrom<CairoKey> for Key {
+    type Error = KeyError;
+    fn try_from(value: CairoKey) -> Result<Self, Self::Error> {
+        let address_bytes = value.address.to_bytes_be();
+        if address_bytes[..12].iter().any(|byte| *byte != 0) {
+            return Err(KeyError::ConversionError(format!("invalid address {:#x}", value.address)));
+        }
+        let topic0_low: u128 = value
+            .topic0_low
+            .try_into()
+            .map_err(|e| KeyError::ConversionError(format!("{}", e)))?;
+        let topic0_high: u128 = value
+            .topic0_high
+            .try_into()
+            .map_err(|e| KeyError::ConversionError(format!("{}", e)))?;
+
+        Ok(Self {
+            chain_id: value.chain_id.try_into().map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            block_number: value
+                .block_number
+                .try_into()
+                .map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            address: Address::from_slice(&address_bytes[12..]),
+            topic0: B256::from((U256::from(topic0_high) << 128) | U256::from(topic0_low)),
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use alloy::primitives::{Address, BlockNumber, B256, U256};
+use cairo_vm::{
+    types::relocatable::Relocatable,
+    vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+    Felt252,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{header, receipt, ChainIdentifiable, KeyError};
+use crate::cairo::traits::CairoType;
+
+#[derive(Debug, Clone)]
+pub struct CairoKey {
+    chain_id: Felt252,
+    from_block: Felt252,
+    to_block: Felt252,
+    address: Felt252,
+    topic0_low: Felt252,
+    topic0_high: Felt252,
+}
+
+impl CairoType for CairoKey {
+    fn from_memory(vm: &VirtualMachine, ptr: Relocatable) -> Result<Self, MemoryError> {
+        Ok(Self {
+            chain_id: *vm.get_integer((ptr + 0)?)?,
+            from_block: *vm.get_integer((ptr + 1)?)?,
+            to_block: *vm.get_integer((ptr + 2)?)?,
+            address: *vm.get_integer((ptr + 3)?)?,
+            topic0_low: *vm.get_integer((ptr + 4)?)?,
+            topic0_high: *vm.get_integer((ptr + 5)?)?,
+        })
+    }
+
+    fn to_memory(&self, vm: &mut VirtualMachine, address: Relocatable) -> Result<Relocatable, MemoryError> {
+        vm.insert_value((address + 0)?, self.chain_id)?;
+        vm.insert_value((address + 1)?, self.from_block)?;
+        vm.insert_value((address + 2)?, self.to_block)?;
+        vm.insert_value((address + 3)?, self.address)?;
+        vm.insert_value((address + 4)?, self.topic0_low)?;
+        vm.insert_value((address + 5)?, self.topic0_high)?;
+        Ok((address + 6)?)
+    }
+
+    fn n_fields(_vm: &VirtualMachine, _address: Relocatable) -> Result<usize, MemoryError> {
+        Ok(6)
+    }
+}
+
+/// Selects the logs emitted by `address` with `topic0` in the inclusive block range `[from_block, to_block]`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub chain_id: u128,
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+    pub address: Address,
+    pub topic0: B256,
+}
+
+impl Key {
+    /// Returns the header key of every block in the range, in ascending order.
+    pub fn header_keys(&self) -> impl Iterator<Item = header::Key> {
+        header::Key::range(self.chain_id, self.from_block..=self.to_block)
+    }
+
+    /// Returns the receipt key of the first `transactions_count` transactions of `block_number`.
+    pub fn receipt_keys(&self, block_number: BlockNumber, transactions_count: u64) -> impl Iterator<Item = receipt::Key> + '_ {
+        (0..transactions_count).map(move |transaction_index| receipt::Key {
+            chain_id: self.chain_id,
+            block_number,
+            transaction_index,
+        })
+    }
+}
+
+impl ChainIdentifiable for Key {
+    fn chain_id(&self) -> u128 {
+        self.chain_id
+    }
+}
+
+impl TryFrom<CairoKey> for Key {
+    type Error = KeyError;
+    fn try_from(value: CairoKey) -> Result<Self, Self::Error> {
+        let address_bytes = value.address.to_bytes_be();
+        if address_bytes[..12].iter().any(|byte| *byte != 0) {
+            return Err(KeyError::ConversionError(format!("invalid address {:#x}", value.address)));
+        }
+        let topic0_low: u128 = value
+            .topic0_low
+            .try_into()
+            .map_err(|e| KeyError::ConversionError(format!("{}", e)))?;
+        let topic0_high: u128 = value
+            .topic0_high
+            .try_into()
+            .map_err(|e| KeyError::ConversionError(format!("{}", e)))?;
+
+        let key = Self {
+            chain_id: value.chain_id.try_into().map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            from_block: value
+                .from_block
+                .try_into()
+                .map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            to_block: value.to_block.try_into().map_err(|e| KeyError::ConversionError(format!("{}", e)))?,
+            address: Address::from_slice(&address_bytes[12..]),
+            topic0: B256::from((U256::from(topic0_high) << 128) | U256::from(topic0_low)),
+        };
+        if key.from_block > key.to_block {
+            return Err(KeyError::ConversionError(format!(
+                "empty block range {}..={}",
+                key.from_block, key.to_block
+            )));
+        }
+        Ok(key)
+    }
+}
@@ -73,3 +73,13 @@ impl TryFrom<CairoKey> for Key {
         })
     }
 }
+
+impl From<Key> for CairoKey {
+    fn from(value: Key) -> Self {
+        Self {
+            chain_id: Felt252::from(value.chain_id),
+            block_number: Felt252::from(value.block_number),
+            transaction_index: Felt252::from(value.transaction_index),
+        }
+    }
+}
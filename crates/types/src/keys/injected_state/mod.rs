@@ -1,3 +1,4 @@
+pub mod delete;
 pub mod label;
 pub mod read;
 pub mod write;
@@ -1,7 +1,4 @@
-use crate::{
-    ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID, OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID, STARKNET_MAINNET_CHAIN_ID,
-    STARKNET_TESTNET_CHAIN_ID,
-};
+use crate::chain_registry::{ChainLayout, ChainRegistry, ChainRegistryError};
 
 pub mod evm;
 pub mod injected_state;
@@ -12,16 +9,13 @@ pub enum KeyType {
     STARKNET,
 }
 
-impl From<u128> for KeyType {
-    fn from(chain_id: u128) -> Self {
-        match chain_id {
-            STARKNET_MAINNET_CHAIN_ID => Self::STARKNET,
-            STARKNET_TESTNET_CHAIN_ID => Self::STARKNET,
-            ETHEREUM_MAINNET_CHAIN_ID => Self::EVM,
-            ETHEREUM_TESTNET_CHAIN_ID => Self::EVM,
-            OPTIMISM_MAINNET_CHAIN_ID => Self::EVM,
-            OPTIMISM_TESTNET_CHAIN_ID => Self::EVM,
-            _ => panic!("Unknown chain id: {}", chain_id),
+impl TryFrom<u128> for KeyType {
+    type Error = ChainRegistryError;
+
+    fn try_from(chain_id: u128) -> Result<Self, Self::Error> {
+        match ChainRegistry::global().layout(chain_id)? {
+            ChainLayout::Evm => Ok(Self::EVM),
+            ChainLayout::Starknet => Ok(Self::STARKNET),
         }
     }
 }
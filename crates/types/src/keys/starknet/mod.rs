@@ -1,26 +1,27 @@
 pub mod header;
 pub mod storage;
 
-use std::env;
-
 use thiserror::Error;
 
-use crate::{RPC_URL_STARKNET_MAINNET, RPC_URL_STARKNET_TESTNET, STARKNET_MAINNET_CHAIN_ID, STARKNET_TESTNET_CHAIN_ID};
+use crate::chain_registry::{ChainLayout, ChainRegistry, ChainRegistryError};
 
 #[derive(Error, Debug)]
 pub enum KeyError {
     #[error("Conversion Error: {0}")]
     ConversionError(String),
+    #[error(transparent)]
+    ChainRegistry(#[from] ChainRegistryError),
 }
 
 pub trait ChainIdentifiable {
     fn chain_id(&self) -> u128;
 }
 
+/// Returns the primary rpc url of the key's chain from the [`ChainRegistry`].
 pub fn get_corresponding_rpc_url<T: ChainIdentifiable>(key: &T) -> Result<String, KeyError> {
-    match key.chain_id() {
-        STARKNET_MAINNET_CHAIN_ID => Ok(env::var(RPC_URL_STARKNET_MAINNET).unwrap()),
-        STARKNET_TESTNET_CHAIN_ID => Ok(env::var(RPC_URL_STARKNET_TESTNET).unwrap()),
-        _ => Err(KeyError::ConversionError("Unsupported starknet chain id".into())),
+    let chain = ChainRegistry::global().get(key.chain_id())?;
+    if chain.layout != ChainLayout::Starknet {
+        return Err(KeyError::ConversionError("Unsupported starknet chain id".into()));
     }
+    Ok(chain.rpc_urls()?.remove(0))
 }
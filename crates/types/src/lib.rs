@@ -4,11 +4,15 @@
 #![forbid(unsafe_code)]
 #![feature(iter_next_chunk)]
 
+pub mod budget;
 pub mod cairo;
+pub mod chain_registry;
 pub mod error;
 pub mod keys;
 pub mod param;
 pub mod proofs;
+pub mod report;
+pub mod results;
 
 use std::{fmt, str::FromStr};
 
@@ -20,7 +24,7 @@ use proofs::{evm, injected_state::StateProofs, starknet};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
-use crate::cairo::unconstrained::UnconstrainedStateValue;
+use crate::{cairo::unconstrained::UnconstrainedStateValue, error::Error};
 
 pub const RPC_URL_ETHEREUM_MAINNET: &str = "RPC_URL_ETHEREUM_MAINNET";
 pub const RPC_URL_ETHEREUM_TESTNET: &str = "RPC_URL_ETHEREUM_TESTNET";
@@ -81,24 +85,17 @@ pub struct UnconstrainedState(pub HashMap<Felt252, UnconstrainedStateValue>);
 pub struct InjectedState(pub HashMap<Felt252, Felt252>);
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum ChainProofs {
-    EthereumMainnet(evm::Proofs),
-    EthereumSepolia(evm::Proofs),
-    OptimismMainnet(evm::Proofs),
-    OptimismSepolia(evm::Proofs),
-    StarknetMainnet(starknet::Proofs),
-    StarknetSepolia(starknet::Proofs),
+    Evm { chain_id: u128, proofs: evm::Proofs },
+    Starknet { chain_id: u128, proofs: starknet::Proofs },
 }
 
 impl ChainProofs {
     pub fn chain_id(&self) -> u128 {
         match self {
-            ChainProofs::EthereumMainnet(_) => 0x1,
-            ChainProofs::EthereumSepolia(_) => 0xaa36a7,
-            ChainProofs::OptimismMainnet(_) => 0xa,
-            ChainProofs::OptimismSepolia(_) => 0xaa37dc,
-            ChainProofs::StarknetMainnet(_) => 0x534e5f4d41494e,
-            ChainProofs::StarknetSepolia(_) => 0x534e5f5345504f4c4941,
+            ChainProofs::Evm { chain_id, .. } => *chain_id,
+            ChainProofs::Starknet { chain_id, .. } => *chain_id,
         }
     }
 }
@@ -232,39 +229,55 @@ pub struct HDPOutput {
 }
 
 impl FromIterator<Felt252> for HDPOutput {
+    /// Decodes an output written by the HDP program, see [`HDPOutput::try_from_felts`] for untrusted outputs.
     fn from_iter<T: IntoIterator<Item = Felt252>>(iter: T) -> Self {
+        Self::try_from_felts(iter).expect("malformed HDP output")
+    }
+}
+
+impl HDPOutput {
+    /// Decodes the program output, failing on an output that is short, has trailing words or invalid section lengths.
+    pub fn try_from_felts<T: IntoIterator<Item = Felt252>>(iter: T) -> Result<Self, Error> {
         let mut i = iter.into_iter();
+        let mut next = |missing: &'static str| i.next().ok_or(Error::MalformedOutput(missing));
 
         // Fixed 4 words
-        let task_hash_low = i.next().unwrap();
-        let task_hash_high = i.next().unwrap();
-        let output_tree_root_low = i.next().unwrap();
-        let output_tree_root_high = i.next().unwrap();
+        let task_hash_low = next("missing task_hash_low")?;
+        let task_hash_high = next("missing task_hash_high")?;
+        let output_tree_root_low = next("missing output_tree_root_low")?;
+        let output_tree_root_high = next("missing output_tree_root_high")?;
 
         // New mixed-layout header: [poseidon_len, keccak_len]
-        let poseidon_len_f = i.next().unwrap();
-        let keccak_len_f = i.next().unwrap();
+        let poseidon_len = next("missing poseidon_len")?;
+        let keccak_len = next("missing keccak_len")?;
 
-        // Convert Felt252 -> usize by reading the last 8 bytes (big-endian)
-        let felt_to_usize = |f: &Felt252| -> usize {
+        // Convert Felt252 -> usize, rejecting lengths that do not fit in a u64
+        let felt_to_usize = |f: &Felt252, out_of_range: &'static str| -> Result<usize, Error> {
             let bytes = f.to_bytes_be();
+            if bytes[..24].iter().any(|byte| *byte != 0) {
+                return Err(Error::MalformedOutput(out_of_range));
+            }
             let mut buf = [0u8; 8];
             buf.copy_from_slice(&bytes[24..32]);
-            u64::from_be_bytes(buf) as usize
+            Ok(u64::from_be_bytes(buf) as usize)
         };
-        let poseidon_len = felt_to_usize(&poseidon_len_f);
-        let keccak_len = felt_to_usize(&keccak_len_f);
+        let poseidon_len = felt_to_usize(&poseidon_len, "poseidon_len out of range")?;
+        let keccak_len = felt_to_usize(&keccak_len, "keccak_len out of range")?;
 
         // Poseidon section: poseidon_len * 4 felts
-        let mut mmr_metas = Vec::<MmrMetaOutput>::with_capacity(poseidon_len + keccak_len);
+        let mut mmr_metas = Vec::<MmrMetaOutput>::new();
         for _ in 0..poseidon_len {
-            let [id, size, chain_id, root] = i.next_chunk::<4>().expect("missing poseidon mmr_meta words");
+            let [id, size, chain_id, root] = i
+                .next_chunk::<4>()
+                .map_err(|_| Error::MalformedOutput("missing poseidon mmr_meta words"))?;
             mmr_metas.push(MmrMetaOutput::Poseidon { id, size, chain_id, root });
         }
 
         // Keccak section: keccak_len * 5 felts (id, size, chain_id, root_low, root_high)
         for _ in 0..keccak_len {
-            let [id, size, chain_id, root_low, root_high] = i.next_chunk::<5>().expect("missing keccak mmr_meta words");
+            let [id, size, chain_id, root_low, root_high] = i
+                .next_chunk::<5>()
+                .map_err(|_| Error::MalformedOutput("missing keccak mmr_meta words"))?;
             mmr_metas.push(MmrMetaOutput::Keccak {
                 id,
                 size,
@@ -274,17 +287,19 @@ impl FromIterator<Felt252> for HDPOutput {
             });
         }
 
-        Self {
+        if i.next().is_some() {
+            return Err(Error::MalformedOutput("trailing words after the mmr_metas"));
+        }
+
+        Ok(Self {
             task_hash_low,
             task_hash_high,
             output_tree_root_low,
             output_tree_root_high,
             mmr_metas,
-        }
+        })
     }
-}
 
-impl HDPOutput {
     pub fn to_felt_vec(&self) -> Vec<Felt252> {
         let mut felt_vec = vec![
             self.task_hash_low,
@@ -327,3 +342,51 @@ impl HDPOutput {
         felt_vec
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output() -> HDPOutput {
+        HDPOutput {
+            task_hash_low: Felt252::from(1),
+            task_hash_high: Felt252::from(2),
+            output_tree_root_low: Felt252::from(3),
+            output_tree_root_high: Felt252::from(4),
+            mmr_metas: vec![MmrMetaOutput::Keccak {
+                id: Felt252::from(5),
+                size: Felt252::from(6),
+                chain_id: Felt252::from(11155111),
+                root_low: Felt252::from(7),
+                root_high: Felt252::from(8),
+            }],
+        }
+    }
+
+    #[test]
+    fn output_round_trips_through_felts() {
+        assert_eq!(HDPOutput::try_from_felts(output().to_felt_vec()).unwrap(), output());
+    }
+
+    #[test]
+    fn short_output_fails_to_decode() {
+        let felts = output().to_felt_vec();
+        for len in 0..felts.len() {
+            assert!(HDPOutput::try_from_felts(felts[..len].to_vec()).is_err(), "decoded {} words", len);
+        }
+    }
+
+    #[test]
+    fn trailing_words_fail_to_decode() {
+        let mut felts = output().to_felt_vec();
+        felts.push(Felt252::ZERO);
+        assert!(HDPOutput::try_from_felts(felts).is_err());
+    }
+
+    #[test]
+    fn oversized_section_length_fails_to_decode() {
+        let mut felts = output().to_felt_vec();
+        felts[5] = Felt252::MAX;
+        assert!(HDPOutput::try_from_felts(felts).is_err());
+    }
+}
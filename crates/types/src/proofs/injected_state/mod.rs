@@ -10,7 +10,7 @@ use pathfinder_crypto::Felt;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cairo::{FELT_0, FELT_1},
+    cairo::{FELT_0, FELT_1, FELT_2},
     proofs::injected_state::leaf::TrieLeaf,
 };
 
@@ -20,6 +20,7 @@ pub type StateProofs = Vec<StateProof>;
 pub enum StateProof {
     Read(StateProofRead),
     Write(StateProofWrite),
+    Delete(StateProofDelete),
 }
 
 impl StateProof {
@@ -27,6 +28,7 @@ impl StateProof {
         match self {
             Self::Read(_) => FELT_0,
             Self::Write(_) => FELT_1,
+            Self::Delete(_) => FELT_2,
         }
     }
 }
@@ -50,6 +52,33 @@ pub struct StateProofWrite {
     pub leaf_post: TrieLeaf,
 }
 
+/// Removal of `leaf_prev.key`: `state_proof_prev` proves its inclusion under `trie_root_prev`
+/// and `state_proof_post` its non-inclusion under `trie_root_post`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct StateProofDelete {
+    pub trie_label: Felt,
+    pub trie_root_prev: Felt,
+    pub state_proof_prev: Vec<TrieNodeSerde>,
+    pub leaf_prev: TrieLeaf,
+    pub trie_root_post: Felt,
+    pub state_proof_post: Vec<TrieNodeSerde>,
+}
+
+/// A delete is verified as an update of the leaf to zero.
+impl From<StateProofDelete> for StateProofWrite {
+    fn from(value: StateProofDelete) -> Self {
+        Self {
+            trie_label: value.trie_label,
+            trie_root_prev: value.trie_root_prev,
+            state_proof_prev: value.state_proof_prev,
+            leaf_prev: value.leaf_prev,
+            trie_root_post: value.trie_root_post,
+            state_proof_post: value.state_proof_post,
+            leaf_post: TrieLeaf::empty(value.leaf_prev.key),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub enum TrieNodeSerde {
     Binary { left: Felt, right: Felt },
@@ -139,6 +168,7 @@ impl IntoIterator for CairoTrieNodeSerde {
 pub enum Action {
     Read(ActionRead),
     Write(ActionWrite),
+    Delete(ActionDelete),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,3 +186,11 @@ pub struct ActionWrite {
     pub key: Felt,
     pub value: Felt,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionDelete {
+    pub trie_label: Felt,
+    // Root hash before delete operation is applied
+    pub trie_root: Felt,
+    pub key: Felt,
+}
@@ -0,0 +1,93 @@
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
+
+use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// How `--print_output` writes the run report to stdout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// A single line of JSON, for tools parsing stdout.
+    Json,
+    /// Indented JSON, for humans.
+    #[default]
+    Pretty,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "pretty" => Ok(Self::Pretty),
+            _ => Err(format!("invalid output format `{}`, expected `json` or `pretty`", s)),
+        }
+    }
+}
+
+/// Resources used by the run, as reported by `CairoRunner::get_execution_resources`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExecutionResourcesReport {
+    pub n_steps: usize,
+    pub n_memory_holes: usize,
+    /// Instances used per builtin, keyed by builtin name.
+    pub builtin_instance_counter: BTreeMap<String, usize>,
+}
+
+/// Files written by the run, omitted when not requested.
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct RunArtifacts {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run_output: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proofs: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cairo_pie: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stwo_prover_input: Option<PathBuf>,
+}
+
+/// Machine readable summary of a dry run or sound run, printed to stdout with `--print_output`.
+#[derive(Clone, Debug, Serialize)]
+pub struct RunReport<O> {
+    pub output: O,
+    pub execution_resources: ExecutionResourcesReport,
+    /// Length of the relocated trace, padded in proof mode. Only known when the trace is enabled.
+    pub step_count: Option<usize>,
+    pub artifacts: RunArtifacts,
+}
+
+impl<O: Serialize> RunReport<O> {
+    pub fn new(output: O, cairo_runner: &CairoRunner, artifacts: RunArtifacts) -> Result<Self, Error> {
+        let resources = cairo_runner.get_execution_resources()?;
+
+        Ok(Self {
+            output,
+            execution_resources: ExecutionResourcesReport {
+                n_steps: resources.n_steps,
+                n_memory_holes: resources.n_memory_holes,
+                builtin_instance_counter: resources
+                    .builtin_instance_counter
+                    .into_iter()
+                    .map(|(builtin, count)| (builtin.to_str().to_string(), count))
+                    .collect(),
+            },
+            step_count: cairo_runner.relocated_trace.as_ref().map(|trace| trace.len()),
+            artifacts,
+        })
+    }
+
+    /// Prints the report as the only line(s) on stdout, logs go to stderr.
+    pub fn print(&self, format: OutputFormat) -> Result<(), Error> {
+        let report = match format {
+            OutputFormat::Json => serde_json::to_string(self)?,
+            OutputFormat::Pretty => serde_json::to_string_pretty(self)?,
+        };
+        println!("{}", report);
+        Ok(())
+    }
+}
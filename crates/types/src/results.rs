@@ -0,0 +1,125 @@
+use alloy::primitives::{keccak256, B256};
+use cairo_vm::Felt252;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, HDPOutput};
+
+/// Root the program outputs when the module returns no values, `keccak(0)`.
+pub const EMPTY_RESULTS_ROOT: B256 = B256::new([
+    0xbc, 0x36, 0x78, 0x9e, 0x7a, 0x1e, 0x28, 0x14, 0x36, 0x46, 0x42, 0x29, 0x82, 0x8f, 0x81, 0x7d, 0x66, 0x12, 0xf7, 0xb4, 0x77, 0xd6,
+    0x65, 0x91, 0xff, 0x96, 0xa9, 0xe0, 0x64, 0xbc, 0xc9, 0x8a,
+]);
+
+/// A value returned by the module, its leaf in the results tree and the inclusion path of that leaf.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ResultLeaf {
+    pub index: usize,
+    pub value: Felt252,
+    pub leaf: B256,
+    /// Sibling hashes from the leaf up to the root, hashed as sorted pairs.
+    pub proof: Vec<B256>,
+}
+
+impl ResultLeaf {
+    /// Recomputes the root from the leaf and its proof, as an OpenZeppelin `MerkleProof.verify` would.
+    pub fn verify(&self, root: B256) -> bool {
+        self.proof.iter().fold(self.leaf, |node, sibling| hash_pair(node, *sibling)) == root
+    }
+}
+
+/// The values returned by the module and the results tree committed to by `output_tree_root`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HDPResults {
+    pub task_hash: B256,
+    pub output_tree_root: B256,
+    pub results: Vec<Felt252>,
+    pub leaves: Vec<ResultLeaf>,
+}
+
+impl HDPResults {
+    /// Rebuilds the results tree of `src/utils/merkle.cairo` from the module return values and checks its root
+    /// against the one written to the program output.
+    pub fn new(output: &HDPOutput, results: Vec<Felt252>) -> Result<Self, Error> {
+        let task_hash = uint256_to_b256(output.task_hash_low, output.task_hash_high);
+        let output_tree_root = uint256_to_b256(output.output_tree_root_low, output.output_tree_root_high);
+
+        let (root, leaves) = results_tree(&results);
+        if root != output_tree_root {
+            return Err(Error::ResultsRootMismatch {
+                expected: output_tree_root,
+                computed: root,
+            });
+        }
+
+        Ok(Self {
+            task_hash,
+            output_tree_root,
+            results,
+            leaves,
+        })
+    }
+}
+
+/// Builds the tree the same way `compute_merkle_root` does: leaves are double keccak hashes of the little endian
+/// result, stored right to left at the end of the array, and parents hash their children as sorted pairs.
+fn results_tree(results: &[Felt252]) -> (B256, Vec<ResultLeaf>) {
+    match results {
+        [] => (EMPTY_RESULTS_ROOT, vec![]),
+        // A single result is hashed once and its digest is output without the endianness swap, so the leaf is the root
+        [value] => {
+            let mut root = keccak256(value.to_bytes_le());
+            root.reverse();
+            let leaf = ResultLeaf {
+                index: 0,
+                value: *value,
+                leaf: root,
+                proof: vec![],
+            };
+            (root, vec![leaf])
+        }
+        _ => {
+            let tree_len = 2 * results.len() - 1;
+            let mut tree = vec![B256::ZERO; tree_len];
+            for (index, value) in results.iter().enumerate() {
+                tree[tree_len - 1 - index] = keccak256(keccak256(value.to_bytes_le()));
+            }
+            for node in (0..tree_len - results.len()).rev() {
+                tree[node] = hash_pair(tree[2 * node + 1], tree[2 * node + 2]);
+            }
+
+            let leaves = results
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let mut position = tree_len - 1 - index;
+                    let mut proof = vec![];
+                    while position > 0 {
+                        let sibling = if position % 2 == 1 { position + 1 } else { position - 1 };
+                        proof.push(tree[sibling]);
+                        position = (position - 1) / 2;
+                    }
+                    ResultLeaf {
+                        index,
+                        value: *value,
+                        leaf: tree[tree_len - 1 - index],
+                        proof,
+                    }
+                })
+                .collect();
+
+            (tree[0], leaves)
+        }
+    }
+}
+
+fn hash_pair(a: B256, b: B256) -> B256 {
+    let (left, right) = if a < b { (a, b) } else { (b, a) };
+    keccak256([left.as_slice(), right.as_slice()].concat())
+}
+
+fn uint256_to_b256(low: Felt252, high: Felt252) -> B256 {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&high.to_bytes_be()[16..]);
+    bytes[16..].copy_from_slice(&low.to_bytes_be()[16..]);
+    B256::new(bytes)
+}
@@ -2,7 +2,10 @@ pub mod account;
 pub mod block_receipt;
 pub mod block_tx;
 pub mod header;
+pub mod header_range;
 pub mod log;
+pub mod log_absence;
+pub mod log_filter;
 pub mod storage;
 
 pub const ETHEREUM_MAINNET_CHAIN_ID: felt252 = 0x1;
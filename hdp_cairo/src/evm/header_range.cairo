@@ -0,0 +1,69 @@
+use hdp_cairo::EvmMemorizer;
+use starknet::SyscallResultTrait;
+use starknet::syscalls::call_contract_syscall;
+
+const HEADER_RANGE: felt252 = 6;
+
+const HEADER_RANGE_GET_PARENT: felt252 = 0;
+const HEADER_RANGE_GET_STATE_ROOT: felt252 = 3;
+const HEADER_RANGE_GET_RECEIPT_ROOT: felt252 = 5;
+const HEADER_RANGE_GET_GAS_LIMIT: felt252 = 9;
+const HEADER_RANGE_GET_GAS_USED: felt252 = 10;
+const HEADER_RANGE_GET_TIMESTAMP: felt252 = 11;
+const HEADER_RANGE_GET_BASE_FEE_PER_GAS: felt252 = 15;
+
+#[derive(Serde, Drop)]
+pub struct HeaderRangeKey {
+    pub chain_id: felt252,
+    pub from_block: felt252,
+    pub to_block: felt252,
+}
+
+#[generate_trait]
+pub impl HeaderRangeImpl of HeaderRangeTrait {
+    fn header_range_get_parent(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_PARENT)
+    }
+    fn header_range_get_state_root(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_STATE_ROOT)
+    }
+    fn header_range_get_receipt_root(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_RECEIPT_ROOT)
+    }
+    fn header_range_get_gas_limit(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_GAS_LIMIT)
+    }
+    fn header_range_get_gas_used(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_GAS_USED)
+    }
+    fn header_range_get_timestamp(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_TIMESTAMP)
+    }
+    fn header_range_get_base_fee_per_gas(self: @EvmMemorizer, key: @HeaderRangeKey) -> Array<u256> {
+        self.header_range_get_field(key, HEADER_RANGE_GET_BASE_FEE_PER_GAS)
+    }
+
+    /// Returns `field` (one of the single word header fields) of every block in the range.
+    fn header_range_get_field(self: @EvmMemorizer, key: @HeaderRangeKey, field: felt252) -> Array<u256> {
+        let mut result = self.call_memorizer(field, key);
+        let mut values = array![];
+        while let Option::Some(low) = result.pop_front() {
+            let high = result.pop_front().unwrap();
+            values.append(u256 { low: (*low).try_into().unwrap(), high: (*high).try_into().unwrap() });
+        };
+        values
+    }
+
+    fn call_memorizer(self: @EvmMemorizer, selector: felt252, key: @HeaderRangeKey) -> Span<felt252> {
+        call_contract_syscall(
+            HEADER_RANGE.try_into().unwrap(),
+            selector,
+            array![
+                *self.dict.segment_index, *self.dict.offset, *key.chain_id, *key.from_block,
+                *key.to_block,
+            ]
+                .span(),
+        )
+            .unwrap_syscall()
+    }
+}
@@ -0,0 +1,38 @@
+use hdp_cairo::EvmMemorizer;
+use starknet::SyscallResultTrait;
+use starknet::syscalls::call_contract_syscall;
+
+const LOG_ABSENCE: felt252 = 8;
+
+const LOG_ABSENCE_NO_MATCHING_LOG: felt252 = 0;
+
+#[derive(Serde, Drop)]
+pub struct LogAbsenceKey {
+    pub chain_id: felt252,
+    pub block_number: felt252,
+    pub address: felt252,
+    pub topic0: u256,
+}
+
+#[generate_trait]
+pub impl LogAbsenceImpl of LogAbsenceTrait {
+    /// Proves that no log of the block was emitted by `address` with `topic0`.
+    /// Fails when such a log exists.
+    fn assert_no_matching_log(self: @EvmMemorizer, key: @LogAbsenceKey) {
+        self.call_memorizer(LOG_ABSENCE_NO_MATCHING_LOG, key);
+    }
+
+    fn call_memorizer(self: @EvmMemorizer, selector: felt252, key: @LogAbsenceKey) -> Span<felt252> {
+        let topic0 = *key.topic0;
+        call_contract_syscall(
+            LOG_ABSENCE.try_into().unwrap(),
+            selector,
+            array![
+                *self.dict.segment_index, *self.dict.offset, *key.chain_id, *key.block_number,
+                *key.address, topic0.low.into(), topic0.high.into(),
+            ]
+                .span(),
+        )
+            .unwrap_syscall()
+    }
+}
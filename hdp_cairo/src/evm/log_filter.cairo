@@ -0,0 +1,56 @@
+use hdp_cairo::EvmMemorizer;
+use hdp_cairo::evm::log::LogKey;
+use starknet::SyscallResultTrait;
+use starknet::syscalls::call_contract_syscall;
+
+const LOGS_BY_FILTER: felt252 = 7;
+
+const LOGS_BY_FILTER_GET_LOGS: felt252 = 0;
+
+#[derive(Serde, Drop)]
+pub struct LogFilterKey {
+    pub chain_id: felt252,
+    pub from_block: felt252,
+    pub to_block: felt252,
+    pub address: felt252,
+    pub topic0: u256,
+}
+
+#[generate_trait]
+pub impl LogFilterImpl of LogFilterTrait {
+    /// Returns the keys of the logs emitted by `address` with `topic0` in the block range, in
+    /// ascending order.
+    fn logs_by_filter(self: @EvmMemorizer, key: @LogFilterKey) -> Array<LogKey> {
+        let mut result = self.call_memorizer(LOGS_BY_FILTER_GET_LOGS, key);
+        let mut n_logs = *result.pop_front().unwrap();
+        let mut logs = array![];
+        // The receipt counts following the positions are only used to verify the result
+        while n_logs != 0 {
+            logs
+                .append(
+                    LogKey {
+                        chain_id: *key.chain_id,
+                        block_number: *result.pop_front().unwrap(),
+                        transaction_index: *result.pop_front().unwrap(),
+                        log_index: *result.pop_front().unwrap(),
+                    },
+                );
+            n_logs -= 1;
+        };
+        logs
+    }
+
+    fn call_memorizer(self: @EvmMemorizer, selector: felt252, key: @LogFilterKey) -> Span<felt252> {
+        let topic0 = *key.topic0;
+        call_contract_syscall(
+            LOGS_BY_FILTER.try_into().unwrap(),
+            selector,
+            array![
+                *self.dict.segment_index, *self.dict.offset, *key.chain_id, *key.from_block,
+                *key.to_block, *key.address, topic0.low.into(), topic0.high.into(),
+            ]
+                .span(),
+        )
+            .unwrap_syscall()
+    }
+}
@@ -7,6 +7,7 @@ const INJECTED_STATE_CONTRACT_ADDRESS: felt252 = 'injected_state';
 const LABEL: felt252 = 0;
 const READ: felt252 = 1;
 const WRITE: felt252 = 2;
+const DELETE: felt252 = 3;
 
 #[generate_trait]
 pub impl InjectedStateMemorizerImpl of InjectedStateMemorizerTrait {
@@ -55,4 +56,15 @@ pub impl InjectedStateMemorizerImpl of InjectedStateMemorizerTrait {
             .unwrap_syscall();
         *ret_data.at(0)
     }
+
+    // @notice Removes the key from the trie and returns the updated trie root
+    // @dev Fails if the key is not set
+    fn delete_key(self: @InjectedStateMemorizer, label: felt252, key: felt252) -> felt252 {
+        let calldata = array![*self.dict.segment_index, *self.dict.offset, label, key];
+        let ret_data = call_contract_syscall(
+            INJECTED_STATE_CONTRACT_ADDRESS.try_into().unwrap(), DELETE, calldata.span(),
+        )
+            .unwrap_syscall();
+        *ret_data.at(0)
+    }
 }
@@ -0,0 +1,67 @@
+use core::dict::{Felt252Dict, Felt252DictTrait};
+use core::math::u256_mul_mod_n;
+use starknet::SyscallResultTrait;
+use starknet::syscalls::call_contract_syscall;
+use crate::HDP;
+
+const UNCONSTRAINED_CONTRACT_ADDRESS: felt252 = 'unconstrained';
+
+const ORACLE: felt252 = 1;
+
+pub const SORT_PERMUTATION: felt252 = 'sort_permutation';
+pub const MOD_INVERSE: felt252 = 'mod_inverse';
+
+/// Oracle values are computed off-chain during the dry run and are NOT proven.
+/// Callers must check every witness they receive, as the built-in helpers below do.
+#[generate_trait]
+pub impl UnconstrainedOracleImpl of UnconstrainedOracleTrait {
+    fn call_oracle(self: @HDP, oracle_id: felt252, input: Span<felt252>) -> Span<felt252> {
+        let mut calldata = array![
+            *self.unconstrained.dict.segment_index, *self.unconstrained.dict.offset, oracle_id,
+        ];
+        input.serialize(ref calldata);
+
+        call_contract_syscall(UNCONSTRAINED_CONTRACT_ADDRESS.try_into().unwrap(), ORACLE, calldata.span())
+            .unwrap_syscall()
+    }
+
+    /// Returns the indices that sort `values` ascending, checked to be a sorting permutation.
+    fn sort_permutation(self: @HDP, values: Span<felt252>) -> Span<u32> {
+        let mut witness = self.call_oracle(SORT_PERMUTATION, values);
+        assert(witness.len() == values.len(), 'Oracle: bad permutation length');
+
+        let mut seen: Felt252Dict<bool> = Default::default();
+        let mut permutation: Array<u32> = array![];
+        let mut previous: u256 = 0;
+        while let Option::Some(index) = witness.pop_front() {
+            let index: u32 = (*index).try_into().unwrap();
+            assert(index < values.len(), 'Oracle: index out of range');
+            assert(!seen.get(index.into()), 'Oracle: duplicate index');
+            seen.insert(index.into(), true);
+
+            let current: u256 = (*values.at(index)).into();
+            assert(current >= previous, 'Oracle: not sorted');
+            previous = current;
+            permutation.append(index);
+        };
+
+        permutation.span()
+    }
+
+    /// Returns `inv` with `value * inv = 1 mod modulus`, checked against `modulus`.
+    fn mod_inverse(self: @HDP, value: u256, modulus: NonZero<u256>) -> u256 {
+        let modulus_value: u256 = modulus.into();
+        let witness = self
+            .call_oracle(
+                MOD_INVERSE,
+                array![value.low.into(), value.high.into(), modulus_value.low.into(), modulus_value.high.into()].span(),
+            );
+        assert(witness.len() == 2, 'Oracle: bad inverse length');
+
+        let inverse = u256 { low: (*witness.at(0)).try_into().unwrap(), high: (*witness.at(1)).try_into().unwrap() };
+        assert(inverse < modulus_value, 'Oracle: inverse not reduced');
+        assert(u256_mul_mod_n(value, inverse, modulus) == 1, 'Oracle: not an inverse');
+
+        inverse
+    }
+}
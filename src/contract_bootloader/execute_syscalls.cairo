@@ -28,6 +28,8 @@ from starkware.cairo.common.dict_access import DictAccess
 from starkware.cairo.common.registers import get_label_location
 from src.utils.chain_info import chain_id_to_layout
 from src.utils.utils import find_message_len_bytes, copy_prefix_words, build_tail_le_word
+from src.memorizers.evm.log_absence import EvmLogAbsence
+from src.memorizers.evm.range_access import EvmRangeAccess
 from src.memorizers.evm.state_access import EvmStateAccess, EvmStateAccessType
 from src.memorizers.starknet.state_access import (
     StarknetStateAccess,
@@ -154,19 +156,44 @@ func execute_call_contract{
     }
 
     if (request.contract_address == 'unconstrained') {
-        tempvar key_chain_id = request.calldata_start[2];
-        tempvar key_block_number = request.calldata_start[3];
-        tempvar key_address = request.calldata_start[4];
+        let call_handler_id = request.selector;
 
-        let memorizer_key = UnconstrainedHashParams.bytecode{poseidon_ptr=poseidon_ptr}(
-            chain_id=key_chain_id, block_number=key_block_number, address=key_address
-        );
+        if (call_handler_id == 0) {
+            tempvar key_chain_id = request.calldata_start[2];
+            tempvar key_block_number = request.calldata_start[3];
+            tempvar key_address = request.calldata_start[4];
+
+            let memorizer_key = UnconstrainedHashParams.bytecode{poseidon_ptr=poseidon_ptr}(
+                chain_id=key_chain_id, block_number=key_block_number, address=key_address
+            );
+
+            let (data_start) = UnconstrainedMemorizer.get(key=memorizer_key);
+            let ptr: felt** = cast(data_start, felt**);
+            // use memory copy fn as check for memory slice
+            memcpy(response.retdata_start, [ptr], response.retdata_end - response.retdata_start);
+
+            return ();
+        }
+
+        if (call_handler_id == 1) {
+            tempvar key_oracle_id = request.calldata_start[2];
+            tempvar key_input_len = request.calldata_start[3];
+
+            let memorizer_key = UnconstrainedHashParams.oracle{poseidon_ptr=poseidon_ptr}(
+                oracle_id=key_oracle_id, input_len=key_input_len, input=request.calldata_start + 4
+            );
 
-        let (data_start) = UnconstrainedMemorizer.get(key=memorizer_key);
-        let ptr: felt** = cast(data_start, felt**);
-        // use memory copy fn as check for memory slice
-        memcpy(response.retdata_start, [ptr], response.retdata_end - response.retdata_start);
+            let (data_start) = UnconstrainedMemorizer.get(key=memorizer_key);
+            let ptr: felt** = cast(data_start, felt**);
+            // oracle values are stored as [len, values...], retdata only holds the values
+            let value: felt* = [ptr];
+            assert value[0] = response.retdata_end - response.retdata_start;
+            memcpy(response.retdata_start, value + 1, value[0]);
 
+            return ();
+        }
+
+        assert 1 = 0;
         return ();
     }
 
@@ -234,6 +261,25 @@ func execute_call_contract{
             return ();
         }
 
+        if (call_handler_id == 3) {
+            tempvar key_trie_label = request.calldata_start[2];
+            tempvar key_key = request.calldata_start[3];
+
+            let label_memorizer_key = InjectedStateHashParams.label{poseidon_ptr=poseidon_ptr}(
+                label=key_trie_label
+            );
+            let (trie_root_ptr) = InjectedStateMemorizer.get(key=label_memorizer_key);
+
+            let memorizer_key = InjectedStateHashParams.delete{poseidon_ptr=poseidon_ptr}(
+                label=key_trie_label, root=[trie_root_ptr], value=key_key
+            );
+            let (new_root_ptr) = InjectedStateMemorizer.get(key=memorizer_key);
+
+            assert [new_root_ptr] = response.retdata_start[0];
+            InjectedStateMemorizer.add(key=label_memorizer_key, data=new_root_ptr);
+            return ();
+        }
+
         // Unknown DictId
         assert 1 = 0;
 
@@ -246,6 +292,34 @@ func execute_call_contract{
     let output_ptr = response.retdata_start;
 
     if (layout == Layout.EVM) {
+        if (state_access_type == EvmStateAccessType.HEADER_RANGE) {
+            with output_ptr {
+                EvmRangeAccess.read_header_range(params=request.calldata_start + 2, field=field);
+            }
+            assert output_ptr = response.retdata_end;
+
+            return ();
+        }
+
+        if (state_access_type == EvmStateAccessType.LOG_ABSENCE) {
+            assert response.retdata_end - response.retdata_start = 1;
+            EvmLogAbsence.verify(
+                params=request.calldata_start + 2, transactions_count=response.retdata_start[0]
+            );
+
+            return ();
+        }
+
+        if (state_access_type == EvmStateAccessType.LOGS_BY_FILTER) {
+            EvmRangeAccess.verify_logs_by_filter(
+                params=request.calldata_start + 2,
+                retdata=response.retdata_start,
+                retdata_len=response.retdata_end - response.retdata_start,
+            );
+
+            return ();
+        }
+
         with output_ptr {
             EvmStateAccess.read_and_decode(
                 params=request.calldata_start + 2, state_access_type=state_access_type, field=field
@@ -203,6 +203,7 @@ func run{
         injected_state_memorizer=injected_state_memorizer,
         unconstrained_memorizer=unconstrained_memorizer,
     }(module_inputs, module_inputs_len);
+    %{ module_results = [memory[ids.retdata + i] for i in range(ids.retdata_size)] %}
 
     // Post Verification Checks: Ensure dict consistency
     default_dict_finalize(evm_memorizer_start, evm_memorizer, BareMemorizer.DEFAULT_VALUE);
@@ -0,0 +1,430 @@
+from packages.eth_essentials.lib.utils import felt_divmod
+from starkware.cairo.common.alloc import alloc
+from starkware.cairo.common.cairo_builtins import BitwiseBuiltin, PoseidonBuiltin
+from starkware.cairo.common.cairo_keccak.keccak import (
+    cairo_keccak_bigend as keccak_bigend,
+    cairo_keccak_uint256s_bigend as keccak_uint256s_bigend,
+)
+from starkware.cairo.common.dict_access import DictAccess
+from starkware.cairo.common.math import assert_le, assert_not_equal, unsigned_div_rem
+from starkware.cairo.common.uint256 import Uint256, uint256_reverse_endian
+
+from src.decoders.evm.header_decoder import HeaderField
+from src.decoders.evm.log_decoder import LOGS_OFFSET
+from src.decoders.evm.receipt_decoder import ReceiptDecoder, ReceiptField
+from src.memorizers.evm.memorizer import EvmMemorizer
+from src.memorizers.evm.state_access import EvmStateAccess, EvmStateAccessType
+from src.utils.rlp import get_rlp_len, get_rlp_list_meta, le_chunks_to_be_uint256, rlp_list_retrieve
+
+// The logs bloom is decoded into 16 big endian chunks of 128 bits
+const BLOOM_CHUNKS = 16;
+const BLOOM_CHUNK_BITS = 128;
+// Every bloom input sets 3 bits of the 2048 bits filter
+const BLOOM_BITS_PER_INPUT = 3;
+const BLOOM_BITS = 2048;
+
+// This namespace contains the functions proving that no log of a block was emitted by an address
+// with a given topic0.
+// The header logs bloom proves the absence on its own when it rules the log out. Otherwise every
+// receipt of the block is read and either its own bloom rules the log out or its logs are scanned.
+// That every receipt was read is proven by matching the cumulative gas used of the last receipt
+// against the gas used of the header. This assumes that every transaction uses gas and that the
+// header gas used is the cumulative gas used of the last receipt, which is only guaranteed on the
+// chains accepted by verify_transactions_count.
+namespace EvmLogAbsence {
+    // Params:
+    // - params: The key [chain_id, block_number, address, topic0.low, topic0.high]
+    // - transactions_count: The number of receipts of the block, 0 if the header bloom rules the log out
+    func verify{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(params: felt*, transactions_count: felt) -> () {
+        alloc_locals;
+
+        let (local address_bits: felt*) = alloc();
+        address_bloom_bits(address=params[2], bits=address_bits);
+        let (local topic0_bits: felt*) = alloc();
+        topic_bloom_bits(topic=Uint256(low=params[3], high=params[4]), bits=topic0_bits);
+
+        let (local header_params: felt*) = alloc();
+        assert header_params[0] = params[0];
+        assert header_params[1] = params[1];
+
+        let (local header_bloom: felt*) = alloc();
+        let output_ptr = header_bloom;
+        with output_ptr {
+            EvmStateAccess.read_and_decode(
+                params=header_params, state_access_type=EvmStateAccessType.HEADER, field=HeaderField.BLOOM
+            );
+        }
+
+        let may_contain = bloom_may_contain(
+            bloom=header_bloom, address_bits=address_bits, topic0_bits=topic0_bits
+        );
+        if (may_contain == 0) {
+            assert transactions_count = 0;
+            return ();
+        }
+
+        verify_transactions_count(
+            chain_id=params[0], block_number=params[1], transactions_count=transactions_count
+        );
+
+        return verify_receipts(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            transaction_index=0,
+            transactions_count=transactions_count,
+        );
+    }
+
+    // Proves that the block has exactly transactions_count receipts, given that its header bloom is
+    // not empty.
+    func verify_transactions_count{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(chain_id: felt, block_number: felt, transactions_count: felt) -> () {
+        alloc_locals;
+
+        // Only Ethereum mainnet and Sepolia are known to satisfy the cumulative gas assumption
+        with_attr error_message("UNSUPPORTED CHAIN FOR RECEIPT COUNT") {
+            assert (chain_id - 1) * (chain_id - 11155111) = 0;
+        }
+
+        let (local header_params: felt*) = alloc();
+        assert header_params[0] = chain_id;
+        assert header_params[1] = block_number;
+
+        let (local gas_used: felt*) = alloc();
+        let output_ptr = gas_used;
+        with output_ptr {
+            EvmStateAccess.read_and_decode(
+                params=header_params, state_access_type=EvmStateAccessType.HEADER, field=HeaderField.GAS_USED
+            );
+        }
+
+        // The header bloom of a block without transactions is empty, so the block has at least one receipt
+        with_attr error_message("MISSING BLOCK RECEIPTS") {
+            assert_not_equal(transactions_count, 0);
+        }
+        let (local last_receipt_params: felt*) = alloc();
+        assert last_receipt_params[0] = chain_id;
+        assert last_receipt_params[1] = block_number;
+        assert last_receipt_params[2] = transactions_count - 1;
+
+        let (local cumulative_gas_used: felt*) = alloc();
+        let output_ptr = cumulative_gas_used;
+        with output_ptr {
+            EvmStateAccess.read_and_decode(
+                params=last_receipt_params,
+                state_access_type=EvmStateAccessType.BLOCK_RECEIPT,
+                field=ReceiptField.CUMULATIVE_GAS_USED,
+            );
+        }
+
+        // Every transaction uses gas, so no receipt can follow the one reaching the gas used of the block
+        with_attr error_message("INCOMPLETE BLOCK RECEIPTS") {
+            assert cumulative_gas_used[0] = gas_used[0];
+            assert cumulative_gas_used[1] = gas_used[1];
+        }
+
+        return ();
+    }
+
+    func verify_receipts{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(
+        params: felt*,
+        address_bits: felt*,
+        topic0_bits: felt*,
+        transaction_index: felt,
+        transactions_count: felt,
+    ) -> () {
+        if (transaction_index == transactions_count) {
+            return ();
+        }
+
+        verify_receipt(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            transaction_index=transaction_index,
+        );
+
+        return verify_receipts(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            transaction_index=transaction_index + 1,
+            transactions_count=transactions_count,
+        );
+    }
+
+    func verify_receipt{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(params: felt*, address_bits: felt*, topic0_bits: felt*, transaction_index: felt) -> () {
+        alloc_locals;
+
+        let (local receipt_params: felt*) = alloc();
+        assert receipt_params[0] = params[0];
+        assert receipt_params[1] = params[1];
+        assert receipt_params[2] = transaction_index;
+
+        let may_contain = receipt_may_contain(
+            receipt_params=receipt_params, address_bits=address_bits, topic0_bits=topic0_bits
+        );
+        if (may_contain == 0) {
+            return ();
+        }
+
+        // The receipt bloom is a false positive, every log of the receipt has to be checked
+        let (logs, logs_bytes_len) = read_receipt_logs(receipt_params=receipt_params);
+
+        return verify_logs(
+            logs=logs,
+            logs_bytes_len=logs_bytes_len,
+            offset=0,
+            log_index=0,
+            address=params[2],
+            topic0=Uint256(low=params[3], high=params[4]),
+        );
+    }
+
+    // Walks the rlp encoded logs list until its end, so no log can be skipped.
+    func verify_logs{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+        logs: felt*, logs_bytes_len: felt, offset: felt, log_index: felt, address: felt, topic0: Uint256
+    ) -> () {
+        alloc_locals;
+        if (offset == logs_bytes_len) {
+            return ();
+        }
+
+        let (word, byte) = felt_divmod(offset, 8);
+        let item_len = get_rlp_len(logs + word, byte);
+        local next_offset = offset + item_len;
+        assert_le(next_offset, logs_bytes_len);
+
+        verify_log(logs=logs, log_index=log_index, address=address, topic0=topic0);
+
+        return verify_logs(
+            logs=logs,
+            logs_bytes_len=logs_bytes_len,
+            offset=next_offset,
+            log_index=log_index + 1,
+            address=address,
+            topic0=topic0,
+        );
+    }
+
+    func verify_log{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+        logs: felt*, log_index: felt, address: felt, topic0: Uint256
+    ) -> () {
+        let matches = log_matches(logs=logs, log_index=log_index, address=address, topic0=topic0);
+        with_attr error_message("MATCHING LOG FOUND") {
+            assert matches = 0;
+        }
+
+        return ();
+    }
+
+    // Returns 1 if the bloom of the receipt may contain a log matching the bloom bits, 0 otherwise.
+    func receipt_may_contain{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(receipt_params: felt*, address_bits: felt*, topic0_bits: felt*) -> felt {
+        alloc_locals;
+
+        let (local receipt_bloom: felt*) = alloc();
+        let output_ptr = receipt_bloom;
+        with output_ptr {
+            EvmStateAccess.read_and_decode(
+                params=receipt_params,
+                state_access_type=EvmStateAccessType.BLOCK_RECEIPT,
+                field=ReceiptField.BLOOM,
+            );
+        }
+
+        return bloom_may_contain(bloom=receipt_bloom, address_bits=address_bits, topic0_bits=topic0_bits);
+    }
+
+    // Returns the rlp encoded logs list of a memorized receipt and its length in bytes.
+    func read_receipt_logs{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(receipt_params: felt*) -> (logs: felt*, logs_bytes_len: felt) {
+        let (memorizer_key) = EvmStateAccess.compute_memorizer_key(
+            receipt_params, EvmStateAccessType.BLOCK_RECEIPT
+        );
+        let (rlp) = EvmMemorizer.get(key=memorizer_key);
+
+        let (tx_type, rlp_start_offset) = ReceiptDecoder.open_receipt_envelope(rlp);
+        let (value_start_offset) = get_rlp_list_meta(rlp, rlp_start_offset);
+        let (logs, logs_len, logs_bytes_len) = rlp_list_retrieve(
+            rlp, LOGS_OFFSET, value_start_offset, 0
+        );
+
+        return (logs=logs, logs_bytes_len=logs_bytes_len);
+    }
+}
+
+// Returns 1 if the log at log_index of the rlp encoded logs list was emitted by the address with the
+// topic0, 0 otherwise.
+func log_matches{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+    logs: felt*, log_index: felt, address: felt, topic0: Uint256
+) -> felt {
+    alloc_locals;
+
+    let (log, log_len, log_bytes_len) = rlp_list_retrieve(logs, log_index, 0, 0);
+    let (log_address, log_address_len, log_address_bytes_len) = rlp_list_retrieve(log, 0, 0, 0);
+    let (local log_address_value) = le_chunks_to_be_uint256(
+        log_address, log_address_len, log_address_bytes_len
+    );
+    if (log_address_value.low + log_address_value.high * 2 ** 128 != address) {
+        return 0;
+    }
+
+    let (topics, topics_len, topics_bytes_len) = rlp_list_retrieve(log, 1, 0, 0);
+    // An empty topics list is decoded as a single zero byte, a topic always takes 33 bytes
+    if (topics_bytes_len == 1) {
+        return 0;
+    }
+
+    let (log_topic0, log_topic0_len, log_topic0_bytes_len) = rlp_list_retrieve(topics, 0, 0, 0);
+    let (local log_topic0_value) = le_chunks_to_be_uint256(
+        log_topic0, log_topic0_len, log_topic0_bytes_len
+    );
+    if (log_topic0_value.low != topic0.low) {
+        return 0;
+    }
+    if (log_topic0_value.high != topic0.high) {
+        return 0;
+    }
+
+    return 1;
+}
+
+// Returns 1 if both the address and the topic0 bits are set in the bloom, 0 if the bloom rules
+// out a log emitted by the address with the topic0.
+func bloom_may_contain{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+    bloom: felt*, address_bits: felt*, topic0_bits: felt*
+) -> felt {
+    let contains_address = bloom_contains_bits(bloom=bloom, bits=address_bits, n_bits=BLOOM_BITS_PER_INPUT);
+    if (contains_address == 0) {
+        return 0;
+    }
+
+    return bloom_contains_bits(bloom=bloom, bits=topic0_bits, n_bits=BLOOM_BITS_PER_INPUT);
+}
+
+func bloom_contains_bits{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+    bloom: felt*, bits: felt*, n_bits: felt
+) -> felt {
+    if (n_bits == 0) {
+        return 1;
+    }
+
+    // Bit 0 is the least significant bit of the last chunk
+    let (chunk_index_from_end, bit_in_chunk) = unsigned_div_rem(bits[0], BLOOM_CHUNK_BITS);
+    assert bitwise_ptr.x = bloom[BLOOM_CHUNKS - 1 - chunk_index_from_end];
+    assert bitwise_ptr.y = pow2_array[bit_in_chunk];
+    tempvar is_set = bitwise_ptr.x_and_y;
+    let bitwise_ptr = bitwise_ptr + BitwiseBuiltin.SIZE;
+
+    if (is_set == 0) {
+        return 0;
+    }
+
+    return bloom_contains_bits(bloom=bloom, bits=bits + 1, n_bits=n_bits - 1);
+}
+
+// Writes the bloom bits of a 20 bytes address.
+func address_bloom_bits{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, keccak_ptr: felt*}(
+    address: felt, bits: felt*
+) {
+    alloc_locals;
+
+    // Left align the address in a big endian word and convert it to little endian 64 bits words
+    let (high, low) = unsigned_div_rem(address, 2 ** 32);
+    let (reversed) = uint256_reverse_endian(Uint256(low=low * 2 ** 96, high=high));
+    let (word_1, word_0) = unsigned_div_rem(reversed.low, 2 ** 64);
+
+    let (local words: felt*) = alloc();
+    assert words[0] = word_0;
+    assert words[1] = word_1;
+    assert words[2] = reversed.high;
+
+    let (hash) = keccak_bigend(inputs=words, n_bytes=20);
+    hash_bloom_bits(hash=hash, bits=bits);
+
+    return ();
+}
+
+// Writes the bloom bits of a 32 bytes topic.
+func topic_bloom_bits{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, keccak_ptr: felt*}(
+    topic: Uint256, bits: felt*
+) {
+    alloc_locals;
+    let (local elements: Uint256*) = alloc();
+    assert elements[0] = topic;
+
+    let (hash) = keccak_uint256s_bigend(n_elements=1, elements=elements);
+    hash_bloom_bits(hash=hash, bits=bits);
+
+    return ();
+}
+
+// The bloom bits are the low 11 bits of the first three 16 bits pairs of the big endian hash.
+func hash_bloom_bits{range_check_ptr}(hash: Uint256, bits: felt*) {
+    let (first_bytes, _) = unsigned_div_rem(hash.high, 2 ** 80);
+    let (pair_0, rest) = unsigned_div_rem(first_bytes, 2 ** 32);
+    let (pair_1, pair_2) = unsigned_div_rem(rest, 2 ** 16);
+
+    let (_, bit_0) = unsigned_div_rem(pair_0, BLOOM_BITS);
+    let (_, bit_1) = unsigned_div_rem(pair_1, BLOOM_BITS);
+    let (_, bit_2) = unsigned_div_rem(pair_2, BLOOM_BITS);
+
+    assert bits[0] = bit_0;
+    assert bits[1] = bit_1;
+    assert bits[2] = bit_2;
+
+    return ();
+}
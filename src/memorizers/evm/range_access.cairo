@@ -0,0 +1,324 @@
+from packages.eth_essentials.lib.utils import felt_divmod
+from starkware.cairo.common.alloc import alloc
+from starkware.cairo.common.cairo_builtins import BitwiseBuiltin, PoseidonBuiltin
+from starkware.cairo.common.dict_access import DictAccess
+from starkware.cairo.common.math import assert_le, assert_nn_le, assert_not_equal
+from starkware.cairo.common.uint256 import Uint256
+
+from src.decoders.evm.header_decoder import HeaderField
+from src.memorizers.evm.log_absence import (
+    EvmLogAbsence,
+    address_bloom_bits,
+    bloom_may_contain,
+    log_matches,
+    topic_bloom_bits,
+)
+from src.memorizers.evm.state_access import EvmStateAccess, EvmStateAccessType
+from src.utils.rlp import get_rlp_len
+
+// This namespace contains the functions verifying the results of the EVM range calls.
+// Every range call expands into reads of the single-item states memorized by the dry run.
+namespace EvmRangeAccess {
+    // Writes the field of every header in the inclusive block range to the output pointer.
+    // Params:
+    // - params: The range key [chain_id, from_block, to_block]
+    // - field: The header field to read (e.g HeaderField.TIMESTAMP)
+    // Each header produces 2 felts, the output pointer is advanced past the written values.
+    func read_header_range{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+        output_ptr: felt*,
+    }(params: felt*, field: felt) -> () {
+        // Variable length fields can not be packed into the range result
+        with_attr error_message("UNSUPPORTED HEADER RANGE FIELD") {
+            assert_not_equal(field, HeaderField.BLOOM);
+            assert_not_equal(field, HeaderField.EXTRA_DATA);
+        }
+        assert_le(params[1], params[2]);
+
+        return read_headers(
+            chain_id=params[0], block_number=params[1], to_block=params[2], field=field
+        );
+    }
+
+    func read_headers{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+        output_ptr: felt*,
+    }(chain_id: felt, block_number: felt, to_block: felt, field: felt) -> () {
+        alloc_locals;
+        if (block_number == to_block + 1) {
+            return ();
+        }
+
+        let (local header_params: felt*) = alloc();
+        assert header_params[0] = chain_id;
+        assert header_params[1] = block_number;
+
+        EvmStateAccess.read_and_decode(
+            params=header_params, state_access_type=EvmStateAccessType.HEADER, field=field
+        );
+        let output_ptr = output_ptr + 2;
+
+        return read_headers(
+            chain_id=chain_id, block_number=block_number + 1, to_block=to_block, field=field
+        );
+    }
+
+    // Verifies the log positions returned by a LogsByFilter call.
+    // Params:
+    // - params: The filter key [chain_id, from_block, to_block, address, topic0.low, topic0.high]
+    // - retdata: The returned [n_logs, n_logs * [block_number, transaction_index, log_index], transactions_counts]
+    //   where transactions_counts holds the number of receipts of every block of the range, 0 when its
+    //   header bloom rules the log out
+    // - retdata_len: The length of the returned data in felts
+    // Every block of the range is rescanned as EvmLogAbsence does: its header bloom rules the log out or
+    // all of its receipts are read, which is proven by the cumulative gas used of the last one. Every
+    // matching log found by the scan must be the next returned position, so the positions are exactly
+    // the matching logs in ascending order.
+    func verify_logs_by_filter{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(params: felt*, retdata: felt*, retdata_len: felt) -> () {
+        alloc_locals;
+        assert_le(params[1], params[2]);
+
+        local n_logs = retdata[0];
+        assert_nn_le(n_logs, retdata_len);
+        assert retdata_len = 1 + n_logs * 3 + params[2] - params[1] + 1;
+
+        let (local address_bits: felt*) = alloc();
+        address_bloom_bits(address=params[3], bits=address_bits);
+        let (local topic0_bits: felt*) = alloc();
+        topic_bloom_bits(topic=Uint256(low=params[4], high=params[5]), bits=topic0_bits);
+
+        let positions_end = verify_blocks(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            block_number=params[1],
+            transactions_counts=retdata + 1 + n_logs * 3,
+            positions=retdata + 1,
+        );
+
+        with_attr error_message("UNMATCHED LOG POSITION") {
+            assert positions_end = retdata + 1 + n_logs * 3;
+        }
+
+        return ();
+    }
+
+    // Scans the blocks up to the end of the range and returns the first position not consumed.
+    func verify_blocks{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(
+        params: felt*,
+        address_bits: felt*,
+        topic0_bits: felt*,
+        block_number: felt,
+        transactions_counts: felt*,
+        positions: felt*,
+    ) -> felt* {
+        alloc_locals;
+        if (block_number == params[2] + 1) {
+            return positions;
+        }
+
+        let (local header_params: felt*) = alloc();
+        assert header_params[0] = params[0];
+        assert header_params[1] = block_number;
+
+        let (local header_bloom: felt*) = alloc();
+        let output_ptr = header_bloom;
+        with output_ptr {
+            EvmStateAccess.read_and_decode(
+                params=header_params, state_access_type=EvmStateAccessType.HEADER, field=HeaderField.BLOOM
+            );
+        }
+
+        let may_contain = bloom_may_contain(
+            bloom=header_bloom, address_bits=address_bits, topic0_bits=topic0_bits
+        );
+        if (may_contain == 0) {
+            assert transactions_counts[0] = 0;
+            return verify_blocks(
+                params=params,
+                address_bits=address_bits,
+                topic0_bits=topic0_bits,
+                block_number=block_number + 1,
+                transactions_counts=transactions_counts + 1,
+                positions=positions,
+            );
+        }
+
+        EvmLogAbsence.verify_transactions_count(
+            chain_id=params[0], block_number=block_number, transactions_count=transactions_counts[0]
+        );
+        let positions = verify_receipts(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            block_number=block_number,
+            transaction_index=0,
+            transactions_count=transactions_counts[0],
+            positions=positions,
+        );
+
+        return verify_blocks(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            block_number=block_number + 1,
+            transactions_counts=transactions_counts + 1,
+            positions=positions,
+        );
+    }
+
+    func verify_receipts{
+        range_check_ptr,
+        poseidon_ptr: PoseidonBuiltin*,
+        bitwise_ptr: BitwiseBuiltin*,
+        keccak_ptr: felt*,
+        evm_memorizer: DictAccess*,
+        evm_decoder_ptr: felt**,
+        evm_key_hasher_ptr: felt**,
+        pow2_array: felt*,
+    }(
+        params: felt*,
+        address_bits: felt*,
+        topic0_bits: felt*,
+        block_number: felt,
+        transaction_index: felt,
+        transactions_count: felt,
+        positions: felt*,
+    ) -> felt* {
+        alloc_locals;
+        if (transaction_index == transactions_count) {
+            return positions;
+        }
+
+        let (local receipt_params: felt*) = alloc();
+        assert receipt_params[0] = params[0];
+        assert receipt_params[1] = block_number;
+        assert receipt_params[2] = transaction_index;
+
+        let may_contain = EvmLogAbsence.receipt_may_contain(
+            receipt_params=receipt_params, address_bits=address_bits, topic0_bits=topic0_bits
+        );
+        if (may_contain == 0) {
+            return verify_receipts(
+                params=params,
+                address_bits=address_bits,
+                topic0_bits=topic0_bits,
+                block_number=block_number,
+                transaction_index=transaction_index + 1,
+                transactions_count=transactions_count,
+                positions=positions,
+            );
+        }
+
+        let (logs, logs_bytes_len) = EvmLogAbsence.read_receipt_logs(receipt_params=receipt_params);
+        let positions = verify_logs(
+            logs=logs,
+            logs_bytes_len=logs_bytes_len,
+            offset=0,
+            log_index=0,
+            address=params[3],
+            topic0=Uint256(low=params[4], high=params[5]),
+            block_number=block_number,
+            transaction_index=transaction_index,
+            positions=positions,
+        );
+
+        return verify_receipts(
+            params=params,
+            address_bits=address_bits,
+            topic0_bits=topic0_bits,
+            block_number=block_number,
+            transaction_index=transaction_index + 1,
+            transactions_count=transactions_count,
+            positions=positions,
+        );
+    }
+
+    // Walks the rlp encoded logs list until its end and consumes a position for every matching log.
+    func verify_logs{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, pow2_array: felt*}(
+        logs: felt*,
+        logs_bytes_len: felt,
+        offset: felt,
+        log_index: felt,
+        address: felt,
+        topic0: Uint256,
+        block_number: felt,
+        transaction_index: felt,
+        positions: felt*,
+    ) -> felt* {
+        alloc_locals;
+        if (offset == logs_bytes_len) {
+            return positions;
+        }
+
+        let (word, byte) = felt_divmod(offset, 8);
+        let item_len = get_rlp_len(logs + word, byte);
+        local next_offset = offset + item_len;
+        assert_le(next_offset, logs_bytes_len);
+
+        let matches = log_matches(logs=logs, log_index=log_index, address=address, topic0=topic0);
+        if (matches == 0) {
+            return verify_logs(
+                logs=logs,
+                logs_bytes_len=logs_bytes_len,
+                offset=next_offset,
+                log_index=log_index + 1,
+                address=address,
+                topic0=topic0,
+                block_number=block_number,
+                transaction_index=transaction_index,
+                positions=positions,
+            );
+        }
+
+        with_attr error_message("MISSING LOG POSITION") {
+            assert positions[0] = block_number;
+            assert positions[1] = transaction_index;
+            assert positions[2] = log_index;
+        }
+
+        return verify_logs(
+            logs=logs,
+            logs_bytes_len=logs_bytes_len,
+            offset=next_offset,
+            log_index=log_index + 1,
+            address=address,
+            topic0=topic0,
+            block_number=block_number,
+            transaction_index=transaction_index,
+            positions=positions + 3,
+        );
+    }
+}
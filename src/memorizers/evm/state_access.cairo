@@ -22,6 +22,10 @@ namespace EvmStateAccessType {
     const BLOCK_TX = 3;
     const BLOCK_RECEIPT = 4;
     const LOG = 5;
+    // Range accesses expand into the states above and are handled by EvmRangeAccess and EvmLogAbsence
+    const HEADER_RANGE = 6;
+    const LOGS_BY_FILTER = 7;
+    const LOG_ABSENCE = 8;
 }
 
 namespace EvmDecoder {
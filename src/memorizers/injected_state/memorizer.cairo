@@ -12,6 +12,7 @@ namespace InjectedStatePackParams {
     const INCLUSION = 1944862448358072610670;
     const NON_INCLUSION = 8749584145069082368101870825326;
     const WRITE = 513020621925;
+    const DELETE = 110386773062757;
 
     func label(label: felt) -> (params: felt*, params_len: felt) {
         alloc_locals;
@@ -58,6 +59,17 @@ namespace InjectedStatePackParams {
         assert params[2] = root;
         assert params[3] = value;
 
+        return (params=params, params_len=4);
+    }
+    func delete(label: felt, root: felt, value: felt) -> (params: felt*, params_len: felt) {
+        alloc_locals;
+
+        local params: felt* = nondet %{ segments.add() %};
+        assert params[0] = InjectedStatePackParams.DELETE;
+        assert params[1] = label;
+        assert params[2] = root;
+        assert params[3] = value;
+
         return (params=params, params_len=4);
     }
 }
@@ -92,6 +104,12 @@ namespace InjectedStateHashParams {
         );
         return hash_memorizer_key(params, params_len);
     }
+    func delete{poseidon_ptr: PoseidonBuiltin*}(label: felt, root: felt, value: felt) -> felt {
+        let (params, params_len) = InjectedStatePackParams.delete(
+            label=label, root=root, value=value
+        );
+        return hash_memorizer_key(params, params_len);
+    }
 }
 
 func hash_memorizer_key{poseidon_ptr: PoseidonBuiltin*}(params: felt*, params_len: felt) -> felt {
@@ -17,6 +17,17 @@ namespace UnconstrainedPackParams {
 
         return (params=params, params_len=3);
     }
+
+    func oracle(oracle_id: felt, input_len: felt, input: felt*) -> (params: felt*, params_len: felt) {
+        alloc_locals;
+
+        local params: felt* = nondet %{ segments.add() %};
+        assert params[0] = 'oracle';
+        assert params[1] = oracle_id;
+        memcpy(params + 2, input, input_len);
+
+        return (params=params, params_len=input_len + 2);
+    }
 }
 
 namespace UnconstrainedHashParams {
@@ -26,6 +37,13 @@ namespace UnconstrainedHashParams {
         );
         return hash_memorizer_key(params, params_len);
     }
+
+    func oracle{poseidon_ptr: PoseidonBuiltin*}(oracle_id: felt, input_len: felt, input: felt*) -> felt {
+        let (params, params_len) = UnconstrainedPackParams.oracle(
+            oracle_id=oracle_id, input_len=input_len, input=input
+        );
+        return hash_memorizer_key(params, params_len);
+    }
 }
 
 namespace UnconstrainedHashParams2 {
@@ -1,3 +1,5 @@
+from starkware.cairo.common.math import assert_le, assert_not_zero
+from starkware.cairo.common.math_cmp import is_le
 from src.types import ChainInfo
 
 namespace Layout {
@@ -5,7 +7,7 @@ namespace Layout {
     const STARKNET = 1;
 }
 
-func fetch_chain_info(chain_id: felt) -> (info: ChainInfo) {
+func fetch_chain_info{range_check_ptr}(chain_id: felt) -> (info: ChainInfo) {
     if (chain_id == 1) {
         return (
             info=ChainInfo(
@@ -88,15 +90,62 @@ func fetch_chain_info(chain_id: felt) -> (info: ChainInfo) {
         );
     }
 
-    assert 1 = 0;
+    // Any other chain is an EVM chain
+    return derive_evm_chain_info(chain_id);
+}
+
+// Derives the info of an EVM chain without a dedicated entry above.
+// The encoded id is the RLP encoding of the chain id, as appended to EIP-155 signing payloads.
+func derive_evm_chain_info{range_check_ptr}(chain_id: felt) -> (info: ChainInfo) {
+    alloc_locals;
+    let (id_bytes_len, upper_bound) = chain_id_bytes_len(
+        chain_id=chain_id, bytes_len=1, upper_bound=0x100
+    );
+
+    let is_single_byte = is_le(chain_id, 0x7f);
+    if (is_single_byte == 1) {
+        return (
+            info=ChainInfo(
+                id=chain_id,
+                id_bytes_len=1,
+                encoded_id=chain_id,
+                encoded_id_bytes_len=1,
+                byzantium=0,
+                layout=Layout.EVM,
+            ),
+        );
+    }
+
     return (
         info=ChainInfo(
-            id=0, id_bytes_len=0, encoded_id=0, encoded_id_bytes_len=0, byzantium=0, layout=-1
+            id=chain_id,
+            id_bytes_len=id_bytes_len,
+            encoded_id=(0x80 + id_bytes_len) * upper_bound + chain_id,
+            encoded_id_bytes_len=id_bytes_len + 1,
+            byzantium=0,
+            layout=Layout.EVM,
         ),
     );
 }
 
-func chain_id_to_layout(chain_id: felt) -> felt {
+// Returns the byte length of a non-zero chain id, along with 0x100 ** bytes_len.
+func chain_id_bytes_len{range_check_ptr}(
+    chain_id: felt, bytes_len: felt, upper_bound: felt
+) -> (bytes_len: felt, upper_bound: felt) {
+    assert_not_zero(chain_id);
+    let fits = is_le(chain_id, upper_bound - 1);
+    if (fits == 1) {
+        return (bytes_len=bytes_len, upper_bound=upper_bound);
+    }
+
+    // Chain ids are bounded to 15 bytes, keeping the comparisons above within the range check bound.
+    assert_le(bytes_len + 1, 15);
+    return chain_id_bytes_len(
+        chain_id=chain_id, bytes_len=bytes_len + 1, upper_bound=upper_bound * 0x100
+    );
+}
+
+func chain_id_to_layout{range_check_ptr}(chain_id: felt) -> felt {
     let (info) = fetch_chain_info(chain_id=chain_id);
     return info.layout;
 }
@@ -3,4 +3,5 @@ from src.types import InjectedStateInfo
 namespace ProofType {
     const READ = 0;
     const WRITE = 1;
+    const DELETE = 2;
 }
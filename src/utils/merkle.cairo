@@ -158,16 +158,8 @@ func hash_pair{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, keccak_ptr: felt*}
     // ToDo: We have to figure out if the pair-wise order is something we want to do in a hint. The order could be messed with by a malicious prover.
     %{
         def flip_endianess(val):
-            val_hex = hex(val)[2:]
-
-            if len(val_hex) % 2:
-                val_hex = '0' + val_hex
-
-            # Convert hex string to bytes
-            byte_data = bytes.fromhex(val_hex)
-            num = int.from_bytes(byte_data, byteorder="little")
-
-            return num
+            # Reverse all 16 bytes of the Uint256 half, so leading zero bytes keep their weight
+            return int.from_bytes(val.to_bytes(16, "big"), byteorder="little")
 
         # In LE Uint256, the low and high are reversed
         left = flip_endianess(ids.left.low) * 2**128 + flip_endianess(ids.left.high)
@@ -3,7 +3,12 @@ from starkware.cairo.common.uint256 import Uint256, uint256_reverse_endian
 from starkware.cairo.common.alloc import alloc
 from packages.eth_essentials.lib.mpt import verify_mpt_proof as verify_mpt_proof_lib
 
+// keccak256(rlp("")), the root of an empty trie
+const EMPTY_TRIE_ROOT_HIGH = 0x56e81f171bcc55a6ff8345e692c0f86e;
+const EMPTY_TRIE_ROOT_LOW = 0x5b48e01b996cadc001622fb5e363b421;
+
 // Wraps the original verify_mpt_proof function with the logic required for handling non-inclusion.
+// Excluded keys, including every key of an empty trie, are returned as the empty value 0x80.
 func verify_mpt_proof{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, keccak_ptr: felt*}(
     mpt_proof: felt**,
     mpt_proof_bytes_len: felt*,
@@ -13,6 +18,15 @@ func verify_mpt_proof{range_check_ptr, bitwise_ptr: BitwiseBuiltin*, keccak_ptr:
     root: Uint256,
     pow2_array: felt*,
 ) -> (value: felt*, value_len: felt) {
+    // An empty trie holds no key, proofs against its root come without any node
+    if (mpt_proof_len == 0) {
+        assert root.low = EMPTY_TRIE_ROOT_LOW;
+        assert root.high = EMPTY_TRIE_ROOT_HIGH;
+        let (res: felt*) = alloc();
+        assert res[0] = 0x80;
+        return (value=res, value_len=1);
+    }
+
     let (root_le) = uint256_reverse_endian(root);
     let (rlp: felt*, value_len: felt) = verify_mpt_proof_lib(
         mpt_proof=mpt_proof,
@@ -114,11 +114,13 @@ func verify_account{
         pow2_array=pow2_array,
     );
 
+    let account_rlp = account_rlp_or_empty(rlp=rlp, value_len=value_len);
+
     // add account to memorizer
     let memorizer_key = EvmHashParams.account(
         chain_id=chain_info.id, block_number=block_number, address=address
     );
-    EvmMemorizer.add(key=memorizer_key, data=rlp);
+    EvmMemorizer.add(key=memorizer_key, data=account_rlp);
 
     return verify_account(
         address=address,
@@ -128,3 +130,25 @@ func verify_account{
         idx=idx + 1,
     );
 }
+
+// Returns the verified account rlp, or the rlp of the empty account if the mpt proof verified the
+// exclusion of the address. An account rlp is never a single byte, unlike the excluded value 0x80.
+func account_rlp_or_empty(rlp: felt*, value_len: felt) -> felt* {
+    if (value_len != 1) {
+        return rlp;
+    }
+
+    // rlp([nonce=0, balance=0, storage_root=EMPTY_TRIE_ROOT, code_hash=keccak256("")]) in LE chunks
+    let (empty_account: felt*) = alloc();
+    assert empty_account[0] = 0x1fe856a0808044f8;
+    assert empty_account[1] = 0x4583ffa655cc1b17;
+    assert empty_account[2] = 0xe0485b6ef8c092e6;
+    assert empty_account[3] = 0x2f6201c0ad6c991b;
+    assert empty_account[4] = 0xd2c5a021b463e3b5;
+    assert empty_account[5] = 0x7e923c23f7860146;
+    assert empty_account[6] = 0xe5c003c7dcb27d;
+    assert empty_account[7] = 0xfa7b3b2782ca53b6;
+    assert empty_account[8] = 0x70a4855d04d8;
+
+    return empty_account;
+}
@@ -15,6 +15,7 @@ from starkware.cairo.common.cairo_builtins import (
 
 from src.memorizers.injected_state.memorizer import InjectedStateMemorizer, InjectedStateHashParams
 from starkware.cairo.common.alloc import alloc
+from starkware.cairo.common.math import assert_not_zero
 from src.types import MMRMetaPoseidon, MMRMetaKeccak, ChainInfo, InjectedStateInfo
 from src.utils.chain_info import fetch_chain_info, Layout
 from src.utils.injected_state_info import ProofType
@@ -191,6 +192,36 @@ func run_injected_state_verification_inner{
         return run_injected_state_verification_inner(idx=idx - 1);
     }
 
+    if (proof_type == ProofType.DELETE) {
+        // The hint hands delete proofs over as writes of zero, the patricia update then proves
+        // the key was set under prev_root and is absent from new_root.
+        %{ vm_enter_scope({'state_proof': state_proofs[ids.idx - 1], '__dict_manager': __dict_manager}) %}
+        tempvar key_trie_label: felt = nondet %{ state_proof_write.trie_label %};
+        let (prev_root, new_root, key, prev_value, new_value) = update_state_verification{
+            range_check_ptr=range_check_ptr,
+            bitwise_ptr=bitwise_ptr,
+            keccak_ptr=keccak_ptr,
+            pow2_array=pow2_array,
+            injected_state_memorizer=injected_state_memorizer,
+        }();
+        %{ vm_exit_scope() %}
+
+        assert new_value = 0;
+        with_attr error_message("InjectedState: deleted key is not set") {
+            assert_not_zero(prev_value);
+        }
+
+        let (data_ptr: felt*) = alloc();
+        assert [data_ptr] = new_root;
+
+        let memorizer_key = InjectedStateHashParams.delete{poseidon_ptr=poseidon_ptr}(
+            label=key_trie_label, root=prev_root, value=key
+        );
+        InjectedStateMemorizer.add(key=memorizer_key, data=data_ptr);
+
+        return run_injected_state_verification_inner(idx=idx - 1);
+    }
+
     assert 0 = 1;
 
     return (idx=0);
@@ -0,0 +1,86 @@
+use cairo_vm::types::builtin_name::BuiltinName;
+use types::{
+    budget::{BuiltinLimit, Resource, ResourceBudget},
+    error::Error,
+};
+
+use crate::test_utils::dry_run_with_budget;
+
+fn assert_exceeded(result: Result<(), Error>, expected: Resource) {
+    match result {
+        Err(Error::ResourceLimitExceeded { resource, .. }) => assert_eq!(resource, expected),
+        Err(e) => panic!("expected the {} budget to abort the run, got {}", expected, e),
+        Ok(()) => panic!("expected the {} budget to abort the run", expected),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_step_budget_aborts_run() {
+    dotenvy::dotenv().ok();
+    let result = dry_run_with_budget(
+        serde_json::from_slice(include_bytes!(
+            "../../target/dev/tests_hashers_poseidon.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        ResourceBudget {
+            max_steps: Some(1000),
+            ..Default::default()
+        },
+    );
+    assert_exceeded(result, Resource::Steps);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_syscall_budget_aborts_run() {
+    dotenvy::dotenv().ok();
+    let result = dry_run_with_budget(
+        serde_json::from_slice(include_bytes!(
+            "../../target/dev/tests_unconstrained_sort_permutation.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        ResourceBudget {
+            max_syscalls: Some(0),
+            ..Default::default()
+        },
+    );
+    assert_exceeded(result, Resource::Syscalls);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_builtin_budget_aborts_run() {
+    dotenvy::dotenv().ok();
+    let result = dry_run_with_budget(
+        serde_json::from_slice(include_bytes!(
+            "../../target/dev/tests_hashers_poseidon.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        ResourceBudget {
+            max_builtins: vec![BuiltinLimit {
+                builtin: BuiltinName::poseidon,
+                limit: 0,
+            }],
+            ..Default::default()
+        },
+    );
+    assert_exceeded(result, Resource::Builtin(BuiltinName::poseidon));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_generous_budget_completes_run() {
+    dotenvy::dotenv().ok();
+    dry_run_with_budget(
+        serde_json::from_slice(include_bytes!(
+            "../../target/dev/tests_hashers_poseidon.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        ResourceBudget {
+            max_steps: Some(100_000_000),
+            max_syscalls: Some(1000),
+            max_builtins: vec![BuiltinLimit {
+                builtin: BuiltinName::poseidon,
+                limit: 1_000_000,
+            }],
+        },
+    )
+    .unwrap();
+}
@@ -1,6 +1,9 @@
 pub mod account_modules;
 pub mod fetcher_modules;
 pub mod header_modules;
+pub mod header_range_modules;
+pub mod log_absence_modules;
+pub mod log_filter_modules;
 pub mod log_modules;
 pub mod receipt_modules;
 pub mod storage_modules;
@@ -99,3 +99,44 @@ mod evm_account_get_code_hash {
         );
     }
 }
+
+#[starknet::contract]
+mod evm_account_get_fresh_account {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::account::{AccountImpl, AccountKey, AccountTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        // An address without any account, read through the exclusion proof as the empty account
+        let key = AccountKey {
+            chain_id: 11155111,
+            block_number: 7692344,
+            address: 0x9a3F8e1d2C4b5A6978e0D1c2B3a4F5e6D7c8B9a0,
+        };
+        assert!(hdp.evm.account_get_nonce(@key) == u256 { low: 0x0, high: 0x0 });
+        assert!(hdp.evm.account_get_balance(@key) == u256 { low: 0x0, high: 0x0 });
+        assert!(
+            hdp
+                .evm
+                .account_get_state_root(
+                    @key,
+                ) == u256 {
+                    low: 0x5b48e01b996cadc001622fb5e363b421,
+                    high: 0x56e81f171bcc55a6ff8345e692c0f86e,
+                },
+        );
+        assert!(
+            hdp
+                .evm
+                .account_get_code_hash(
+                    @key,
+                ) == u256 {
+                    low: 0xe500b653ca82273b7bfad8045d85a470,
+                    high: 0xc5d2460186f7233c927e7db2dcc703c0,
+                },
+        );
+    }
+}
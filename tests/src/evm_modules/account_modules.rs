@@ -53,3 +53,16 @@ async fn test_tests_evm_get_state_root() {
     )
     .await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_get_fresh_account() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_account_get_fresh_account.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
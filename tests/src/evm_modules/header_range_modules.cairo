@@ -0,0 +1,109 @@
+#[starknet::contract]
+mod evm_header_range_get_parent {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::header_range::{HeaderRangeImpl, HeaderRangeKey, HeaderRangeTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let parents = hdp
+            .evm
+            .header_range_get_parent(
+                @HeaderRangeKey { chain_id: 11155111, from_block: 7692343, to_block: 7692344 },
+            );
+        assert!(parents.len() == 2);
+        assert!(
+            *parents
+                .at(
+                    1,
+                ) == u256 {
+                    low: 0x2bf29adc0426c14ce89ecf3040c01be1,
+                    high: 0xf0c0ec0462d1f58b9ac41a9bd43b2b90,
+                },
+        );
+
+        let timestamps = hdp
+            .evm
+            .header_range_get_timestamp(
+                @HeaderRangeKey { chain_id: 11155111, from_block: 7692343, to_block: 7692344 },
+            );
+        assert!(timestamps.len() == 2);
+        assert!(*timestamps.at(0) < *timestamps.at(1));
+    }
+}
+
+#[starknet::contract]
+mod evm_header_range_single_block {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::header::{HeaderImpl, HeaderKey, HeaderTrait};
+    use hdp_cairo::evm::header_range::{HeaderRangeImpl, HeaderRangeKey, HeaderRangeTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let key = HeaderRangeKey { chain_id: 11155111, from_block: 7692344, to_block: 7692344 };
+        let header_key = HeaderKey { chain_id: 11155111, block_number: 7692344 };
+
+        let gas_used = hdp.evm.header_range_get_gas_used(@key);
+        assert!(gas_used.len() == 1);
+        assert!(*gas_used.at(0) == hdp.evm.header_get_gas_used(@header_key));
+
+        let base_fees = hdp.evm.header_range_get_base_fee_per_gas(@key);
+        assert!(base_fees.len() == 1);
+        assert!(*base_fees.at(0) == hdp.evm.header_get_base_fee_per_gas(@header_key));
+    }
+}
+
+#[starknet::contract]
+mod evm_header_range_ends_match_headers {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::header::{HeaderImpl, HeaderKey, HeaderTrait};
+    use hdp_cairo::evm::header_range::{HeaderRangeImpl, HeaderRangeKey, HeaderRangeTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let state_roots = hdp
+            .evm
+            .header_range_get_state_root(
+                @HeaderRangeKey { chain_id: 11155111, from_block: 7692342, to_block: 7692344 },
+            );
+        assert!(state_roots.len() == 3);
+        assert!(
+            *state_roots
+                .at(0) == hdp
+                .evm
+                .header_get_state_root(@HeaderKey { chain_id: 11155111, block_number: 7692342 }),
+        );
+        assert!(
+            *state_roots
+                .at(2) == hdp
+                .evm
+                .header_get_state_root(@HeaderKey { chain_id: 11155111, block_number: 7692344 }),
+        );
+    }
+}
+
+#[starknet::contract]
+mod evm_header_range_empty {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::header_range::{HeaderRangeImpl, HeaderRangeKey, HeaderRangeTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        hdp
+            .evm
+            .header_range_get_parent(
+                @HeaderRangeKey { chain_id: 11155111, from_block: 7692344, to_block: 7692343 },
+            );
+    }
+}
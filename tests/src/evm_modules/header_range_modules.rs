@@ -0,0 +1,112 @@
+use alloy::{consensus::Header, rlp::Decodable};
+use types::{proofs::evm::header::Header as EvmHeader, ChainProofs, HDPInput, InjectedState};
+
+use crate::test_utils::{run, run_tampered};
+
+fn block_number(header: &EvmHeader) -> u64 {
+    Header::decode(&mut header.rlp.as_ref()).unwrap().number
+}
+
+/// Applies `f` to the evm header proofs collected for the sound run.
+fn tamper_headers(input: &mut HDPInput, mut f: impl FnMut(&mut Vec<EvmHeader>)) {
+    for chain_proofs in &mut input.chain_proofs {
+        if let ChainProofs::Evm { proofs, .. } = chain_proofs {
+            for header_with_mmr in &mut proofs.headers_with_mmr {
+                f(&mut header_with_mmr.headers);
+            }
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_header_range_get_parent() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_get_parent.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_header_range_single_block() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_single_block.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_header_range_ends_match_headers() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_ends_match_headers.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[should_panic(expected = "empty block range")]
+async fn test_tests_evm_header_range_empty() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_empty.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_header_range_rejects_omitted_header() {
+    dotenvy::dotenv().ok();
+    // The module only reads the range, so the first block of the range is read from no other proof
+    let result = run_tampered(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_get_parent.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+        |input| tamper_headers(input, |headers| headers.retain(|header| block_number(header) != 7692343)),
+    )
+    .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_header_range_rejects_tampered_header() {
+    dotenvy::dotenv().ok();
+    // Altering the header rlp changes its hash, which no longer matches the MMR leaf
+    let result = run_tampered(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_header_range_get_parent.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+        |input| {
+            tamper_headers(input, |headers| {
+                for header in headers.iter_mut().filter(|header| block_number(header) == 7692343) {
+                    let mut rlp = header.rlp.to_vec();
+                    *rlp.last_mut().unwrap() ^= 1;
+                    header.rlp = rlp.into();
+                }
+            })
+        },
+    )
+    .await;
+    assert!(result.is_err());
+}
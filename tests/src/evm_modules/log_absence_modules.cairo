@@ -0,0 +1,72 @@
+#[starknet::contract]
+mod log_absence_header_bloom {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::log_absence::{LogAbsenceImpl, LogAbsenceKey, LogAbsenceTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        hdp
+            .evm
+            .assert_no_matching_log(
+                @LogAbsenceKey {
+                    chain_id: 11155111,
+                    block_number: 7692344,
+                    address: 0x7Eaa8557E1A608bcc77C2d392093cE7F05c0DB14,
+                    topic0: u256 { low: 0xdead, high: 0x0 },
+                },
+            );
+    }
+}
+
+#[starknet::contract]
+mod log_absence_receipts {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::log_absence::{LogAbsenceImpl, LogAbsenceKey, LogAbsenceTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        // The topic1 of an Approval log of the address in this block, so the bloom can not rule it
+        // out, while no log of the address has it as topic0
+        hdp
+            .evm
+            .assert_no_matching_log(
+                @LogAbsenceKey {
+                    chain_id: 11155111,
+                    block_number: 7692344,
+                    address: 0x7Eaa8557E1A608bcc77C2d392093cE7F05c0DB14,
+                    topic0: u256 { low: 0xc2eD6f12bF99dAb43C55f40d7D40b730, high: 0xfB41B2F3 },
+                },
+            );
+    }
+}
+
+#[starknet::contract]
+mod log_absence_matching_log {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::log_absence::{LogAbsenceImpl, LogAbsenceKey, LogAbsenceTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        hdp
+            .evm
+            .assert_no_matching_log(
+                @LogAbsenceKey {
+                    chain_id: 11155111,
+                    block_number: 7692344,
+                    address: 0x7Eaa8557E1A608bcc77C2d392093cE7F05c0DB14,
+                    topic0: u256 {
+                        low: 0xdd0314c0f7b2291e5b200ac8c7c3b925, high: 0x8c5be1e5ebec7d5bd14f71427d1e84f3,
+                    },
+                },
+            );
+    }
+}
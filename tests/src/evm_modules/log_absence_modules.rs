@@ -0,0 +1,70 @@
+use alloy::primitives::U256;
+use types::{ChainProofs, InjectedState};
+
+use crate::test_utils::{run, run_tampered};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_log_absence_header_bloom() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_log_absence_header_bloom.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_log_absence_receipts() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_log_absence_receipts.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[should_panic(expected = "matching log found")]
+async fn test_tests_log_absence_matching_log() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_log_absence_matching_log.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_log_absence_rejects_missing_receipt() {
+    dotenvy::dotenv().ok();
+    // Without one of the scanned receipts the hint returns a shorter block, which the cumulative gas
+    // check of the block has to reject
+    let dropped_receipt = U256::from_be_slice(&alloy::rlp::encode(U256::from(180)));
+    let result = run_tampered(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_log_absence_receipts.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+        |input| {
+            for chain_proofs in &mut input.chain_proofs {
+                if let ChainProofs::Evm { proofs, .. } = chain_proofs {
+                    proofs
+                        .transaction_receipts
+                        .retain(|receipt| !(receipt.proof.block_number == 7692344 && receipt.key == dropped_receipt));
+                }
+            }
+        },
+    )
+    .await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,38 @@
+#[starknet::contract]
+mod logs_by_filter_get_logs {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::log::{LogImpl, LogTrait};
+    use hdp_cairo::evm::log_filter::{LogFilterImpl, LogFilterKey, LogFilterTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let address = u256 { low: 0xE1A608bcc77C2d392093cE7F05c0DB14, high: 0x7Eaa8557 };
+        let topic0 = u256 {
+            low: 0xdd0314c0f7b2291e5b200ac8c7c3b925, high: 0x8c5be1e5ebec7d5bd14f71427d1e84f3,
+        };
+        let mut logs = hdp
+            .evm
+            .logs_by_filter(
+                @LogFilterKey {
+                    chain_id: 11155111,
+                    from_block: 7692343,
+                    to_block: 7692344,
+                    address: 0x7Eaa8557E1A608bcc77C2d392093cE7F05c0DB14,
+                    topic0: topic0,
+                },
+            );
+
+        let mut found = false;
+        while let Option::Some(log) = logs.pop_front() {
+            assert!(hdp.evm.log_get_address(@log) == address);
+            assert!(hdp.evm.log_get_topic0(@log) == topic0);
+            if log.block_number == 7692344 && log.transaction_index == 180 && log.log_index == 0 {
+                found = true;
+            }
+        };
+        assert!(found);
+    }
+}
@@ -0,0 +1,43 @@
+use alloy::primitives::U256;
+use types::{ChainProofs, InjectedState};
+
+use crate::test_utils::{run, run_tampered};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_logs_by_filter_get_logs() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_logs_by_filter_get_logs.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_logs_by_filter_rejects_omitted_log() {
+    dotenvy::dotenv().ok();
+    // Without the receipt holding the matching log the hint returns the block without it, which the
+    // cumulative gas check of the block has to reject
+    let omitted_receipt = U256::from_be_slice(&alloy::rlp::encode(U256::from(180)));
+    let result = run_tampered(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_logs_by_filter_get_logs.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+        |input| {
+            for chain_proofs in &mut input.chain_proofs {
+                if let ChainProofs::Evm { proofs, .. } = chain_proofs {
+                    proofs
+                        .transaction_receipts
+                        .retain(|receipt| !(receipt.proof.block_number == 7692344 && receipt.key == omitted_receipt));
+                }
+            }
+        },
+    )
+    .await;
+    assert!(result.is_err());
+}
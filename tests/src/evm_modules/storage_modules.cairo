@@ -22,3 +22,55 @@ mod evm_storage_get_slot {
         )
     }
 }
+
+#[starknet::contract]
+mod evm_storage_get_unset_slot {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::storage::{StorageImpl, StorageKey, StorageTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        // A slot never written by the contract, read through the exclusion proof as zero
+        assert!(
+            hdp
+                .evm
+                .storage_get_slot(
+                    @StorageKey {
+                        chain_id: 11155111,
+                        block_number: 7692344,
+                        address: 0x75cec1db9dceb703200eaa6595f66885c962b920,
+                        storage_slot: 0xdeadbeef,
+                    },
+                ) == u256 { low: 0x0, high: 0x0 },
+        )
+    }
+}
+
+#[starknet::contract]
+mod evm_storage_get_fresh_account_slot {
+    use hdp_cairo::HDP;
+    use hdp_cairo::evm::storage::{StorageImpl, StorageKey, StorageTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        // The storage of an address without any account is the empty trie, proven without any node
+        assert!(
+            hdp
+                .evm
+                .storage_get_slot(
+                    @StorageKey {
+                        chain_id: 11155111,
+                        block_number: 7692344,
+                        address: 0x9a3F8e1d2C4b5A6978e0D1c2B3a4F5e6D7c8B9a0,
+                        storage_slot: 0x1,
+                    },
+                ) == u256 { low: 0x0, high: 0x0 },
+        )
+    }
+}
@@ -14,3 +14,29 @@ async fn test_tests_evm_get_slot() {
     )
     .await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_get_unset_slot() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_storage_get_unset_slot.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_tests_evm_get_fresh_account_slot() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_evm_storage_get_fresh_account_slot.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
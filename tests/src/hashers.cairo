@@ -1,3 +1,4 @@
 pub mod keccak_modules;
 pub mod pedersen_modules;
 pub mod poseidon_modules;
+pub mod results_modules;
@@ -3,6 +3,7 @@ use alloy::{
     primitives::{keccak256, FixedBytes, U256},
 };
 use cairo_vm::Felt252;
+use hints::merkle::is_left_smaller;
 use starknet_crypto::{pedersen_hash, poseidon_hash_many};
 
 pub mod tests_runner;
@@ -41,3 +42,25 @@ async fn test_perdersen_hash_rust() {
     assert!(hash1 == Felt252::from_hex("0x5bb9440e27889a364bcb678b1f679ecd1347acdedcbf36e83494f857cc58026").unwrap());
     assert!(hash2 == Felt252::from_hex("0x262697b88544f733e5c6907c3e1763131e9f14c51ee7951258abbfb29415fbf").unwrap());
 }
+
+#[tokio::test]
+async fn test_results_pair_order_keeps_leading_zeros_rust() {
+    // Results tree leaves are double keccak hashes of the little endian result, split into little endian halves
+    let leaf = |value: u64| {
+        let digest = keccak256(keccak256(Felt252::from(value).to_bytes_le()));
+        (
+            digest,
+            [
+                Felt252::from_bytes_le_slice(&digest[..16]),
+                Felt252::from_bytes_le_slice(&digest[16..]),
+            ],
+        )
+    };
+    let (digest1, uint1) = leaf(1);
+    let (digest246, uint246) = leaf(246);
+
+    assert!(digest246[15] == 0, "the low half of the leaf of 246 has a leading zero byte");
+    assert!(digest1 < digest246);
+    assert!(is_left_smaller(uint1, uint246));
+    assert!(!is_left_smaller(uint246, uint1));
+}
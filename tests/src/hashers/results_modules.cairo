@@ -0,0 +1,13 @@
+#[starknet::contract]
+mod hashers_results_leading_zero_leaf {
+    use hdp_cairo::HDP;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) -> Array<felt252> {
+        // The low half of the leaf of 246 has a leading zero byte, which must not change its order against the leaf of 1
+        array![246, 1]
+    }
+}
@@ -40,3 +40,16 @@ async fn test_pedersen_hash() {
     )
     .await
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_results_tree_leading_zero_leaf() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_hashers_results_leading_zero_leaf.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
@@ -2,6 +2,10 @@
 pub mod boundary_value_testing;
 pub mod cross_trie_collision_test;
 
+// Delete tests
+pub mod delete_existing_key;
+pub mod delete_unset_key;
+
 // Invariant tests
 pub mod edge_inputs_max;
 pub mod empty_trie_operations;
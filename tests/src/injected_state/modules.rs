@@ -53,6 +53,34 @@ async fn test_injected_state_cross_trie_collision_test(_: &mut StateServerCtx) {
     .await
 }
 
+// Delete tests
+#[test_context(StateServerCtx)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_injected_state_delete_existing_key(_: &mut StateServerCtx) {
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_delete_existing_key.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        serde_json::from_slice(include_bytes!("modules/delete_existing_key_injected_state.json")).unwrap(),
+    )
+    .await
+}
+
+#[test_context(StateServerCtx)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[should_panic(expected = "it is not set in trie")]
+async fn test_injected_state_delete_unset_key(_: &mut StateServerCtx) {
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../../target/dev/tests_delete_unset_key.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        serde_json::from_slice(include_bytes!("modules/delete_unset_key_injected_state.json")).unwrap(),
+    )
+    .await
+}
+
 // Invariant tests
 #[test_context(StateServerCtx)]
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
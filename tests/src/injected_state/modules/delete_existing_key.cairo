@@ -0,0 +1,39 @@
+#[starknet::contract]
+mod delete_existing_key {
+    use hdp_cairo::HDP;
+    use hdp_cairo::injected_state::state::{InjectedStateMemorizerImpl, InjectedStateMemorizerTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) -> Array<felt252> {
+        let label = 'delete_existing_key';
+        let k1 = 0x1;
+        let k2 = 0x2;
+        let v1 = 0x1;
+        let v2 = 0x2;
+
+        // Start with empty trie
+        let initial_root = hdp.injected_state.read_injected_state_trie_root(label).unwrap();
+        assert!(initial_root == 0x0, "Initial trie root should be 0x0");
+
+        let root1 = hdp.injected_state.write_key(label, k1, v1);
+        let root2 = hdp.injected_state.write_key(label, k2, v2);
+        assert!(root1 != root2, "Writing a second key should change the root");
+
+        // Deleting k2 restores the trie holding only k1
+        let root3 = hdp.injected_state.delete_key(label, k2);
+        assert!(root3 == root1, "Deleting the second key should restore the previous root");
+
+        // The deleted key is read back through a non-inclusion proof
+        let deleted_value = hdp.injected_state.read_key(label, k2);
+        assert!(deleted_value.is_none(), "Deleted key should return None");
+
+        // The other key is untouched
+        let existing_value = hdp.injected_state.read_key(label, k1).unwrap();
+        assert!(existing_value == v1, "Remaining key should return correct value");
+
+        array![root1, root2, root3, existing_value]
+    }
+}
@@ -0,0 +1,23 @@
+#[starknet::contract]
+mod delete_unset_key {
+    use hdp_cairo::HDP;
+    use hdp_cairo::injected_state::state::{InjectedStateMemorizerImpl, InjectedStateMemorizerTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) -> Array<felt252> {
+        let label = 'delete_unset_key';
+        let k1 = 0x1;
+        let k2 = 0x2;
+        let v1 = 0x1;
+
+        let root = hdp.injected_state.write_key(label, k1, v1);
+
+        // k2 was never written, so deleting it must fail
+        let root_after_delete = hdp.injected_state.delete_key(label, k2);
+
+        array![root, root_after_delete]
+    }
+}
@@ -19,75 +19,83 @@ pub mod unconstrained;
 #[cfg(test)]
 pub mod test_state_server;
 
+#[cfg(test)]
+pub mod budget;
+
+#[cfg(test)]
+pub mod rpc_fixture;
+
+#[cfg(all(test, feature = "stwo"))]
+pub mod prove;
+
 #[cfg(test)]
 mod test_utils {
-    use std::{env, path::PathBuf};
+    use std::{env, path::PathBuf, sync::Once};
 
     use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
     use cairo_vm::{
         cairo_run::CairoRunConfig,
-        types::{layout_name::LayoutName, program::Program},
-        vm::runners::cairo_runner::{CairoRunner, RunnerMode},
+        types::{layout_name::LayoutName, program::Program, relocatable::Relocatable},
+        vm::{
+            errors::vm_errors::VirtualMachineError,
+            runners::cairo_runner::{CairoRunner, RunnerMode},
+        },
+    };
+    use dry_hint_processor::{
+        rpc_fixture::{FixtureMode, RpcFixture, RPC_FIXTURE_PATH},
+        syscall_handler::{evm, injected_state, starknet, unconstrained},
     };
-    use dry_hint_processor::syscall_handler::{evm, injected_state, starknet, unconstrained};
     use fetcher::{parse_syscall_handler, Fetcher};
     use hints::vars;
     use indexer_client::models::{MMRDeploymentConfig, MMRHasherConfig};
     use syscall_handler::{SyscallHandler, SyscallHandlerWrapper};
     use tracing::debug;
-    use types::{
-        ChainProofs, HDPDryRunInput, HDPInput, InjectedState, ETHEREUM_MAINNET_CHAIN_ID, ETHEREUM_TESTNET_CHAIN_ID,
-        OPTIMISM_MAINNET_CHAIN_ID, OPTIMISM_TESTNET_CHAIN_ID, STARKNET_MAINNET_CHAIN_ID, STARKNET_TESTNET_CHAIN_ID,
-    };
+    use types::{budget::ResourceBudget, error::Error, results::HDPResults, HDPDryRunInput, HDPInput, HDPOutput, InjectedState};
+
+    /// Replays the rpc responses committed in `tests/fixtures/rpc` in the dry runs, unless `HDP_RPC_FIXTURE` selects
+    /// another fixture. Without recorded responses the dry runs query the rpc endpoints.
+    fn init_rpc_fixture() {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            if env::var(RPC_FIXTURE_PATH).is_ok() {
+                return;
+            }
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("rpc");
+            let recorded = std::fs::read_dir(&path)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .any(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                })
+                .unwrap_or(false);
+            if recorded {
+                RpcFixture::init_global(RpcFixture::open(path, FixtureMode::Replay).unwrap()).unwrap();
+            }
+        });
+    }
 
     pub async fn run(compiled_class: CasmContractClass, injected_state: InjectedState) {
-        // Init CairoRunConfig
-        let cairo_run_config = CairoRunConfig {
-            layout: LayoutName::all_cairo,
-            relocate_mem: true,
-            trace_enabled: true,
-            ..Default::default()
-        };
+        run_tampered(compiled_class, injected_state, |_| {}).await.unwrap()
+    }
 
-        let runner_mode = if cairo_run_config.proof_mode {
-            RunnerMode::ProofModeCairo1
-        } else {
-            RunnerMode::ExecutionMode
-        };
+    /// Runs the module like [`run`], letting `tamper` alter the input collected by the dry run before the sound run.
+    /// Returns the error of a sound run rejecting the altered input.
+    pub async fn run_tampered(
+        compiled_class: CasmContractClass,
+        injected_state: InjectedState,
+        tamper: impl FnOnce(&mut HDPInput),
+    ) -> Result<(), VirtualMachineError> {
+        init_rpc_fixture();
 
-        // Locate the compiled program file in the `OUT_DIR` folder.
-        let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+        let (cairo_run_config, runner_mode, out_dir) = run_config();
+        let (mut cairo_runner, end) = dry_runner();
 
+        // Run the Cairo VM
         let program_inputs = HDPDryRunInput {
             params: vec![],
             compiled_class: compiled_class.clone(),
             injected_state: injected_state.clone(),
         };
-
-        // Load the Program
-        let program = Program::from_bytes(
-            &std::fs::read(out_dir.join("cairo").join("dry_run_compiled.json")).unwrap(),
-            Some(cairo_run_config.entrypoint),
-        )
-        .unwrap();
-
-        // Init cairo runner
-        let mut cairo_runner = CairoRunner::new_v2(
-            &program,
-            cairo_run_config.layout,
-            None,
-            runner_mode.clone(),
-            cairo_run_config.trace_enabled,
-            cairo_run_config.disable_trace_padding,
-        )
-        .unwrap();
-
-        // Init the Cairo VM
-        let end = cairo_runner
-            .initialize(cairo_run_config.allow_missing_builtins.unwrap_or(false))
-            .unwrap();
-
-        // Run the Cairo VM
         let mut hint_processor = dry_hint_processor::CustomHintProcessor::new(program_inputs);
         cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
 
@@ -115,42 +123,17 @@ mod test_utils {
         let proof_keys = parse_syscall_handler(syscall_handler).unwrap();
 
         let fetcher = Fetcher::new(&proof_keys, MMRHasherConfig::default(), MMRDeploymentConfig::default());
-        let (
-            eth_proofs_mainnet,
-            eth_proofs_sepolia,
-            starknet_proofs_mainnet,
-            starknet_proofs_sepolia,
-            optimism_proofs_mainnet,
-            optimism_proofs_sepolia,
-            unconstrained,
-            state_proofs,
-        ) = tokio::try_join!(
-            fetcher.collect_evm_proofs(ETHEREUM_MAINNET_CHAIN_ID),
-            fetcher.collect_evm_proofs(ETHEREUM_TESTNET_CHAIN_ID),
-            fetcher.collect_starknet_proofs(STARKNET_MAINNET_CHAIN_ID),
-            fetcher.collect_starknet_proofs(STARKNET_TESTNET_CHAIN_ID),
-            fetcher.collect_evm_proofs(OPTIMISM_MAINNET_CHAIN_ID),
-            fetcher.collect_evm_proofs(OPTIMISM_TESTNET_CHAIN_ID),
-            fetcher.collect_unconstrained_data(),
-            fetcher.collect_state_proofs(),
-        )
-        .unwrap();
+        let proofs_data = fetcher.collect_proofs().await.unwrap();
 
-        let program_inputs = HDPInput {
-            chain_proofs: vec![
-                ChainProofs::EthereumMainnet(eth_proofs_mainnet),
-                ChainProofs::EthereumSepolia(eth_proofs_sepolia),
-                ChainProofs::StarknetMainnet(starknet_proofs_mainnet),
-                ChainProofs::StarknetSepolia(starknet_proofs_sepolia),
-                ChainProofs::OptimismMainnet(optimism_proofs_mainnet),
-                ChainProofs::OptimismSepolia(optimism_proofs_sepolia),
-            ],
+        let mut program_inputs = HDPInput {
+            chain_proofs: proofs_data.chain_proofs,
             params: vec![],
             compiled_class,
-            state_proofs,
+            state_proofs: proofs_data.state_proofs,
             injected_state,
-            unconstrained,
+            unconstrained: proofs_data.unconstrained,
         };
+        tamper(&mut program_inputs);
 
         // Load the Program
         let program = Program::from_bytes(
@@ -177,8 +160,90 @@ mod test_utils {
 
         // Run the Cairo VM
         let mut hint_processor = sound_hint_processor::CustomHintProcessor::new(program_inputs);
-        cairo_runner.run_until_pc(end, &mut hint_processor).unwrap();
+        cairo_runner.run_until_pc(end, &mut hint_processor)?;
 
         debug!("Sound run completed successfully.");
+
+        // Every module result must be provable against the output tree root
+        let segment_index = cairo_runner.vm.get_output_builtin_mut().unwrap().base();
+        let segment_size = cairo_runner.vm.segments.compute_effective_sizes()[segment_index];
+        let output = HDPOutput::from_iter(
+            cairo_runner
+                .vm
+                .get_range(Relocatable::from((segment_index as isize, 0)), segment_size)
+                .into_iter()
+                .map(|v| v.unwrap().get_int().unwrap()),
+        );
+        let results = HDPResults::new(&output, hint_processor.module_results().to_vec()).unwrap();
+        assert!(results.leaves.iter().all(|leaf| leaf.verify(results.output_tree_root)));
+        Ok(())
+    }
+
+    /// Dry runs the module under `budget`, returning the breach that aborted the run. Builtin usage is only checked
+    /// while the run executes hints, leaving out the check the dry run does once the run finished.
+    pub fn dry_run_with_budget(compiled_class: CasmContractClass, budget: ResourceBudget) -> Result<(), Error> {
+        init_rpc_fixture();
+
+        let (mut cairo_runner, end) = dry_runner();
+
+        let program_inputs = HDPDryRunInput {
+            params: vec![],
+            compiled_class,
+            injected_state: InjectedState::default(),
+        };
+        let mut hint_processor = dry_hint_processor::CustomHintProcessor::new(program_inputs).with_budget(budget);
+        cairo_runner
+            .run_until_pc(end, &mut hint_processor)
+            .map_err(|e| hint_processor.budget().exceeded().unwrap_or(Error::VirtualMachine(e)))
+    }
+
+    fn run_config() -> (CairoRunConfig<'static>, RunnerMode, PathBuf) {
+        let cairo_run_config = CairoRunConfig {
+            layout: LayoutName::all_cairo,
+            relocate_mem: true,
+            trace_enabled: true,
+            ..Default::default()
+        };
+
+        let runner_mode = if cairo_run_config.proof_mode {
+            RunnerMode::ProofModeCairo1
+        } else {
+            RunnerMode::ExecutionMode
+        };
+
+        // Locate the compiled program file in the `OUT_DIR` folder.
+        let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+
+        (cairo_run_config, runner_mode, out_dir)
+    }
+
+    /// Loads the dry run program and initializes a runner for it, returning the runner and the pc it ends at.
+    fn dry_runner() -> (CairoRunner, Relocatable) {
+        let (cairo_run_config, runner_mode, out_dir) = run_config();
+
+        // Load the Program
+        let program = Program::from_bytes(
+            &std::fs::read(out_dir.join("cairo").join("dry_run_compiled.json")).unwrap(),
+            Some(cairo_run_config.entrypoint),
+        )
+        .unwrap();
+
+        // Init cairo runner
+        let mut cairo_runner = CairoRunner::new_v2(
+            &program,
+            cairo_run_config.layout,
+            None,
+            runner_mode,
+            cairo_run_config.trace_enabled,
+            cairo_run_config.disable_trace_padding,
+        )
+        .unwrap();
+
+        // Init the Cairo VM
+        let end = cairo_runner
+            .initialize(cairo_run_config.allow_missing_builtins.unwrap_or(false))
+            .unwrap();
+
+        (cairo_runner, end)
     }
 }
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use cairo_vm::{types::program::Program, Felt252};
+use sound_run::{
+    cairo_run_config,
+    prove::{prove, prover_input_from_runner, verify, ExpectedOutput},
+    HDP_COMPILED_JSON,
+};
+use types::{budget::ResourceBudget, error::Error, HDPInput, InjectedState};
+
+/// Proves a proof mode sound run of a module without syscalls, then verifies the proof against the results and the
+/// output of the run.
+#[test]
+fn test_prove_and_verify_sound_run() {
+    dotenvy::dotenv().ok();
+    let (cairo_runner, output, results) = sound_run::run(
+        PathBuf::from(HDP_COMPILED_JSON),
+        cairo_run_config(true),
+        HDPInput {
+            params: vec![],
+            compiled_class: serde_json::from_slice(include_bytes!(
+                "../../target/dev/tests_hashers_results_leading_zero_leaf.compiled_contract_class.json"
+            ))
+            .unwrap(),
+            injected_state: InjectedState::default(),
+            chain_proofs: vec![],
+            state_proofs: vec![],
+            unconstrained: Default::default(),
+        },
+        ResourceBudget::default(),
+    )
+    .unwrap();
+
+    // Round trip the proof through the artifact `hdp prove` writes
+    let proof = serde_json::to_string(&prove(prover_input_from_runner(&cairo_runner)).unwrap()).unwrap();
+    let program = Program::from_bytes(&std::fs::read(HDP_COMPILED_JSON).unwrap(), Some(cairo_run_config(true).entrypoint)).unwrap();
+
+    let proven = verify(serde_json::from_str(&proof).unwrap(), &program, &ExpectedOutput::Results(results)).unwrap();
+    assert_eq!(proven, output);
+
+    let mut other = output.clone();
+    other.task_hash_low = other.task_hash_low + Felt252::from(1_u64);
+    assert!(matches!(
+        verify(serde_json::from_str(&proof).unwrap(), &program, &ExpectedOutput::Output(other)),
+        Err(Error::ProofOutputMismatch)
+    ));
+}
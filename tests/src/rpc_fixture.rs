@@ -0,0 +1,60 @@
+use std::{env, path::Path, process::Command};
+
+use dry_hint_processor::rpc_fixture::{RPC_FIXTURE_MODE, RPC_FIXTURE_PATH};
+use types::InjectedState;
+
+use crate::test_utils::run;
+
+/// Nothing listens on the discard port, so any rpc call that is not replayed fails.
+const CLOSED_RPC_URL: &str = "http://127.0.0.1:9";
+
+/// Runs [`test_rpc_fixture_module`] in a child process of the test binary, which reads its fixture from the env vars
+/// like the CLI does and gets its own process wide fixture.
+fn run_module_with_fixture(path: &Path, mode: &str, rpc_url: Option<&str>) {
+    let mut command = Command::new(env::current_exe().unwrap());
+    command
+        .args(["--exact", "rpc_fixture::test_rpc_fixture_module", "--ignored", "--nocapture"])
+        .env(RPC_FIXTURE_PATH, path)
+        .env(RPC_FIXTURE_MODE, mode);
+    if let Some(rpc_url) = rpc_url {
+        for var in [
+            "RPC_URL_HERODOTUS_INDEXER",
+            "RPC_URL_ETHEREUM_MAINNET",
+            "RPC_URL_ETHEREUM_TESTNET",
+            "RPC_URL_OPTIMISM_MAINNET",
+            "RPC_URL_OPTIMISM_TESTNET",
+            "RPC_URL_STARKNET_MAINNET",
+            "RPC_URL_STARKNET_TESTNET",
+        ] {
+            command.env(var, rpc_url);
+        }
+    }
+    let status = command.status().unwrap();
+    assert!(status.success(), "{} run with the rpc fixture failed", mode);
+}
+
+#[test]
+fn test_rpc_fixture_replays_run_offline() {
+    let path = env::temp_dir().join(format!("hdp_rpc_fixture_e2e_{}", std::process::id()));
+
+    run_module_with_fixture(&path, "record", None);
+    run_module_with_fixture(&path, "replay", Some(CLOSED_RPC_URL));
+
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+/// Dry runs, fetches the proofs and sound runs a module touching every evm proof kind, through the fixture selected
+/// by the env vars.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[ignore = "run by test_rpc_fixture_replays_run_offline"]
+async fn test_rpc_fixture_module() {
+    dotenvy::dotenv().ok();
+    run(
+        serde_json::from_slice(include_bytes!(
+            "../../target/dev/tests_evm_fetcher_many_keys_same_header.compiled_contract_class.json"
+        ))
+        .unwrap(),
+        InjectedState::default(),
+    )
+    .await
+}
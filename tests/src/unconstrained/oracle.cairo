@@ -0,0 +1,44 @@
+#[starknet::contract]
+mod unconstrained_sort_permutation {
+    use hdp_cairo::HDP;
+    use hdp_cairo::unconstrained::oracle::UnconstrainedOracleTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let permutation = hdp.sort_permutation(array![30, 10, 20, 10].span());
+        assert!(permutation == array![1, 3, 2, 0].span());
+    }
+}
+
+#[starknet::contract]
+mod unconstrained_sort_permutation_empty {
+    use hdp_cairo::HDP;
+    use hdp_cairo::unconstrained::oracle::UnconstrainedOracleTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let permutation = hdp.sort_permutation(array![].span());
+        assert!(permutation.len() == 0);
+    }
+}
+
+#[starknet::contract]
+mod unconstrained_mod_inverse {
+    use hdp_cairo::HDP;
+    use hdp_cairo::unconstrained::oracle::UnconstrainedOracleTrait;
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    pub fn main(ref self: ContractState, hdp: HDP) {
+        let inverse = hdp.mod_inverse(3, 11_u256.try_into().unwrap());
+        assert!(inverse == 4);
+    }
+}